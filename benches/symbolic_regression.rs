@@ -13,19 +13,17 @@ use mycoforge::optimizers::ga::{EABuilder, EAComponents};
 
 use mycoforge::prelude::tree_gp::*;
 
-fn x(data: &[&[f64]]) -> Vec<f64> { return data[0].to_vec();}
-
 pub fn benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("symbolic_regression");
 
     let mut rng = StdRng::seed_from_u64(42);
     
     let operators = OperatorsBuilder::default()
-        .add_operator("+", add, 2, 1.0 / 5.0).expect("Failed to add an operator!")
-        .add_operator("-", sub, 2, 1.0 / 5.0).expect("Failed to add an operator!")
-        .add_operator("*", mul, 2, 1.0 / 5.0).expect("Failed to add an operator!")
-        .add_operator("/", div, 2, 1.0 / 5.0).expect("Failed to add an operator!")
-        .add_operator("x", x, 0, 1.0 / 5.0).expect("Failed to add an operator!")
+        .add_registered("+", "add", 1.0 / 4.0).expect("Failed to add an operator!")
+        .add_registered("-", "sub", 1.0 / 4.0).expect("Failed to add an operator!")
+        .add_registered("*", "mul", 1.0 / 4.0).expect("Failed to add an operator!")
+        .add_registered("/", "protected_div", 1.0 / 4.0).expect("Failed to add an operator!")
+        .add_variable("x", 1.0).expect("Failed to add an operator!")
         .build().expect("Failed to build operators!");
 
     let sampler = operators.sampler();
@@ -39,7 +37,7 @@ pub fn benchmark(c: &mut Criterion) {
             &(min_depth, max_depth),
             |b, &(min, max)| {
                 let init_scheme = Grow::new(min, max);
-                b.iter(|| init_scheme.initialize(&mut rng, sampler));
+                b.iter(|| init_scheme.initialize(&mut rng, &sampler));
             }
         );
     }
@@ -47,11 +45,11 @@ pub fn benchmark(c: &mut Criterion) {
     for (min_depth, max_depth) in depths.clone() {
         let init_scheme = Grow::new(min_depth, max_depth);
         let mutation_scheme = SubtreeMutation::new(1.0, (1, 2)).expect("Failed to create SubtreeMutation");
-        let trees = (0..pool_size).map(|_| init_scheme.initialize(&mut rng, sampler)).collect::<Vec<TreeGenotype>>();
+        let trees = (0..pool_size).map(|_| init_scheme.initialize(&mut rng, &sampler)).collect::<Vec<TreeGenotype>>();
         group.bench_function(format!("mutation/d{}_{}", min_depth, max_depth),
             |b| b.iter(|| {
-                let idx = rng.gen_range(0..trees.len());
-                mutation_scheme.variate(&mut rng, &trees[idx], sampler);
+                let idx = rng.random_range(0..trees.len());
+                mutation_scheme.variate(&mut rng, &trees[idx], &sampler);
             })
         );
     }
@@ -59,29 +57,27 @@ pub fn benchmark(c: &mut Criterion) {
     for (min_depth, max_depth) in depths.clone() {
         let init_scheme = Grow::new(min_depth, max_depth);
         let crossover_scheme = SubtreeCrossover::new(1.0).expect("Failed to create SubtreeCrossover");
-        let trees = (0..pool_size).map(|_| init_scheme.initialize(&mut rng, sampler)).collect::<Vec<TreeGenotype>>();
+        let trees = (0..pool_size).map(|_| init_scheme.initialize(&mut rng, &sampler)).collect::<Vec<TreeGenotype>>();
         group.bench_function(format!("crossover/d{}_{}", min_depth, max_depth),
             |b| b.iter(|| {
-                let idx1 = rng.gen_range(0..trees.len());
-                let idx2 = rng.gen_range(0..trees.len());
-                crossover_scheme.variate(&mut rng, &trees[idx1], &trees[idx2], sampler);
+                let idx1 = rng.random_range(0..trees.len());
+                let idx2 = rng.random_range(0..trees.len());
+                crossover_scheme.variate(&mut rng, &trees[idx1], &trees[idx2], &sampler);
             })
         );
     }
     let feature_names = ["x"].iter().map(|&s| s.to_string()).collect::<Vec<String>>();
-    let no_dims = 2;
     let xs: Vec<f64> = vec![0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
     let ys = xs.iter().map(|&x| x.powi(2) + x ).collect::<Vec<f64>>();
-    let test_data = vec![xs, ys];
 
-    let data = Dataset::new(feature_names, no_dims, test_data.clone(), test_data);
+    let data = Dataset::new(feature_names, "y".to_string(), vec![xs], ys);
     let map = operators.create_map();
 
     for (min_depth, max_depth) in depths {
         let init_scheme = Grow::new(min_depth, max_depth);
-        let evaluator = MeanSquared::new();
+        let evaluator = MSE::new();
 
-        let trees = (0..pool_size/2).map(|_| init_scheme.initialize(&mut rng, sampler)).collect::<Vec<TreeGenotype>>();
+        let trees = (0..pool_size/2).map(|_| init_scheme.initialize(&mut rng, &sampler)).collect::<Vec<TreeGenotype>>();
         let trees = trees.iter().cycle().take(pool_size).cloned().collect::<Vec<TreeGenotype>>();
 
         group.bench_function(format!("evaluation/d{}_{}", min_depth, max_depth),
@@ -92,6 +88,39 @@ pub fn benchmark(c: &mut Criterion) {
             }) 
         );
 
+        let compiler = Compiler::new(&operators);
+        let programs = trees.iter().map(|tree| compiler.compile(tree).expect("Failed to compile tree!")).collect::<Vec<Program>>();
+        let (features, targets) = data.data();
+        let mut workspace = Workspace::new();
+
+        group.bench_function(format!("evaluation_compiled/d{}_{}", min_depth, max_depth),
+            |b| b.iter(|| {
+                for program in &programs {
+                    let predictions = program.execute(features, &mut workspace);
+                    std::hint::black_box(predictions.iter().zip(targets.iter()).map(|(p, t)| (p - t).powi(2)).sum::<f64>() / targets.len() as f64);
+                }
+            })
+        );
+
+        group.bench_function(format!("evaluation_compile_and_run/d{}_{}", min_depth, max_depth),
+            |b| b.iter(|| {
+                for tree in &trees {
+                    let program = compiler.compile(tree).expect("Failed to compile tree!");
+                    let predictions = program.execute(features, &mut workspace);
+                    std::hint::black_box(predictions.iter().zip(targets.iter()).map(|(p, t)| (p - t).powi(2)).sum::<f64>() / targets.len() as f64);
+                }
+            })
+        );
+
+        let compiled = Compiled::new(MSE::new(), &operators);
+        group.bench_function(format!("evaluation_compiled_evaluator/d{}_{}", min_depth, max_depth),
+            |b| b.iter(|| {
+                for tree in &trees {
+                    compiled.evaluate(tree, &data, &map);
+                }
+            })
+        );
+
         group.bench_function(format!("evaluation_hashed/d{}_{}", min_depth, max_depth),
             |b| b.iter(|| {
                 let mut cache: HashMap<TreeGenotype, f64> = HashMap::new();
@@ -118,7 +147,48 @@ pub fn benchmark(c: &mut Criterion) {
                     init: Grow,
                     mutation: SubtreeMutation,
                     crossover: SubtreeCrossover,
-                    evaluation: MeanSquared,
+                    evaluation: MSE,
+                    selection: TournamentSelection
+                },
+                operators: operators,
+                config: {
+                    init: Grow::new(2, 4),
+                    mutation: SubtreeMutation::new(0.1, (1, 2)).expect("Failed to create SubtreeMutation"),
+                    crossover: SubtreeCrossover::new(0.9).expect("Failed to create SubtreeCrossover"),
+                    evaluation: MSE::new(),
+                    selection: TournamentSelection::new(5)
+                }
+            };
+            let feature_names = ["x"].iter().map(|&s| s.to_string()).collect::<Vec<String>>();
+            let xs: Vec<f64> = vec![0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
+            let ys = xs.iter().map(|&x| x.powi(2) + x ).collect::<Vec<f64>>();
+
+            let data = Dataset::new(feature_names, "y".to_string(), vec![xs], ys);
+
+            let mut rng = StdRng::seed_from_u64(42);
+            
+            let population_size = 500;
+            let initial_population = ea.init_population(&mut rng, population_size);
+            let mut fitnesses = initial_population.iter().map(|ind| ea.evaluator().evaluate(ind, &data, ea.map())).collect::<Vec<f64>>();
+
+            let mut population = TreeIndividual::from_vecs(&initial_population, &fitnesses);
+            for _ in 0..100 {
+                let next_population = ea.optimize(&mut rng, &population);
+                fitnesses = next_population.iter().map(|ind| ea.evaluator().evaluate(ind, &data, ea.map())).collect::<Vec<f64>>();
+                population = TreeIndividual::from_vecs(&next_population, &fitnesses);
+            }
+        });
+    });
+    group.bench_function("f1(x)=x^2+x/compiled", |b| {
+        b.iter(|| {
+            let ea = ea_components! {
+                genotype: TreeGenotype,
+                individual: TreeIndividual<TreeGenotype>,
+                components: {
+                    init: Grow,
+                    mutation: SubtreeMutation,
+                    crossover: SubtreeCrossover,
+                    evaluation: Compiled<MSE>,
                     selection: TournamentSelection
                 },
                 operators: operators,
@@ -126,17 +196,15 @@ pub fn benchmark(c: &mut Criterion) {
                     init: Grow::new(2, 4),
                     mutation: SubtreeMutation::new(0.1, (1, 2)).expect("Failed to create SubtreeMutation"),
                     crossover: SubtreeCrossover::new(0.9).expect("Failed to create SubtreeCrossover"),
-                    evaluation: MeanSquared::new(),
+                    evaluation: Compiled::new(MSE::new(), &operators),
                     selection: TournamentSelection::new(5)
                 }
             };
             let feature_names = ["x"].iter().map(|&s| s.to_string()).collect::<Vec<String>>();
-            let no_dims = 2;
             let xs: Vec<f64> = vec![0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
             let ys = xs.iter().map(|&x| x.powi(2) + x ).collect::<Vec<f64>>();
-            let test_data = vec![xs, ys];
 
-            let data = Dataset::new(feature_names, no_dims, test_data.clone(), test_data);
+            let data = Dataset::new(feature_names, "y".to_string(), vec![xs], ys);
 
             let mut rng = StdRng::seed_from_u64(42);
             
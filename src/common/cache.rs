@@ -0,0 +1,161 @@
+//! Fitness cache for memoized evaluation.
+//!
+//! This module provides [`FitnessCache`], a bounded cache with least-recently-used (LRU) eviction
+//! and hit/miss statistics. Entries are keyed by genotype together with dataset identity (see
+//! [`Data::fingerprint`][`crate::common::traits::Data::fingerprint`]), so the same cache can be
+//! safely shared between different datasets.
+
+use std::collections::{BTreeMap, HashMap};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::common::traits::Data;
+
+/// Computes identity of a dataset from its names and values.
+///
+/// # Arguments
+/// * `data: &D` - dataset implementing [`Data`][`crate::common::traits::Data`] trait
+///
+/// # Returns
+/// * `u64` - hash of feature names, target name and bit patterns of all values
+pub fn fingerprint<D: Data + ?Sized>(data: &D) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    let (feature_names, target_name) = data.names();
+    let (features, targets) = data.data();
+
+    feature_names.hash(&mut hasher);
+    target_name.hash(&mut hasher);
+    for column in features.iter().chain(std::iter::once(targets)) {
+        column.len().hash(&mut hasher);
+        column.iter().for_each(|value| value.to_bits().hash(&mut hasher));
+    }
+    return hasher.finish();
+}
+
+/// Bounded fitness cache with LRU eviction.
+///
+/// # Fields
+/// * `capacity: usize` - maximum number of stored entries
+/// * `entries: HashMap<(u64, G), (f64, u64)>` - fitness and last use stamp keyed by dataset
+///   fingerprint and genotype
+/// * `order: BTreeMap<u64, (u64, G)>` - keys ordered from least to most recently used
+/// * `clock: u64` - monotonically increasing use stamp
+/// * `hits: usize`, `misses: usize`, `evictions: usize` - cache statistics
+///
+/// # Examples
+/// ```
+/// use mycoforge::common::cache::FitnessCache;
+///
+/// let mut cache: FitnessCache<String> = FitnessCache::new(2);
+/// cache.insert(0, "a".to_string(), 1.0);
+/// cache.insert(0, "b".to_string(), 2.0);
+///
+/// assert_eq!(cache.get(0, &"a".to_string()), Some(1.0));
+/// assert_eq!(cache.get(1, &"a".to_string()), None); // different dataset
+///
+/// cache.insert(0, "c".to_string(), 3.0); // evicts "b", least recently used
+///
+/// assert_eq!(cache.get(0, &"b".to_string()), None);
+/// assert_eq!((cache.hits(), cache.misses(), cache.evictions()), (1, 2, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FitnessCache<G> {
+    capacity: usize,
+    entries: HashMap<(u64, G), (f64, u64)>,
+    order: BTreeMap<u64, (u64, G)>,
+    clock: u64,
+    hits: usize,
+    misses: usize,
+    evictions: usize
+}
+
+impl<G> FitnessCache<G> {
+    /// Creates new empty cache.
+    ///
+    /// # Arguments
+    /// * `capacity: usize` - maximum number of stored entries, 0 disables caching
+    pub fn new(capacity: usize) -> Self {
+        return Self {
+            capacity, entries: HashMap::new(), order: BTreeMap::new(),
+            clock: 0, hits: 0, misses: 0, evictions: 0
+        };
+    }
+
+    pub fn capacity(&self) -> usize { return self.capacity; }
+    pub fn len(&self) -> usize { return self.entries.len(); }
+    pub fn is_empty(&self) -> bool { return self.entries.is_empty(); }
+    pub fn hits(&self) -> usize { return self.hits; }
+    pub fn misses(&self) -> usize { return self.misses; }
+    pub fn evictions(&self) -> usize { return self.evictions; }
+
+    /// Returns ratio of hits to all lookups, 0.0 if there were no lookups.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 { return 0.0; }
+        return self.hits as f64 / lookups as f64;
+    }
+}
+
+impl<G: Hash + Eq + Clone> FitnessCache<G> {
+    /// Looks up fitness of genotype evaluated on dataset with given fingerprint and marks it as
+    /// recently used.
+    ///
+    /// # Arguments
+    /// * `dataset: u64` - dataset fingerprint
+    /// * `genotype: &G` - genotype to look up
+    ///
+    /// # Returns
+    /// * `Option<f64>` - cached fitness or `None`
+    pub fn get(&mut self, dataset: u64, genotype: &G) -> Option<f64> {
+        let key = (dataset, genotype.clone());
+        let Some((fitness, stamp)) = self.entries.get(&key).copied() else {
+            self.misses += 1;
+            return None;
+        };
+        self.hits += 1;
+        self.touch(key, fitness, stamp);
+        return Some(fitness);
+    }
+
+    /// Inserts fitness of genotype evaluated on dataset with given fingerprint, evicting least
+    /// recently used entry if the cache is full.
+    ///
+    /// # Arguments
+    /// * `dataset: u64` - dataset fingerprint
+    /// * `genotype: G` - evaluated genotype
+    /// * `fitness: f64` - computed fitness
+    pub fn insert(&mut self, dataset: u64, genotype: G, fitness: f64) {
+        if self.capacity == 0 { return; }
+
+        let key = (dataset, genotype);
+        if let Some(&(_, stamp)) = self.entries.get(&key) {
+            self.touch(key, fitness, stamp);
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.order.pop_first() {
+                self.entries.remove(&oldest);
+                self.evictions += 1;
+            }
+        }
+        self.clock += 1;
+        self.order.insert(self.clock, key.clone());
+        self.entries.insert(key, (fitness, self.clock));
+    }
+
+    /// Removes all entries and resets statistics.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.hits = 0;
+        self.misses = 0;
+        self.evictions = 0;
+    }
+
+    fn touch(&mut self, key: (u64, G), fitness: f64, stamp: u64) {
+        self.order.remove(&stamp);
+        self.clock += 1;
+        self.order.insert(self.clock, key.clone());
+        self.entries.insert(key, (fitness, self.clock));
+    }
+}
@@ -0,0 +1,55 @@
+//! Serialization of floats that may be non-finite.
+//!
+//! JSON has no representation of infinity or NaN, `serde_json` writes them as `null` and fails to
+//! read them back. Fitness values are routinely infinite (e.g. invalid individuals), so fields
+//! holding them use this module via `#[serde(with = "crate::common::float_serde")]`: in
+//! human-readable formats non-finite values are written as strings `"inf"`, `"-inf"` and `"NaN"`,
+//! binary formats store floats natively.
+use std::fmt;
+
+use serde::de::{self, Visitor};
+use serde::{Deserializer, Serializer};
+
+pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+    if value.is_finite() || !serializer.is_human_readable() { return serializer.serialize_f64(*value); }
+    return serializer.serialize_str(&value.to_string());
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    return deserializer.deserialize_any(FloatVisitor);
+}
+
+struct FloatVisitor;
+
+impl Visitor<'_> for FloatVisitor {
+    type Value = f64;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "a number or one of \"inf\", \"-inf\", \"NaN\"");
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<f64, E> { return Ok(value); }
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<f64, E> { return Ok(value as f64); }
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<f64, E> { return Ok(value as f64); }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<f64, E> {
+        return value.parse::<f64>().map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self));
+    }
+}
+
+/// Same encoding for vectors of floats, used via `#[serde(with = "crate::common::float_serde::vec")]`.
+pub mod vec {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Float(#[serde(with = "super")] f64);
+
+    pub fn serialize<S: Serializer>(values: &[f64], serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.collect_seq(values.iter().map(|&value| Float(value)));
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<f64>, D::Error> {
+        let values = Vec::<Float>::deserialize(deserializer)?;
+        return Ok(values.into_iter().map(|Float(value)| value).collect());
+    }
+}
@@ -3,7 +3,17 @@
 //! This module provides:
 //! - [`types`] - Common type definitions
 //! - [`traits`] - Core traits defining GP components
+//! - [`cache`] - Fitness cache for memoized evaluation
+//! - [`objectives`] - Optimization directions and multi-objective fitness
+//! - `float_serde` - Serialization of non-finite floats in human-readable formats
 
 pub mod types;
 
 pub mod traits;
+
+pub mod cache;
+
+pub mod objectives;
+
+#[cfg(feature = "serde")]
+pub(crate) mod float_serde;
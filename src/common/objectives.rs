@@ -0,0 +1,179 @@
+//! Multi-objective fitness.
+//!
+//! This module provides [`Direction`] of a single objective, also used by single-objective
+//! optimizers through [`Evaluator::direction`][`crate::common::traits::Evaluator::direction`],
+//! [`Objectives`] combining several
+//! scalar [`Evaluator`][`crate::common::traits::Evaluator`]s into a fitness vector and
+//! [`dominates`] comparing such vectors by Pareto dominance.
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::common::traits::{Data, Evaluator, Genotype};
+use crate::common::types::VectorFunction;
+
+/// Optimization direction of an objective.
+///
+/// # Variants
+/// * `Minimize` - lower values are better (errors, size)
+/// * `Maximize` - higher values are better (accuracy, likelihood)
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering;
+/// use mycoforge::common::objectives::Direction;
+///
+/// assert_eq!(Direction::Minimize.compare(1.0, 2.0), Ordering::Less);
+/// assert_eq!(Direction::Maximize.compare(1.0, 2.0), Ordering::Greater);
+/// // NaN is worse than anything
+/// assert_eq!(Direction::Maximize.compare(f64::NAN, f64::NEG_INFINITY), Ordering::Greater);
+/// assert_eq!(Direction::Maximize.best([1.0, f64::NAN, 3.0]), 3.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Direction {
+    #[default]
+    Minimize,
+    Maximize
+}
+
+impl Direction {
+    /// Maps value to a key where lower is better, NaN maps to infinity.
+    pub fn key(&self, value: f64) -> f64 {
+        if value.is_nan() { return f64::INFINITY; }
+        return match self {
+            Direction::Minimize => value,
+            Direction::Maximize => -value,
+        };
+    }
+
+    /// Compares two values, [`Ordering::Less`] means `a` is better than `b`.
+    pub fn compare(&self, a: f64, b: f64) -> Ordering {
+        if a.is_nan() || b.is_nan() { return a.is_nan().cmp(&b.is_nan()); }
+        return self.key(a).total_cmp(&self.key(b));
+    }
+
+    /// Returns whether `a` is strictly better than `b`.
+    pub fn is_better(&self, a: f64, b: f64) -> bool { return self.compare(a, b) == Ordering::Less; }
+
+    /// Returns the worst value apart from NaN, infinity when minimizing.
+    pub fn worst(&self) -> f64 {
+        return match self {
+            Direction::Minimize => f64::INFINITY,
+            Direction::Maximize => f64::NEG_INFINITY,
+        };
+    }
+
+    /// Returns the best of values, [`worst`][`Direction::worst`] if there are none besides NaN.
+    pub fn best(&self, values: impl IntoIterator<Item = f64>) -> f64 {
+        return values.into_iter().fold(self.worst(), |best, value| if self.is_better(value, best) { value } else { best });
+    }
+}
+
+/// Returns whether fitness vector `a` Pareto-dominates `b`: it is not worse in any objective and
+/// strictly better in at least one.
+///
+/// # Arguments
+/// * `a: &[f64]`, `b: &[f64]` - objective values, same length as `directions`
+/// * `directions: &[Direction]` - direction of every objective
+///
+/// # Examples
+/// ```
+/// use mycoforge::common::objectives::{dominates, Direction};
+///
+/// let directions = [Direction::Minimize, Direction::Maximize];
+///
+/// assert!(dominates(&[1.0, 5.0], &[2.0, 5.0], &directions));
+/// assert!(!dominates(&[1.0, 4.0], &[2.0, 5.0], &directions));
+/// assert!(!dominates(&[1.0, 5.0], &[1.0, 5.0], &directions));
+/// ```
+pub fn dominates(a: &[f64], b: &[f64], directions: &[Direction]) -> bool {
+    let mut strictly = false;
+    for ((&x, &y), direction) in a.iter().zip(b.iter()).zip(directions.iter()) {
+        match direction.compare(x, y) {
+            Ordering::Greater => return false,
+            Ordering::Less => strictly = true,
+            Ordering::Equal => {},
+        }
+    }
+    return strictly;
+}
+
+/// Objective evaluated on genotypes of type `G` with data `D`.
+type Objective<G, D> = Box<dyn Evaluator<G, D = D> + Send + Sync>;
+
+/// Set of objectives computing fitness vectors, one scalar evaluator per objective.
+///
+/// # Fields
+/// * `objectives: Vec<(Objective<G, D>, Direction)>` - evaluators and their directions
+///
+/// # Examples
+/// ```
+/// use mycoforge::common::objectives::{Direction, Objectives};
+/// use mycoforge::dataset::core::Dataset;
+/// use mycoforge::tree::core::tree::TreeGenotype;
+/// use mycoforge::tree::fitness::complexity::Size;
+/// use mycoforge::tree::fitness::evaluate::MSE;
+///
+/// let objectives = Objectives::<TreeGenotype, Dataset>::new()
+///     .add(MSE::new())
+///     .add_with_direction(Size::new(), Direction::Maximize);
+///
+/// assert_eq!(objectives.len(), 2);
+/// assert_eq!(objectives.directions(), vec![Direction::Minimize, Direction::Maximize]);
+/// ```
+pub struct Objectives<G: Genotype, D: Data> {
+    objectives: Vec<(Objective<G, D>, Direction)>
+}
+
+impl<G: Genotype, D: Data> Objectives<G, D> {
+    pub fn new() -> Self { return Self { objectives: Vec::new() }; }
+
+    /// Adds objective computed by given evaluator, optimized in the evaluator's own direction.
+    ///
+    /// # Arguments
+    /// * `evaluator: E` - scalar [`Evaluator`][`crate::common::traits::Evaluator`] of the objective
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<E: Evaluator<G, D = D> + Send + Sync + 'static>(self, evaluator: E) -> Self {
+        let direction = evaluator.direction();
+        return self.add_with_direction(evaluator, direction);
+    }
+
+    /// Adds objective computed by given evaluator, overriding the evaluator's own direction.
+    ///
+    /// # Arguments
+    /// * `evaluator: E` - scalar [`Evaluator`][`crate::common::traits::Evaluator`] of the objective
+    /// * `direction: Direction` - whether the objective is minimized or maximized
+    pub fn add_with_direction<E: Evaluator<G, D = D> + Send + Sync + 'static>(mut self, evaluator: E, direction: Direction) -> Self {
+        self.objectives.push((Box::new(evaluator), direction));
+        return self;
+    }
+
+    pub fn len(&self) -> usize { return self.objectives.len(); }
+    pub fn is_empty(&self) -> bool { return self.objectives.is_empty(); }
+    pub fn directions(&self) -> Vec<Direction> { return self.objectives.iter().map(|(_, d)| *d).collect(); }
+
+    /// Computes fitness vector of a single genotype.
+    pub fn evaluate(&self, genotype: &G, data: &D, map: &HashMap<String, (usize, VectorFunction)>) -> Vec<f64> {
+        return self.objectives.iter().map(|(objective, _)| objective.evaluate(genotype, data, map)).collect();
+    }
+
+    /// Computes fitness vectors of genotypes, each objective evaluates the whole population at
+    /// once, so population-level evaluators (e.g.
+    /// [`ParallelEvaluator`][`crate::tree::fitness::parallel::ParallelEvaluator`]) keep working.
+    ///
+    /// # Returns
+    /// * `Vec<Vec<f64>>` - fitness vector of every genotype, in the order of `genotypes`
+    pub fn evaluate_population(&self, genotypes: &[G], data: &D, map: &HashMap<String, (usize, VectorFunction)>) -> Vec<Vec<f64>> {
+        let columns = self.objectives.iter()
+            .map(|(objective, _)| objective.evaluate_population(genotypes, data, map))
+            .collect::<Vec<Vec<f64>>>();
+        return (0..genotypes.len()).map(|i| columns.iter().map(|column| column[i]).collect()).collect();
+    }
+}
+
+impl<G: Genotype, D: Data> Default for Objectives<G, D> {
+    fn default() -> Self { return Self::new(); }
+}
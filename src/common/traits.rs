@@ -4,8 +4,11 @@ use std::fmt::Display;
 
 use rand::Rng;
 use std::collections::HashMap;
+use std::hash::Hash;
 
 use crate::operators::sampler::OperatorSampler;
+use crate::common::cache::{fingerprint, FitnessCache};
+use crate::common::objectives::Direction;
 use crate::common::types::VectorFunction;
 
 /// Base trait for all genotypes in evolutionary algorithms.
@@ -26,6 +29,31 @@ pub trait Initializer<G: Genotype> {
     fn initialize<R: Rng>(&self, rng: &mut R, sampler: &OperatorSampler) -> G;
 }
 
+/// Handles initialization of a whole population.
+///
+/// Every [`Initializer`][`crate::common::traits::Initializer`] is a population initializer
+/// creating individuals independently. Population-level methods (e.g.
+/// [`RampedHalfAndHalf`][`crate::tree::operators::init::RampedHalfAndHalf`]) implement this trait
+/// directly.
+///
+/// # Arguments
+/// * `rng: &mut Rng` - random number generator, see [`Rng`][`rand::Rng`]
+/// * `sampler: &OperatorSampler` - helper structure for sampling operators, see
+///   [`OperatorSampler`][`crate::operators::sampler::OperatorSampler`]
+/// * `population_size: usize` - number of genotypes to create
+///
+/// # Returns
+/// * `Vec<G>` - newly initialized population
+pub trait PopulationInitializer<G: Genotype> {
+    fn initialize_population<R: Rng>(&self, rng: &mut R, sampler: &OperatorSampler, population_size: usize) -> Vec<G>;
+}
+
+impl<G: Genotype, T: Initializer<G>> PopulationInitializer<G> for T {
+    fn initialize_population<R: Rng>(&self, rng: &mut R, sampler: &OperatorSampler, population_size: usize) -> Vec<G> {
+        return (0..population_size).map(|_| self.initialize(rng, sampler)).collect();
+    }
+}
+
 /// Performs mutation operations on [`Genotype`][`crate::common::traits::Genotype`]
 ///
 /// # Arguments
@@ -61,23 +89,64 @@ pub trait Crossoverer<G: Genotype> {
 /// # Returns
 /// * `names` - names for the data (feature_names: Vec<String> and target_name: String)
 /// * `data` - data contained by the structure (features: Vec<Vec<f64>> and targets: Vec<f64>)
+/// * `fingerprint` - identity of the dataset computed from its names and values, used to key
+///   cached fitness values
 pub trait Data {
     fn names(&self) -> (&Vec<String>, &String);
     fn data(&self) -> (&Vec<Vec<f64>>, &Vec<f64>);
+
+    fn fingerprint(&self) -> u64 { return fingerprint(self); }
 }
 
-/// Evaluates fitness of genotypes, with optional memoization support.
+/// Evaluates fitness of genotypes, with optional memoization and population-level support.
 ///
 /// # Arguments
 /// * `tree: &G` - [`Genotype`][`crate::common::traits::Genotype`] to evaluate
 /// * `data: &Self::D` - dataset implementing [`Data`][`crate::common::traits::Data`] trait
 /// * `map: &HashMap<String, (usize, VectorFunction)>` - mapping of function names to their 
 ///     implementations, see [`VectorFunction`][`crate::common::types::VectorFunction`]
-/// * `cache: &HashMap<G, f64>` - (memoized version only) cache of previously computed 
-///     fitness values
+/// * `cache: &mut FitnessCache<G>` - (memoized version only) cache of previously computed 
+///   fitness values, filled with newly computed ones, see
+///   [`FitnessCache`][`crate::common::cache::FitnessCache`]
+/// * `trees: &[G]` - (population version only) genotypes to evaluate
 ///
 /// # Returns
 /// * `f64` - computed fitness value
+/// * `Vec<f64>` - (population version only) fitness values in the order of `trees`
+/// * `(f64, Vec<f64>)` - (cases version only) fitness value and error on every data row (case),
+///   used by [`LexicaseSelection`][`crate::tree::operators::select::LexicaseSelection`]; empty if
+///   the evaluator does not provide per-case errors
+/// * `Direction` - (`direction` only) whether fitness is minimized (default) or maximized,
+///   honoured by optimizers in selection, replacement, best individual tracking and statistics
+/// * `bool` - (`population_dependent` only) whether fitness of a genotype depends on the other
+///   genotypes evaluated together with it (e.g.
+///   [`Tarpeian`][`crate::tree::fitness::parsimony::Tarpeian`]), such evaluators need whole
+///   populations and cannot be used by steady-state optimizers
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use mycoforge::common::objectives::Direction;
+/// use mycoforge::common::traits::Evaluator;
+/// use mycoforge::common::types::VectorFunction;
+/// use mycoforge::dataset::core::Dataset;
+/// use mycoforge::tree::core::tree::TreeGenotype;
+///
+/// /// Rewards larger trees, fitness is a score to maximize
+/// struct Size;
+///
+/// impl Evaluator<TreeGenotype> for Size {
+///     type D = Dataset;
+///
+///     fn evaluate(&self, tree: &TreeGenotype, _: &Dataset, _: &HashMap<String, (usize, VectorFunction)>) -> f64 {
+///         return tree.arena().len() as f64;
+///     }
+///
+///     fn direction(&self) -> Direction { return Direction::Maximize; }
+/// }
+///
+/// assert_eq!(Size.direction(), Direction::Maximize);
+/// ```
 pub trait Evaluator<G: Genotype> {
     type D: Data;
 
@@ -89,8 +158,43 @@ pub trait Evaluator<G: Genotype> {
     fn memoized_evaluate(&self, 
         tree: &G, data: &Self::D, 
         map: &HashMap<String, (usize, VectorFunction)>,
-        cache: &HashMap<G, f64>
-    ) -> f64;
+        cache: &mut FitnessCache<G>
+    ) -> f64 
+    where
+        G: Hash + Eq,
+    {
+        let dataset = data.fingerprint();
+        if let Some(fitness) = cache.get(dataset, tree) { return fitness; }
+
+        let fitness = self.evaluate(tree, data, map);
+        cache.insert(dataset, tree.clone(), fitness);
+        return fitness;
+    }
+
+    fn evaluate_population(&self,
+        trees: &[G], data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> Vec<f64> {
+        return trees.iter().map(|tree| self.evaluate(tree, data, map)).collect();
+    }
+
+    fn evaluate_cases(&self,
+        tree: &G, data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> (f64, Vec<f64>) {
+        return (self.evaluate(tree, data, map), Vec::new());
+    }
+
+    fn evaluate_population_cases(&self,
+        trees: &[G], data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> Vec<(f64, Vec<f64>)> {
+        return trees.iter().map(|tree| self.evaluate_cases(tree, data, map)).collect();
+    }
+
+    fn direction(&self) -> Direction { return Direction::Minimize; }
+
+    fn population_dependent(&self) -> bool { return false; }
 }
 
 /// Performs selection of genotypes from population.
@@ -99,13 +203,26 @@ pub trait Evaluator<G: Genotype> {
 /// * `rng: &mut Rng` - random number generator, see [`Rng`][`rand::Rng`]
 /// * `population: &[Self::I]` - slice of individuals implementing 
 ///     [`Individual`][`crate::common::traits::Individual`]
+/// * `count: usize` - (`select_many` only) number of genotypes to select
+/// * `direction: Direction` - whether lower or higher fitness is better, see
+///   [`Evaluator::direction`]
 ///
 /// # Returns
 /// * `G` - selected [`Genotype`][`crate::common::traits::Genotype`]
+/// * `Vec<G>` - (`select_many` only) selected genotypes, by default from repeated `select` calls;
+///   selectors drawing a whole sample at once (e.g. stochastic universal sampling) override it
+/// * `uses_cases` - whether selection needs per-case errors of individuals, optimizers then
+///   evaluate with [`Evaluator::evaluate_population_cases`]
 pub trait Selector<G: Genotype> {
     type I: Individual<G>;
 
-    fn select<R: Rng>(&self, rng: &mut R, population: &[Self::I]) -> G;
+    fn select<R: Rng>(&self, rng: &mut R, population: &[Self::I], direction: Direction) -> G;
+
+    fn select_many<R: Rng>(&self, rng: &mut R, population: &[Self::I], count: usize, direction: Direction) -> Vec<G> {
+        return (0..count).map(|_| self.select(rng, population, direction)).collect();
+    }
+
+    fn uses_cases(&self) -> bool { return false; }
 }
 
 /// Represents an individual in population, combining genotype and its fitness.
@@ -113,10 +230,14 @@ pub trait Selector<G: Genotype> {
 /// # Methods
 /// * `genotype()` - returns reference to underlying [`Genotype`][`crate::common::traits::Genotype`]
 /// * `phenotype()` - returns fitness value
+/// * `cases()` - returns per-case errors, empty if not stored
 ///
 /// # Conversion Methods
 /// * `from_vecs` - creates individuals from separate genotype and fitness vectors
-/// * `from_genotype_vec` - creates individuals from genotypes (fitness needs to be computed)
+/// * `from_evaluations` - creates individuals from genotypes and their fitness with per-case
+///   errors, individuals not storing cases drop them
+/// * `from_genotype_vec` - creates individuals from genotypes (fitness needs to be computed, it
+///   is NaN until then, the worst value in either direction)
 /// * `to_genotype_vec` - extracts genotypes from individuals
 ///
 /// # Returns
@@ -125,9 +246,16 @@ pub trait Individual<G: Genotype>: Sized {
     fn genotype(&self) -> &G;
     fn phenotype(&self) -> f64;
 
+    fn cases(&self) -> &[f64] { return &[]; }
+
     fn from_vecs(genotypes: &[G], fitness: &[f64]) -> Vec<Self>;
     fn from_genotype_vec(genotypes: &[G]) -> Vec<Self>;
     fn to_genotype_vec(individuals: &[Self]) -> Vec<G>;
+
+    fn from_evaluations(genotypes: &[G], evaluations: Vec<(f64, Vec<f64>)>) -> Vec<Self> {
+        let fitness = evaluations.iter().map(|(fitness, _)| *fitness).collect::<Vec<f64>>();
+        return Self::from_vecs(genotypes, &fitness);
+    }
 }
 
 /// Main optimization interface for evolutionary algorithms.
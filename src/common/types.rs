@@ -1,5 +1,7 @@
 //! Common type definitions used across the codebase.
 
+use std::sync::Arc;
+
 /// Function type for vectorized operations on data.
 /// 
 /// # Arguments
@@ -8,3 +10,9 @@
 /// # Returns
 /// * `Vec<f64>` - result of vectorized operation
 pub type VectorFunction = fn(&[&[f64]]) -> Vec<f64>;
+
+/// Generator of ephemeral random constants, called for every sampled ephemeral terminal.
+///
+/// # Returns
+/// * `f64` - value of the new constant
+pub type ConstantGenerator = Arc<dyn Fn() -> f64 + Send + Sync>;
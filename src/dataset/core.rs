@@ -1,4 +1,5 @@
 //! Core dataset structures for handling training and test data.
+use crate::common::cache::fingerprint;
 use crate::common::traits::Data;
 use crate::dataset::error::DatasetError;
 
@@ -14,11 +15,13 @@ pub type OutputData = (Vec<String>, String, Vec<Vec<f64>>, Vec<f64>);
 /// * `target_name: String` - name of the target in Dataset
 /// * `features: Vec<Vec<f64>>` - n-dimensional array of features
 /// * `targets: Vec<f64>` - 1-dimensional array of targets
+/// * `fingerprint: u64` - identity of the dataset, computed once at construction
 pub struct Dataset {
     feature_names: Vec<String>,
     target_name: String,
     features: Vec<Vec<f64>>,
-    targets: Vec<f64>
+    targets: Vec<f64>,
+    fingerprint: u64
 }
 
 impl Dataset {
@@ -33,7 +36,7 @@ impl Dataset {
         feature_names: Vec<String>, target_name: String, 
         features: Vec<Vec<f64>>, targets: Vec<f64>
     ) -> Self {
-        return Self { feature_names, target_name, features, targets };
+        return Self::from_vector(feature_names, target_name, features, targets);
     }
 
     /// Loads dataset from CSV file.
@@ -68,7 +71,9 @@ impl Dataset {
         feature_names: Vec<String>, target_name: String,
         features: Vec<Vec<f64>>, targets: Vec<f64>
     ) -> Self {
-        return Self { feature_names, target_name, features, targets };
+        let mut dataset = Self { feature_names, target_name, features, targets, fingerprint: 0 };
+        dataset.fingerprint = fingerprint(&dataset);
+        return dataset;
     }
 
     pub fn feature_names(&self) -> &Vec<String> { return &self.feature_names; }
@@ -80,4 +85,5 @@ impl Dataset {
 impl Data for Dataset {
     fn names(&self) -> (&Vec<String>, &String) { return (&self.feature_names, &self.target_name); }
     fn data(&self) -> (&Vec<Vec<f64>>, &Vec<f64>) { return (&self.features, &self.targets); }
+    fn fingerprint(&self) -> u64 { return self.fingerprint; }
 }
@@ -10,6 +10,7 @@ use crate::operators::sampler::OperatorSampler;
 use crate::common::types::VectorFunction;
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Errors that can occur during operator set building.
 ///
@@ -18,12 +19,14 @@ use std::collections::HashMap;
 /// * `KeyExists` - operator with given name already exists 
 /// * `OperatorsIsEmpty` - no operators added to builder
 /// * `WrongWeightSum` - weights don't sum to 1.0
+/// * `UnknownFunction(String)` - no function is registered under given key
 #[derive(Debug)]
 pub enum BuilderError {
     IncorrectWeight,
     KeyExists,
     OperatorsIsEmpty,
-    WrongWeightSum
+    WrongWeightSum,
+    UnknownFunction(String)
 }
 
 impl std::error::Error for BuilderError {}
@@ -34,6 +37,7 @@ impl std::fmt::Display for BuilderError {
             Self::KeyExists => write!(f, "KeyExists"),
             Self::OperatorsIsEmpty => write!(f, "OperatorsIsEmpty"),
             Self::WrongWeightSum => write!(f, "WrongWeightSum"),
+            Self::UnknownFunction(key) => write!(f, "UnknownFunction({})", key),
         }
     }
 }
@@ -91,6 +95,28 @@ impl OperatorsBuilder {
             return Ok(self);
     }
     
+    /// Adds function from [`registry`][`crate::operators::registry`] to the set. Unlike
+    /// [`add_function`][`OperatorsBuilder::add_function`], the set remembers the key, so it can be
+    /// serialized and rebuilt in another process.
+    ///
+    /// # Arguments
+    /// * `name: &str` - operator name
+    /// * `key: &str` - key of registered function, e.g. `"protected_div"`
+    /// * `weight: f64` - sampling weight
+    ///
+    /// # Returns
+    /// * `Result<Self, BuilderError>` - Update builder or [`error`][`BuilderError`]
+    pub fn add_registered(mut self, name: &str, key: &str, weight: f64) -> Result<Self, BuilderError> {
+        if weight <= 0.0 || weight > 1.0 { return Err(BuilderError::IncorrectWeight); }
+        if self.functions.contains_key(name) { return Err(BuilderError::KeyExists); }
+
+        let functor = Functor::registered(key, weight).map_err(|_| BuilderError::UnknownFunction(key.to_string()))?;
+        self.functions.insert(name.to_string(), functor);
+        self.function_weights_sum += weight;
+
+        return Ok(self);
+    }
+
     /// Adds variable terminal to the set
     pub fn add_variable(mut self, name: &str, weight: f64) -> Result<Self, BuilderError> {
         if weight <= 0.0 || weight > 1.0 { return Err(BuilderError::IncorrectWeight); }
@@ -113,11 +139,11 @@ impl OperatorsBuilder {
         return Ok(self);
     }
     
-    /// Adds ephemeral random constant generator to the set
-    pub fn add_ephemeral(mut self, generator: Box<dyn Fn() -> f64>, weight: f64) -> Result<Self, BuilderError> {
+    /// Adds ephemeral random constant generator to the set, called for every sampled terminal
+    pub fn add_ephemeral(mut self, generator: Box<dyn Fn() -> f64 + Send + Sync>, weight: f64) -> Result<Self, BuilderError> {
         if weight <= 0.0 || weight > 1.0 { return Err(BuilderError::IncorrectWeight); }
         
-        self.terminals.push(NodeType::EphemeralGenerator(generator));
+        self.terminals.push(NodeType::EphemeralGenerator(Arc::from(generator)));
         self.terminal_weights.push(weight);
         self.terminal_weights_sum += weight;
         
@@ -140,7 +166,11 @@ impl OperatorsBuilder {
         let (mut ops, mut arity, mut weights) = 
             (Vec::with_capacity(capacity), Vec::with_capacity(capacity), Vec::with_capacity(capacity));
 
-        for (name, func) in &self.functions {
+        // Sorted so sampling does not depend on hash map iteration order, which differs between
+        // processes and would make runs irreproducible
+        let mut functions = self.functions.iter().collect::<Vec<_>>();
+        functions.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, func) in functions {
             ops.push(name.clone());
             arity.push(func.arity());
             weights.push(func.weight());
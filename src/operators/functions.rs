@@ -96,47 +96,76 @@ pub mod symbolic {
             .collect();
     }
 
+    /// Scalar kernels of the operators, shared by vectorized operators and compiled trees so that
+    /// both reproduce identical protected semantics.
+    pub mod scalar {
+        use std::ops::{Add, Sub, Mul, Div};
+        use super::Float;
+
+        /// Addition
+        pub fn add<T: Add<Output = T> + Float>(a: T, b: T) -> T { return a + b; }
+
+        /// Subtraction
+        pub fn sub<T: Sub<Output = T> + Float>(a: T, b: T) -> T { return a - b; }
+
+        /// Multiplication with overflow protection
+        pub fn mul<T: Mul<Output = T> + Float>(a: T, b: T) -> T {
+            let result = a * b;
+            if result.is_finite() { return result; } else { return result.signum() * T::max_value(); }
+        }
+
+        /// Protected division (returns 1.0 for division by values smaller than epsilon)
+        pub fn div<T: Div<Output = T> + Float>(a: T, b: T) -> T {
+            if b.abs() < T::epsilon() { return T::one(); } else { return a / b; }
+        }
+
+        /// Protected sine (returns 0.0 for non-finite inputs)
+        pub fn sin<T: Float>(a: T) -> T { if a.is_finite() { return a.sin(); } else { return T::zero(); } }
+
+        /// Protected cosine (returns 0.0 for non-finite inputs)
+        pub fn cos<T: Float>(a: T) -> T { if a.is_finite() { return a.cos(); } else { return T::zero(); } }
+
+        /// Protected natural logarithm (returns minimum value for inputs less than or equal to epsilon)
+        pub fn ln<T: Float>(a: T) -> T { if a > T::epsilon() { return a.ln(); } else { return T::min_value(); } }
+    }
+
     /// Addition operator
     pub fn add<T: Add<Output = T> + Float>(args: &[&[T]]) -> Vec<T> { 
-        return apply_binary(|a, b| a + b, args); 
+        return apply_binary(scalar::add, args); 
     }
     
     /// Subtraction operator
     pub fn sub<T: Sub<Output = T> + Float>(args: &[&[T]]) -> Vec<T> {
-        return apply_binary(|a, b| a - b, args); 
+        return apply_binary(scalar::sub, args); 
     }
 
     /// Multiplication operator with overflow protection
     pub fn mul<T: Mul<Output = T> + Float>(args: &[&[T]]) -> Vec<T> {
-        return apply_binary(|a, b| {
-            let result = a * b;
-            if result.is_finite() { result } else { result.signum() * T::max_value() }
-        }, args);
+        return apply_binary(scalar::mul, args);
     }
 
     /// Protected division operator (returns 1.0 for division by values smaller than epsilon)
     pub fn div<T: Div<Output = T> + Float>(args: &[&[T]]) -> Vec<T> {
-        return apply_binary(|a, b| if b.abs() < T::epsilon() { return T::one(); } else { return a / b }, args);
+        return apply_binary(scalar::div, args);
     }
 
     /// Protected sine operator (returns 0.0 for non-finite inputs)
     pub fn sin<T: Float>(args: &[&[T]]) -> Vec<T> {
-        return apply_unary(|a| if a.is_finite() { a.sin() } else { T::zero() }, args);
+        return apply_unary(scalar::sin, args);
     }
 
     /// Protected cosine operator (returns 0.0 for non-finite inputs)
     pub fn cos<T: Float>(args: &[&[T]]) -> Vec<T> {
-        return apply_unary(|a| if a.is_finite() { a.cos() } else { T::zero() }, args);
+        return apply_unary(scalar::cos, args);
     }
     
     /// Protected natural logarithm (return minimum value for inputs less than or equal to epsilon)
     pub fn ln<T: Float>(args: &[&[T]]) -> Vec<T> {
-        return apply_unary(|a| if a > T::epsilon() { a.ln() } else { T::min_value() }, args);
+        return apply_unary(scalar::ln, args);
     }
 }
 
 use crate::operators::builder::{BuilderError, OperatorsBuilder};
-use symbolic::{add, sub, mul, div, sin, cos};
 
 /// Creates standard Koza function set for symbolic regression.
 ///
@@ -150,12 +179,12 @@ pub fn koza(operators_size: usize) -> Result<OperatorsBuilder, BuilderError> {
     assert!(operators_size > 6, "Operators size too small! Expected more than {}, found {}", 6, operators_size);
     let operators_size = operators_size as f64;
     let koza = OperatorsBuilder::default()
-        .add_function("+", add, 2, 1.0 / operators_size).expect("Failed to add an operator!")
-        .add_function("-", sub, 2, 1.0 / operators_size).expect("Failed to add an operator!")
-        .add_function("*", mul, 2, 1.0 / operators_size).expect("Failed to add an operator!")
-        .add_function("/", div, 2, 1.0 / operators_size).expect("Failed to add an operator!")
-        .add_function("sin", sin, 1, 1.0 / operators_size).expect("Failed to add an operator!")
-        .add_function("cos", cos, 1, 1.0 / operators_size).expect("Failed to add an operator!");
+        .add_registered("+", "add", 1.0 / operators_size).expect("Failed to add an operator!")
+        .add_registered("-", "sub", 1.0 / operators_size).expect("Failed to add an operator!")
+        .add_registered("*", "mul", 1.0 / operators_size).expect("Failed to add an operator!")
+        .add_registered("/", "protected_div", 1.0 / operators_size).expect("Failed to add an operator!")
+        .add_registered("sin", "sin", 1.0 / operators_size).expect("Failed to add an operator!")
+        .add_registered("cos", "cos", 1.0 / operators_size).expect("Failed to add an operator!");
 
     return Ok(koza);
 }
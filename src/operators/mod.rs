@@ -7,10 +7,12 @@
 //! trigonometric, etc.)
 //! - [`set`] - Management of operator sets including builder batter for creating valid sets and
 //! sampling functionality.
+//! - [`registry`] - Process-wide registry of named functions for serializable operator sets
 
 pub mod functions;
 
 pub mod builder;
 pub mod set;
+pub mod registry;
 
 pub mod sampler;
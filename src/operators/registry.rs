@@ -0,0 +1,108 @@
+//! Process-wide registry of named functions.
+//!
+//! [`Functor`][`crate::operators::set::Functor`] stores a raw
+//! [`VectorFunction`][`crate::common::types::VectorFunction`] pointer, which cannot be written to
+//! disk. The registry maps stable keys (e.g. `"add"`, `"protected_div"`, `"sin"`) to functions and
+//! their arities, so operator sets built from registered functions can be serialized by key and
+//! rebuilt in another process.
+//!
+//! Operators from [`symbolic`][`crate::operators::functions::symbolic`] are registered by default.
+//! Custom functions should use namespaced keys, e.g. `"my_crate::square"`, to avoid collisions.
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result};
+use std::sync::{OnceLock, RwLock};
+
+use crate::common::types::VectorFunction;
+use crate::operators::functions::symbolic::{add, cos, div, ln, mul, sin, sub};
+
+/// Keys, implementations and arities of functions registered by default.
+const BUILTINS: [(&str, VectorFunction, usize); 7] = [
+    ("add", add, 2),
+    ("sub", sub, 2),
+    ("mul", mul, 2),
+    ("protected_div", div, 2),
+    ("sin", sin, 1),
+    ("cos", cos, 1),
+    ("protected_ln", ln, 1),
+];
+
+/// Errors that can occur while registering or resolving functions.
+///
+/// # Variants
+/// * `KeyExists(String)` - different function is already registered under the key
+/// * `UnknownKey(String)` - no function is registered under the key
+/// * `ArityMismatch { key, expected, found }` - function is used with different arity than it
+///   was registered with
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistryError {
+    KeyExists(String),
+    UnknownKey(String),
+    ArityMismatch { key: String, expected: usize, found: usize }
+}
+
+impl std::error::Error for RegistryError {}
+
+impl Display for RegistryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            RegistryError::KeyExists(key) => write!(f, "Function {} is already registered!", key),
+            RegistryError::UnknownKey(key) => write!(f, "Function {} is not registered!", key),
+            RegistryError::ArityMismatch { key, expected, found } =>
+                write!(f, "Function {} has arity {}, found {}!", key, expected, found),
+        }
+    }
+}
+
+fn registry() -> &'static RwLock<HashMap<String, (VectorFunction, usize)>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, (VectorFunction, usize)>>> = OnceLock::new();
+    return REGISTRY.get_or_init(|| RwLock::new(
+        BUILTINS.iter().map(|&(key, function, arity)| (key.to_string(), (function, arity))).collect()
+    ));
+}
+
+/// Registers function under given key.
+///
+/// Registering the same function with the same arity again is a no-op, so registration can be
+/// repeated safely, e.g. at the start of every test.
+///
+/// # Arguments
+/// * `key: &str` - stable name of the function
+/// * `function: VectorFunction` - [`function`][`crate::common::types::VectorFunction`] implementation
+/// * `arity: usize` - number of arguments
+///
+/// # Returns
+/// * `Result<(), RegistryError>` - [`error`][`RegistryError`] if the key is taken by another function
+///
+/// # Examples
+/// ```
+/// use mycoforge::operators::registry::{lookup, register};
+///
+/// fn square(args: &[&[f64]]) -> Vec<f64> { return args[0].iter().map(|a| a * a).collect(); }
+///
+/// register("docs::square", square, 1).expect("Failed to register function!");
+///
+/// assert_eq!(lookup("docs::square").map(|(_, arity)| arity), Some(1));
+/// assert!(lookup("protected_div").is_some());
+/// ```
+pub fn register(key: &str, function: VectorFunction, arity: usize) -> std::result::Result<(), RegistryError> {
+    let mut functions = registry().write().expect("Function registry lock should not be poisoned!");
+    if let Some(&(registered, registered_arity)) = functions.get(key) {
+        if std::ptr::fn_addr_eq(registered, function) && registered_arity == arity { return Ok(()); }
+        return Err(RegistryError::KeyExists(key.to_string()));
+    }
+    functions.insert(key.to_string(), (function, arity));
+    return Ok(());
+}
+
+/// Returns function registered under given key together with its arity.
+pub fn lookup(key: &str) -> Option<(VectorFunction, usize)> {
+    return registry().read().expect("Function registry lock should not be poisoned!").get(key).copied();
+}
+
+/// Returns sorted keys of all registered functions.
+pub fn registered() -> Vec<String> {
+    let mut keys = registry().read().expect("Function registry lock should not be poisoned!")
+        .keys().cloned().collect::<Vec<String>>();
+    keys.sort();
+    return keys;
+}
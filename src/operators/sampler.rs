@@ -6,6 +6,9 @@
 use rand::prelude::*;
 use rand::distr::weighted::WeightedIndex;
 
+use crate::common::types::ConstantGenerator;
+use crate::tree::core::node::Node;
+
 /// Interface for sampling operators.
 pub trait Sampler {
     /// Samples random operator.
@@ -13,6 +16,12 @@ pub trait Sampler {
     /// # Returns
     /// * `(String, usize)` - (operator name, arity) tuple
     fn sample<R: Rng>(&self, rng: &mut R) -> (String, usize);
+
+    /// Samples random operator as a typed node.
+    ///
+    /// # Returns
+    /// * `Node` - sampled [`node`][`crate::tree::core::node::Node`]
+    fn sample_node<R: Rng>(&self, rng: &mut R) -> Node;
 }
 
 /// Sampler for operators with weights and arity constraints.
@@ -21,21 +30,87 @@ pub trait Sampler {
 /// * `operators: Vec<String>` - list of operator names
 /// * `arity: Vec<usize>` - list of operator arities
 /// * `weights: Vec<f64>` - list of sampling weights
+/// * `nodes: Vec<Node>` - typed [`nodes`][`crate::tree::core::node::Node`] matching operators,
+///   ephemeral terminals hold a `Constant(NaN)` placeholder
+/// * `generators: Vec<Option<ConstantGenerator>>` - [`generators`][`ConstantGenerator`] of
+///   ephemeral terminals, sampling one creates a constant with a freshly generated value
 #[derive(Clone)]
 pub struct OperatorSampler {
-    operators: Vec<String>,
-    arity:     Vec<usize>,
-    weights:   Vec<f64>,
+    operators:  Vec<String>,
+    arity:      Vec<usize>,
+    weights:    Vec<f64>,
+    nodes:      Vec<Node>,
+    generators: Vec<Option<ConstantGenerator>>,
 }
 
 impl OperatorSampler {
+    /// Creates new sampler from operator names.
+    ///
+    /// Terminals whose names parse as numbers become constants, all other operators become
+    /// functions (terminal functions are evaluated on the whole feature set). Use
+    /// [`OperatorSampler::with_nodes`] to sample variables by column index.
     pub fn new(operators: Vec<String>, arity: Vec<usize>, weights: Vec<f64>) -> Self {
-        return Self { operators, arity, weights };
+        let nodes = operators.iter().zip(&arity)
+            .map(|(name, &arity)| match name.parse::<f64>() {
+                Ok(value) if arity == 0 => Node::Constant(value),
+                _ => Node::function(name, arity),
+            })
+            .collect();
+        let generators = vec![None; operators.len()];
+        return Self { operators, arity, weights, nodes, generators };
+    }
+
+    /// Creates new sampler from operator names and their typed nodes.
+    ///
+    /// # Panics
+    /// * If lengths of operators, nodes and weights do not match
+    pub fn with_nodes(operators: Vec<String>, nodes: Vec<Node>, weights: Vec<f64>) -> Self {
+        assert!(operators.len() == nodes.len() && nodes.len() == weights.len(),
+            "Operators, nodes and weights should have equal lengths! Found {}, {} and {}",
+            operators.len(), nodes.len(), weights.len()
+        );
+        let arity = nodes.iter().map(|node| node.arity()).collect();
+        let generators = vec![None; operators.len()];
+        return Self { operators, arity, weights, nodes, generators };
+    }
+
+    /// Sets generators of ephemeral terminals, operators with `Some` generator become ephemeral.
+    ///
+    /// # Panics
+    /// * If number of generators differs from number of operators, or a generator belongs to
+    ///   operator with non-zero arity
+    pub fn with_generators(mut self, generators: Vec<Option<ConstantGenerator>>) -> Self {
+        assert_eq!(generators.len(), self.operators.len(), "Every operator needs a generator slot!");
+        assert!(generators.iter().zip(&self.arity).all(|(generator, &arity)| generator.is_none() || arity == 0),
+            "Only terminals can be ephemeral!"
+        );
+        self.generators = generators;
+        return self;
     }
 
     pub fn operators(&self) -> &Vec<String> { return &self.operators; }
     pub fn arities(&self) -> &Vec<usize> { return &self.arity; }
     pub fn weights(&self) -> &Vec<f64> { return &self.weights; }
+    pub fn nodes(&self) -> &Vec<Node> { return &self.nodes; }
+
+    /// Returns node of operator with given name, if present. Ephemeral terminals give a new
+    /// constant on every call.
+    pub fn node(&self, name: &str) -> Option<Node> {
+        return self.operators.iter().position(|op| op == name).map(|i| self.node_at(i));
+    }
+
+    /// Returns node of operator at given index, generating value of ephemeral constants.
+    fn node_at(&self, index: usize) -> Node {
+        return match &self.generators[index] {
+            Some(generator) => Node::Constant(generator()),
+            None => self.nodes[index],
+        };
+    }
+
+    /// Returns name of operator represented by given node, if present.
+    pub fn name(&self, node: &Node) -> Option<&str> {
+        return self.nodes.iter().position(|n| n == node).map(|i| self.operators[i].as_str());
+    }
     
     /// Updates sampling weights.
     ///
@@ -58,15 +133,21 @@ impl OperatorSampler {
         let is_valid = |arity| -> bool {
             return arity >= min_arity && arity <= max_arity;
         };
-        let (mut filtered_operators, mut filtered_arity, mut filtered_weights) = (Vec::new(), Vec::new(), Vec::new());
+        let (mut filtered_operators, mut filtered_arity, mut filtered_weights, mut filtered_nodes, mut filtered_generators) = 
+            (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new());
         for (i, &arity) in self.arity.iter().enumerate() {
             if is_valid(arity) {
                 filtered_operators.push(self.operators[i].clone());
                 filtered_arity.push(self.arity[i]);
                 filtered_weights.push(self.weights[i]);
+                filtered_nodes.push(self.nodes[i]);
+                filtered_generators.push(self.generators[i].clone());
             }
         }
-        return Self { operators: filtered_operators, arity: filtered_arity, weights: filtered_weights };
+        return Self { 
+            operators: filtered_operators, arity: filtered_arity, weights: filtered_weights, nodes: filtered_nodes,
+            generators: filtered_generators
+        };
     }
 
     /// Samples just the index of an operator rather than returning the operator itself
@@ -83,4 +164,8 @@ impl Sampler for OperatorSampler {
 
         return (self.operators[index].clone(), self.arity[index]);
     }
+
+    fn sample_node<R: Rng>(&self, rng: &mut R) -> Node {
+        return self.node_at(self.sample_index(rng));
+    }
 }
@@ -4,13 +4,20 @@
 //! - Managing sets of operators with their arities and weights
 //! - Building operator sets with validation
 //! - Sampling operators based on weights
+//! - Serialization of operator sets built from [`registry`][`crate::operators::registry`] functions
 
 use rand::Rng;
 use std::collections::HashMap;
 use std::fmt;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::operators::builder::{BuilderError, OperatorsBuilder};
+use crate::operators::registry::{lookup, RegistryError};
 use crate::operators::sampler::{OperatorSampler, Sampler};
-use crate::common::types::VectorFunction;
+use crate::common::types::{ConstantGenerator, VectorFunction};
+use crate::tree::core::node::Node;
 
 /// Represents different types of nodes in a GP tree
 pub enum NodeType {
@@ -21,7 +28,7 @@ pub enum NodeType {
     /// Constant numeric value
     Constant(f64),
     /// Generator function for ephemeral random constants
-    EphemeralGenerator(Box<dyn Fn() -> f64>)
+    EphemeralGenerator(ConstantGenerator)
 }
 
 impl fmt::Debug for NodeType {
@@ -41,12 +48,8 @@ impl Clone for NodeType {
             NodeType::Function(name, arity) => NodeType::Function(name.clone(), *arity),
             NodeType::Variable(name) => NodeType::Variable(name.clone()),
             NodeType::Constant(value) => NodeType::Constant(*value),
-            // For ephemeral constants, we create a new EphemeralGenerator that
-            // returns the same value each time - effectively "freezing" the value
-            NodeType::EphemeralGenerator(generator) => {
-                let value = generator();
-                NodeType::Constant(value)
-            }
+            // Clones share the generator, every sampled terminal still gets a fresh value
+            NodeType::EphemeralGenerator(generator) => NodeType::EphemeralGenerator(generator.clone()),
         }
     }
 }
@@ -149,21 +152,40 @@ impl Operators {
         }
     }
 
-    /// Returns a combined sampler that includes both functions and terminals
+    /// Returns a combined sampler that includes both functions and terminals.
+    ///
+    /// Variables are sampled as [`Node::Variable`][`crate::tree::core::node::Node::Variable`]
+    /// with column index given by the order in which they were added. Ephemeral terminals keep
+    /// their generator, so every sampled one becomes a new
+    /// [`Node::Constant`][`crate::tree::core::node::Node::Constant`].
     pub fn sampler(&self) -> OperatorSampler {
         // Create a new sampler that combines both function and terminal samplers
-        let ops = self.functions.keys().cloned()
-            .chain(self.terminals.iter().map(|t| t.name()))
+        let terminals = self.terminals.to_vec();
+        // Functions follow order of the function sampler, which is sorted by name
+        let ops = self.function_sampler.operators().iter().cloned()
+            .chain(terminals.iter().map(|t| t.name()))
             .collect::<Vec<String>>();
-        let arities = self.functions.values()
-            .map(|f| f.arity())
-            .chain(self.terminals.iter().map(|t| t.arity()))
-            .collect::<Vec<usize>>();
+        let mut variables = 0;
+        let nodes = self.function_sampler.operators().iter().zip(self.function_sampler.arities())
+            .map(|(name, &arity)| Node::function(name, arity))
+            .chain(terminals.iter().map(|t| match t {
+                NodeType::Variable(_) => { variables += 1; Node::Variable(variables - 1) },
+                NodeType::Constant(value) => Node::Constant(*value),
+                NodeType::Function(name, arity) => Node::function(name, *arity),
+                NodeType::EphemeralGenerator(_) => Node::Constant(f64::NAN),
+            }))
+            .collect::<Vec<Node>>();
+        let generators = self.function_sampler.operators().iter().map(|_| None)
+            .chain(terminals.iter().map(|t| match t {
+                NodeType::EphemeralGenerator(generator) => Some(generator.clone()),
+                _ => None,
+            }))
+            .collect::<Vec<Option<ConstantGenerator>>>();
         let weights = self.function_sampler.weights().iter()
             .chain(self.terminal_sampler.weights().iter())
             .copied()
             .collect::<Vec<f64>>();
-        return OperatorSampler::new(ops, arities, weights);
+        return OperatorSampler::with_nodes(ops, nodes, weights).with_generators(generators);
     }
 }
 
@@ -179,17 +201,149 @@ impl OperatorSet for Operators {
 /// the operator
 /// * `arity: usize` - number of arguments operator takes
 /// * `weight: f64` - sampling weight for operator
+/// * `key: Option<String>` - key of the function in [`registry`][`crate::operators::registry`],
+///   required for serialization
 #[derive(Clone)]
 pub struct Functor {
     func: VectorFunction,
     arity: usize,
-    weight: f64
+    weight: f64,
+    key: Option<String>
 }
 
 impl Functor {
-    pub fn new(func: VectorFunction, arity: usize, weight: f64) -> Self { return Self { func, arity, weight }; }
+    pub fn new(func: VectorFunction, arity: usize, weight: f64) -> Self { return Self { func, arity, weight, key: None }; }
+
+    /// Creates functor from function registered under given key.
+    ///
+    /// # Arguments
+    /// * `key: &str` - key of the function in [`registry`][`crate::operators::registry`]
+    /// * `weight: f64` - sampling weight for operator
+    ///
+    /// # Returns
+    /// * `Result<Self, RegistryError>` - functor or [`error`][`RegistryError`] if key is unknown
+    pub fn registered(key: &str, weight: f64) -> Result<Self, RegistryError> {
+        let (func, arity) = lookup(key).ok_or_else(|| RegistryError::UnknownKey(key.to_string()))?;
+        return Ok(Self { func, arity, weight, key: Some(key.to_string()) });
+    }
 
     pub fn arity(&self) -> usize { return self.arity; }
     pub fn weight(&self) -> f64 { return self.weight; }
     pub fn func(&self) -> &VectorFunction { return &self.func; }
+    pub fn key(&self) -> Option<&str> { return self.key.as_deref(); }
+}
+
+/// Errors that can occur during conversion of [`Operators`] to and from serialized form.
+///
+/// # Variants
+/// * `UnregisteredFunction(String)` - operator was added from raw function pointer instead of
+///   [`registry`][`crate::operators::registry`] key
+/// * `UnsupportedTerminal(String)` - terminal has no serialized form, e.g. ephemeral generator
+/// * `Registry(RegistryError)` - function could not be resolved, see [`RegistryError`]
+/// * `Builder(BuilderError)` - loaded set is invalid, see [`BuilderError`]
+#[derive(Debug)]
+pub enum SerializationError {
+    UnregisteredFunction(String),
+    UnsupportedTerminal(String),
+    Registry(RegistryError),
+    Builder(BuilderError)
+}
+
+impl std::error::Error for SerializationError {}
+
+impl fmt::Display for SerializationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerializationError::UnregisteredFunction(name) => write!(f, "Operator {} is not backed by registered function!", name),
+            SerializationError::UnsupportedTerminal(name) => write!(f, "Terminal {} cannot be serialized!", name),
+            SerializationError::Registry(error) => write!(f, "{}", error),
+            SerializationError::Builder(error) => write!(f, "Invalid operator set: {}", error),
+        }
+    }
+}
+
+/// Serialized form of function operator.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct FunctionRepr {
+    name: String,
+    function: String,
+    arity: usize,
+    weight: f64
+}
+
+/// Serialized form of terminal operator.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "lowercase"))]
+enum TerminalRepr {
+    Variable { name: String, weight: f64 },
+    Constant { value: f64, weight: f64 }
+}
+
+/// Serialized form of [`Operators`], storing registry keys instead of function pointers.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct OperatorsRepr {
+    functions: Vec<FunctionRepr>,
+    terminals: Vec<TerminalRepr>
+}
+
+impl TryFrom<&Operators> for OperatorsRepr {
+    type Error = SerializationError;
+
+    fn try_from(operators: &Operators) -> Result<Self, Self::Error> {
+        let mut functions = operators.functions.iter()
+            .map(|(name, functor)| {
+                let key = functor.key().ok_or_else(|| SerializationError::UnregisteredFunction(name.clone()))?;
+                return Ok(FunctionRepr { name: name.clone(), function: key.to_string(), arity: functor.arity(), weight: functor.weight() });
+            })
+            .collect::<Result<Vec<FunctionRepr>, SerializationError>>()?;
+        // Sorted so the same set always serializes the same way
+        functions.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let terminals = operators.terminals.iter().zip(operators.terminal_sampler.weights().iter())
+            .map(|(terminal, &weight)| match terminal {
+                NodeType::Variable(name) => Ok(TerminalRepr::Variable { name: name.clone(), weight }),
+                NodeType::Constant(value) => Ok(TerminalRepr::Constant { value: *value, weight }),
+                other => Err(SerializationError::UnsupportedTerminal(other.name())),
+            })
+            .collect::<Result<Vec<TerminalRepr>, SerializationError>>()?;
+        return Ok(Self { functions, terminals });
+    }
+}
+
+impl TryFrom<OperatorsRepr> for Operators {
+    type Error = SerializationError;
+
+    fn try_from(repr: OperatorsRepr) -> Result<Self, Self::Error> {
+        let mut builder = OperatorsBuilder::default();
+        for function in repr.functions {
+            let (_, arity) = lookup(&function.function).ok_or_else(|| SerializationError::Registry(RegistryError::UnknownKey(function.function.clone())))?;
+            if arity != function.arity {
+                return Err(SerializationError::Registry(RegistryError::ArityMismatch { key: function.function, expected: arity, found: function.arity }));
+            }
+            builder = builder.add_registered(&function.name, &function.function, function.weight).map_err(SerializationError::Builder)?;
+        }
+        for terminal in repr.terminals {
+            builder = match terminal {
+                TerminalRepr::Variable { name, weight } => builder.add_variable(&name, weight),
+                TerminalRepr::Constant { value, weight } => builder.add_constant(value, weight),
+            }.map_err(SerializationError::Builder)?;
+        }
+        return builder.build().map_err(SerializationError::Builder);
+    }
+}
+
+/// Operators are serialized with their names, registry keys, arities and weights. Serialization
+/// fails for operators added from raw function pointers and for ephemeral generators.
+#[cfg(feature = "serde")]
+impl Serialize for Operators {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return OperatorsRepr::try_from(self).map_err(serde::ser::Error::custom)?.serialize(serializer);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Operators {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        return Operators::try_from(OperatorsRepr::deserialize(deserializer)?).map_err(serde::de::Error::custom);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,176 @@
+//! Checkpointing of evolutionary runs.
+//!
+//! This module provides [`Checkpoint`], a snapshot of a run taken after a generation, and
+//! [`Checkpointer`] which periodically writes checkpoints to disk. A run resumed from a checkpoint
+//! with [`EA::resume`][`crate::optimizers::ga::EA::resume`] continues exactly as the interrupted
+//! run would have, provided the random number generator state is serializable (e.g.
+//! `rand_chacha::ChaCha8Rng` with its `serde` feature) and the evaluator is deterministic.
+use std::fmt::{Display, Formatter, Result};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::optimizers::stats::GenerationStats;
+
+/// Errors that can occur while writing or reading checkpoints.
+///
+/// # Variants
+/// * `InvalidInterval` - checkpoint interval is zero
+/// * `IoError(std::io::Error)` - reading or writing checkpoint file failed
+/// * `SerializationError(String)` - checkpoint could not be encoded or decoded
+#[derive(Debug)]
+pub enum CheckpointError {
+    InvalidInterval,
+    IoError(std::io::Error),
+    SerializationError(String)
+}
+
+impl std::error::Error for CheckpointError {}
+
+impl Display for CheckpointError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            CheckpointError::InvalidInterval => write!(f, "Checkpoint interval must be positive!"),
+            CheckpointError::IoError(error) => write!(f, "IO error: {}", error),
+            CheckpointError::SerializationError(message) => write!(f, "Serialization error: {}", message),
+        }
+    }
+}
+
+/// Snapshot of an evolutionary run after a completed generation.
+///
+/// # Type Parameters
+/// * `I` - type of individuals
+/// * `R` - type of random number generator
+///
+/// # Fields
+/// * `population: Vec<I>` - current population with fitness values
+/// * `best: I` - best individual found so far
+/// * `history: Vec<GenerationStats>` - per-generation statistics up to the snapshot
+/// * `generation: usize` - number of generations performed after initialization
+/// * `evaluations: usize` - total number of fitness evaluations so far
+/// * `elapsed: Duration` - wall-clock time spent in the run so far
+/// * `rng: R` - state of the random number generator right after the generation
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Checkpoint<I, R> {
+    population: Vec<I>,
+    best: I,
+    history: Vec<GenerationStats>,
+    generation: usize,
+    evaluations: usize,
+    elapsed: Duration,
+    rng: R
+}
+
+impl<I, R> Checkpoint<I, R> {
+    pub fn new(
+        population: Vec<I>, best: I, history: Vec<GenerationStats>,
+        generation: usize, evaluations: usize, elapsed: Duration, rng: R
+    ) -> Self {
+        return Self { population, best, history, generation, evaluations, elapsed, rng };
+    }
+
+    pub fn population(&self) -> &[I] { return &self.population; }
+    pub fn best(&self) -> &I { return &self.best; }
+    pub fn history(&self) -> &[GenerationStats] { return &self.history; }
+    pub fn generation(&self) -> usize { return self.generation; }
+    pub fn evaluations(&self) -> usize { return self.evaluations; }
+    pub fn elapsed(&self) -> Duration { return self.elapsed; }
+    pub fn rng(&self) -> &R { return &self.rng; }
+
+    /// Splits checkpoint into run progress and random number generator.
+    pub(crate) fn into_parts(self) -> (Progress<I>, R) {
+        let progress = Progress {
+            population: self.population, best: self.best, history: self.history,
+            generation: self.generation, evaluations: self.evaluations, elapsed: self.elapsed
+        };
+        return (progress, self.rng);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I: Serialize, R: Serialize> Checkpoint<I, R> {
+    /// Writes checkpoint to file in compact binary (CBOR) form.
+    ///
+    /// The checkpoint is first written to a temporary file next to `path` and then renamed, so a
+    /// crash while writing never leaves a truncated checkpoint behind.
+    pub fn save(&self, path: &Path) -> std::result::Result<(), CheckpointError> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes).map_err(|e| CheckpointError::SerializationError(e.to_string()))?;
+
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        std::fs::write(&temporary, bytes).map_err(CheckpointError::IoError)?;
+        return std::fs::rename(&temporary, path).map_err(CheckpointError::IoError);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I: DeserializeOwned, R: DeserializeOwned> Checkpoint<I, R> {
+    /// Reads checkpoint written by [`save`][`Checkpoint::save`].
+    pub fn load(path: &Path) -> std::result::Result<Self, CheckpointError> {
+        let bytes = std::fs::read(path).map_err(CheckpointError::IoError)?;
+        return ciborium::from_reader(bytes.as_slice()).map_err(|e| CheckpointError::SerializationError(e.to_string()));
+    }
+}
+
+/// State of a run shared by the evolutionary loop and checkpoints.
+pub(crate) struct Progress<I> {
+    pub(crate) population: Vec<I>,
+    pub(crate) best: I,
+    pub(crate) history: Vec<GenerationStats>,
+    pub(crate) generation: usize,
+    pub(crate) evaluations: usize,
+    pub(crate) elapsed: Duration
+}
+
+#[cfg(feature = "serde")]
+impl<I: Clone> Progress<I> {
+    pub(crate) fn checkpoint<R: Clone>(&self, rng: &R, elapsed: Duration) -> Checkpoint<I, R> {
+        return Checkpoint::new(
+            self.population.clone(), self.best.clone(), self.history.clone(),
+            self.generation, self.evaluations, elapsed, rng.clone()
+        );
+    }
+}
+
+/// Periodically writes checkpoints of a run to a single file.
+///
+/// Checkpoints are written after initialization, after every `interval`-th generation and when
+/// the run terminates, each one replacing the previous.
+///
+/// # Fields
+/// * `path: PathBuf` - path of the checkpoint file
+/// * `interval: usize` - number of generations between checkpoints
+///
+/// # Examples
+/// ```
+/// use mycoforge::optimizers::checkpoint::Checkpointer;
+///
+/// let checkpointer = Checkpointer::new("run.ckpt", 10).expect("Failed to create checkpointer!");
+///
+/// assert!(checkpointer.is_due(20));
+/// assert!(!checkpointer.is_due(25));
+/// assert!(Checkpointer::new("run.ckpt", 0).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Checkpointer {
+    path: PathBuf,
+    interval: usize
+}
+
+impl Checkpointer {
+    pub fn new(path: impl Into<PathBuf>, interval: usize) -> std::result::Result<Self, CheckpointError> {
+        if interval == 0 { return Err(CheckpointError::InvalidInterval); }
+        return Ok(Self { path: path.into(), interval });
+    }
+
+    pub fn path(&self) -> &Path { return &self.path; }
+    pub fn interval(&self) -> usize { return self.interval; }
+
+    /// Returns whether checkpoint should be written after given generation.
+    pub fn is_due(&self, generation: usize) -> bool { return generation.is_multiple_of(self.interval); }
+}
@@ -1,14 +1,29 @@
 use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
+use log::{debug, info, warn};
 use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::Serialize;
 
-use crate::common::traits::{Crossoverer, Evaluator, Genotype, Individual, Initializer, Mutator, Optimizer, Selector};
+use crate::common::cache::FitnessCache;
+use crate::common::objectives::Direction;
+use crate::common::traits::{Crossoverer, Data, Evaluator, Genotype, Individual, Mutator, Optimizer, PopulationInitializer, Selector};
 use crate::common::types::VectorFunction;
 use crate::operators::sampler::OperatorSampler;
+use crate::optimizers::checkpoint::{Checkpoint, Progress};
+#[cfg(feature = "serde")]
+use crate::optimizers::checkpoint::Checkpointer;
+use crate::optimizers::replacement::Replacement;
+use crate::optimizers::stats::GenerationStats;
+use crate::optimizers::steady_state::{SteadyStateEA, SteadyStateReplacement};
+use crate::optimizers::termination::{RunState, TerminationCriterion};
 
 pub trait EAComponents<G: Genotype> {
     type I: Individual<G>;
-    type Init: Initializer<G>;
+    type Init: PopulationInitializer<G>;
     type Mut: Mutator<G>;
     type Eval: Evaluator<G>;
     type Cross: Crossoverer<G>;
@@ -23,7 +38,10 @@ pub struct EA<C: EAComponents<G>, G: Genotype>
     evaluator:      C::Eval,
     selector:       C::Sel,
     sampler:        OperatorSampler,
-    map:            HashMap<String, (usize, VectorFunction)>
+    map:            HashMap<String, (usize, VectorFunction)>,
+    replacement:    Replacement,
+    offspring_size: Option<usize>,
+    cache:          Option<Mutex<FitnessCache<G>>>,
 }
 
 impl<C, G> EA<C, G> 
@@ -35,11 +53,299 @@ where
         mutator: C::Mut, crossoverer: C::Cross, evaluator: C::Eval, selector: C::Sel, 
         sampler: OperatorSampler, map: HashMap<String, (usize, VectorFunction)>) -> Self 
     {
-        return Self { initializer, mutator, crossoverer, evaluator, selector, sampler, map };
+        return Self { 
+            initializer, mutator, crossoverer, evaluator, selector, sampler, map,
+            replacement: Replacement::default(), offspring_size: None, cache: None
+        };
     }
 
     pub fn evaluator(&self) -> &C::Eval { return &self.evaluator; }
     pub fn map(&self) -> &HashMap<String, (usize, VectorFunction)> { return &self.map; }
+    pub fn replacement(&self) -> Replacement { return self.replacement; }
+    /// Returns number of offspring created per generation, `None` means population size.
+    pub fn offspring_size(&self) -> Option<usize> { return self.offspring_size; }
+    /// Returns fitness cache used by [`EA::run`], `None` if caching is disabled.
+    pub fn cache(&self) -> Option<&Mutex<FitnessCache<G>>> { return self.cache.as_ref(); }
+
+    /// Evaluates genotypes, reusing cached fitness values if the cache is enabled.
+    ///
+    /// Only distinct cache misses are passed to the evaluator. Results are inserted in the order
+    /// of `genotypes`, so cache contents do not depend on how the evaluator schedules its work.
+    fn evaluate_population(&self, genotypes: &[G], data: &<C::Eval as Evaluator<G>>::D) -> Vec<f64> 
+    where
+        G: Hash + Eq,
+    {
+        let Some(cache) = &self.cache else {
+            return self.evaluator.evaluate_population(genotypes, data, &self.map);
+        };
+        let mut cache = cache.lock().expect("Fitness cache lock should not be poisoned!");
+        let dataset = data.fingerprint();
+
+        let cached = genotypes.iter().map(|g| cache.get(dataset, g)).collect::<Vec<Option<f64>>>();
+        let mut misses: Vec<G> = Vec::new();
+        let mut positions: HashMap<&G, usize> = HashMap::new();
+        for (genotype, fitness) in genotypes.iter().zip(&cached) {
+            if fitness.is_none() && !positions.contains_key(genotype) {
+                positions.insert(genotype, misses.len());
+                misses.push(genotype.clone());
+            }
+        }
+
+        let computed = self.evaluator.evaluate_population(&misses, data, &self.map);
+        for (genotype, &fitness) in misses.into_iter().zip(&computed) {
+            cache.insert(dataset, genotype, fitness);
+        }
+
+        return genotypes.iter().zip(cached)
+            .map(|(genotype, fitness)| fitness.unwrap_or_else(|| computed[positions[genotype]]))
+            .collect();
+    }
+
+    /// Evaluates genotypes into individuals.
+    ///
+    /// If the selector uses per-case errors, genotypes are evaluated with
+    /// [`Evaluator::evaluate_population_cases`] and the fitness cache is bypassed, as it stores
+    /// only fitness values.
+    fn evaluate_individuals(&self, genotypes: &[G], data: &<C::Eval as Evaluator<G>>::D) -> Vec<C::I> 
+    where
+        G: Hash + Eq,
+    {
+        if self.selector.uses_cases() {
+            let evaluations = self.evaluator.evaluate_population_cases(genotypes, data, &self.map);
+            return C::I::from_evaluations(genotypes, evaluations);
+        }
+        return C::I::from_vecs(genotypes, &self.evaluate_population(genotypes, data));
+    }
+
+    /// Runs full generational loop until any of the termination criteria is met.
+    ///
+    /// Initializes population, evaluates it and then repeatedly selects and varies parents,
+    /// evaluates offspring and combines both according to configured
+    /// [`Replacement`][`crate::optimizers::replacement::Replacement`] strategy.
+    /// Criteria are checked after every generation. If the cache is enabled (see
+    /// [`EABuilder::set_cache_capacity`]), previously seen genotypes are not evaluated again.
+    ///
+    /// # Arguments
+    /// * `rng: &mut R` - random number generator
+    /// * `population_size: usize` - size of the population
+    /// * `data: &D` - dataset used by the evaluator
+    /// * `criteria: &[Box<dyn TerminationCriterion>]` - stopping rules, see
+    ///   [`TerminationCriterion`][`crate::optimizers::termination::TerminationCriterion`]
+    ///
+    /// # Returns
+    /// * `RunResult<C::I>` - best individual found, final population and per-generation history
+    ///
+    /// # Panics
+    /// * If no termination criteria are provided
+    pub fn run<R: Rng>(&self, 
+        rng: &mut R, population_size: usize, data: &<C::Eval as Evaluator<G>>::D,
+        criteria: &[Box<dyn TerminationCriterion>]
+    ) -> RunResult<C::I> 
+    where
+        C::I: Clone,
+        G: Hash + Eq,
+    {
+        assert!(!criteria.is_empty(), "At least one termination criterion is required!");
+        let progress = self.initialize(rng, population_size, data);
+        return self.evolve(rng, progress, data, criteria, |_, _, _| {});
+    }
+
+    /// Continues run from a [`Checkpoint`][`crate::optimizers::checkpoint::Checkpoint`] until
+    /// any of the termination criteria is met.
+    ///
+    /// Resumed run follows exactly the same trajectory as the run the checkpoint was taken from
+    /// would have, if the evaluator is deterministic. Elapsed time includes time spent before the
+    /// checkpoint.
+    ///
+    /// # Arguments
+    /// * `checkpoint: Checkpoint<C::I, R>` - snapshot of the interrupted run
+    /// * `data: &D` - dataset used by the evaluator
+    /// * `criteria: &[Box<dyn TerminationCriterion>]` - stopping rules
+    ///
+    /// # Returns
+    /// * `RunResult<C::I>` - result of the whole run, including history before the checkpoint
+    ///
+    /// # Panics
+    /// * If no termination criteria are provided
+    pub fn resume<R: Rng>(&self, 
+        checkpoint: Checkpoint<C::I, R>, data: &<C::Eval as Evaluator<G>>::D,
+        criteria: &[Box<dyn TerminationCriterion>]
+    ) -> RunResult<C::I> 
+    where
+        C::I: Clone,
+        G: Hash + Eq,
+    {
+        assert!(!criteria.is_empty(), "At least one termination criterion is required!");
+        let (progress, mut rng) = checkpoint.into_parts();
+        return self.evolve(&mut rng, progress, data, criteria, |_, _, _| {});
+    }
+
+    /// Initializes and evaluates population.
+    fn initialize<R: Rng>(&self, rng: &mut R, population_size: usize, data: &<C::Eval as Evaluator<G>>::D) -> Progress<C::I> 
+    where
+        C::I: Clone,
+        G: Hash + Eq,
+    {
+        let start = Instant::now();
+        let genotypes = self.init_population(rng, population_size);
+        let population = self.evaluate_individuals(&genotypes, data);
+        let fitnesses = population.iter().map(|i| i.phenotype()).collect::<Vec<f64>>();
+        let evaluations = fitnesses.len();
+
+        let history = vec![GenerationStats::from_fitnesses(0, evaluations, &fitnesses, self.evaluator.direction())];
+        let best = best_individual(&population, self.evaluator.direction()).clone();
+        return Progress { population, best, history, generation: 0, evaluations, elapsed: start.elapsed() };
+    }
+
+    /// Runs generational loop from given progress, calling `on_generation` with the progress,
+    /// generator state and elapsed time after every generation and once more at the end.
+    fn evolve<R: Rng, F: FnMut(&Progress<C::I>, &R, Duration)>(&self, 
+        rng: &mut R, mut progress: Progress<C::I>, data: &<C::Eval as Evaluator<G>>::D,
+        criteria: &[Box<dyn TerminationCriterion>], mut on_generation: F
+    ) -> RunResult<C::I> 
+    where
+        C::I: Clone,
+        G: Hash + Eq,
+    {
+        let start = Instant::now();
+        let offset = progress.elapsed;
+        let direction = self.evaluator.direction();
+        let elapsed = || offset + start.elapsed();
+
+        while !criteria.iter().any(|c| c.should_terminate(&RunState::new(progress.generation, progress.evaluations, elapsed(), &progress.history, direction))) {
+            let offspring = self.optimize(rng, &progress.population);
+            let offspring = self.evaluate_individuals(&offspring, data);
+            progress.evaluations += offspring.len();
+            progress.generation += 1;
+
+            progress.population = self.replacement.replace(&progress.population, &offspring, direction);
+            let candidate = best_individual(&progress.population, direction);
+            if direction.is_better(candidate.phenotype(), progress.best.phenotype()) { progress.best = candidate.clone(); }
+
+            let fitnesses = progress.population.iter().map(|i| i.phenotype()).collect::<Vec<f64>>();
+            let stats = GenerationStats::from_fitnesses(progress.generation, progress.evaluations, &fitnesses, direction);
+            debug!("Generation {}: best {}, mean {}, worst {}", 
+                progress.generation, stats.best_fitness(), stats.mean_fitness(), stats.worst_fitness()
+            );
+            progress.history.push(stats);
+            on_generation(&progress, rng, elapsed());
+        }
+        info!("Finished run after {} generations and {} evaluations with best fitness {}", 
+            progress.generation, progress.evaluations, progress.best.phenotype()
+        );
+
+        return RunResult::new(progress.best, progress.population, progress.history, progress.evaluations, elapsed());
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C, G> EA<C, G> 
+where
+    G: Genotype + Hash + Eq,
+    C: EAComponents<G>,
+    C::I: Clone + Serialize,
+{
+    /// Runs like [`EA::run`], writing checkpoints with given
+    /// [`Checkpointer`][`crate::optimizers::checkpoint::Checkpointer`] after initialization,
+    /// periodically during the run and at its end. Failure to write a checkpoint is logged and
+    /// does not stop the run.
+    ///
+    /// # Arguments
+    /// * `rng: &mut R` - random number generator, its state is stored in checkpoints
+    /// * `population_size: usize` - size of the population
+    /// * `data: &D` - dataset used by the evaluator
+    /// * `criteria: &[Box<dyn TerminationCriterion>]` - stopping rules
+    /// * `checkpointer: &Checkpointer` - checkpoint location and interval
+    ///
+    /// # Returns
+    /// * `RunResult<C::I>` - best individual found, final population and per-generation history
+    ///
+    /// # Panics
+    /// * If no termination criteria are provided
+    pub fn run_checkpointed<R: Rng + Clone + Serialize>(&self, 
+        rng: &mut R, population_size: usize, data: &<C::Eval as Evaluator<G>>::D,
+        criteria: &[Box<dyn TerminationCriterion>], checkpointer: &Checkpointer
+    ) -> RunResult<C::I> {
+        assert!(!criteria.is_empty(), "At least one termination criterion is required!");
+        let progress = self.initialize(rng, population_size, data);
+        write_checkpoint(checkpointer, &progress.checkpoint(rng, progress.elapsed));
+
+        return self.checkpointed_evolve(rng, progress, data, criteria, checkpointer);
+    }
+
+    /// Continues run like [`EA::resume`], writing checkpoints like [`EA::run_checkpointed`].
+    pub fn resume_checkpointed<R: Rng + Clone + Serialize>(&self, 
+        checkpoint: Checkpoint<C::I, R>, data: &<C::Eval as Evaluator<G>>::D,
+        criteria: &[Box<dyn TerminationCriterion>], checkpointer: &Checkpointer
+    ) -> RunResult<C::I> {
+        assert!(!criteria.is_empty(), "At least one termination criterion is required!");
+        let (progress, mut rng) = checkpoint.into_parts();
+        return self.checkpointed_evolve(&mut rng, progress, data, criteria, checkpointer);
+    }
+
+    fn checkpointed_evolve<R: Rng + Clone + Serialize>(&self, 
+        rng: &mut R, progress: Progress<C::I>, data: &<C::Eval as Evaluator<G>>::D,
+        criteria: &[Box<dyn TerminationCriterion>], checkpointer: &Checkpointer
+    ) -> RunResult<C::I> {
+        let result = self.evolve(rng, progress, data, criteria, |progress, rng, elapsed| {
+            if checkpointer.is_due(progress.generation) {
+                write_checkpoint(checkpointer, &progress.checkpoint(rng, elapsed));
+            }
+        });
+        // Final generation is always saved, so the run can be extended later
+        if !checkpointer.is_due(result.generations()) {
+            write_checkpoint(checkpointer, &Checkpoint::new(
+                result.population().to_vec(), result.best().clone(), result.history().to_vec(),
+                result.generations(), result.evaluations(), result.elapsed(), rng.clone()
+            ));
+        }
+        return result;
+    }
+}
+
+#[cfg(feature = "serde")]
+fn write_checkpoint<I: Serialize, R: Serialize>(checkpointer: &Checkpointer, checkpoint: &Checkpoint<I, R>) {
+    match checkpoint.save(checkpointer.path()) {
+        Ok(()) => debug!("Saved checkpoint of generation {} to {}", checkpoint.generation(), checkpointer.path().display()),
+        Err(error) => warn!("Failed to save checkpoint of generation {}: {}", checkpoint.generation(), error),
+    }
+}
+
+/// Returns individual with the best fitness value according to `direction`.
+pub(crate) fn best_individual<G: Genotype, I: Individual<G>>(population: &[I], direction: Direction) -> &I {
+    return population.iter()
+        .min_by(|a, b| direction.compare(a.phenotype(), b.phenotype()))
+        .expect("Population should not be empty!");
+}
+
+/// Outcome of a complete evolutionary run.
+///
+/// # Fields
+/// * `best: I` - best individual found during the whole run
+/// * `population: Vec<I>` - final population
+/// * `history: Vec<GenerationStats>` - per-generation statistics, including initial population
+/// * `evaluations: usize` - total number of fitness evaluations performed
+/// * `elapsed: Duration` - wall-clock duration of the run
+pub struct RunResult<I> {
+    best: I,
+    population: Vec<I>,
+    history: Vec<GenerationStats>,
+    evaluations: usize,
+    elapsed: Duration
+}
+
+impl<I> RunResult<I> {
+    pub fn new(best: I, population: Vec<I>, history: Vec<GenerationStats>, evaluations: usize, elapsed: Duration) -> Self {
+        return Self { best, population, history, evaluations, elapsed };
+    }
+
+    pub fn best(&self) -> &I { return &self.best; }
+    pub fn population(&self) -> &[I] { return &self.population; }
+    pub fn history(&self) -> &[GenerationStats] { return &self.history; }
+    pub fn evaluations(&self) -> usize { return self.evaluations; }
+    pub fn elapsed(&self) -> Duration { return self.elapsed; }
+    /// Returns number of generations performed after initialization.
+    pub fn generations(&self) -> usize { return self.history.len() - 1; }
 }
 
 impl<C, G> Optimizer<G> for EA<C, G> 
@@ -49,25 +355,30 @@ where
 {
     type I = C::I;
     fn init_population<R: Rng>(&self, rng: &mut R, population_size: usize) -> Vec<G> {
-        return (0..population_size).map(|_| self.initializer.initialize(rng, &self.sampler)).collect();
+        return self.initializer.initialize_population(rng, &self.sampler, population_size);
     }
 
     fn optimize<R: Rng>(&self, rng: &mut R, population: &[Self::I]) -> Vec<G> {
-        let mut offspring: Vec<G> = Vec::with_capacity(population.len());
+        let offspring_size = self.offspring_size.unwrap_or(population.len());
+        let mut offspring: Vec<G> = Vec::with_capacity(offspring_size);
 
-        while offspring.len() < population.len() {
-            let parent1 = self.selector.select(rng, population);
-            let parent2 = self.selector.select(rng, population);
+        // Parents of the whole generation are selected at once, so selectors can share
+        // population-level work (e.g. epsilons of epsilon-lexicase selection)
+        while offspring.len() < offspring_size {
+            let pairs = (offspring_size - offspring.len()).div_ceil(2);
+            let parents = self.selector.select_many(rng, population, 2 * pairs, self.evaluator.direction());
 
-            let children = self.crossoverer.variate(rng, &parent1, &parent2, &self.sampler);
+            for pair in parents.chunks(2) {
+                let children = self.crossoverer.variate(rng, &pair[0], &pair[1], &self.sampler);
 
-            for child in children {
-                offspring.push(
-                    self.mutator.variate(rng, &child, &self.sampler)
-                );
-                if offspring.len() >= population.len() { break; }
+                for child in children {
+                    offspring.push(
+                        self.mutator.variate(rng, &child, &self.sampler)
+                    );
+                    if offspring.len() >= offspring_size { break; }
+                }
+                if offspring.len() >= offspring_size { break; }
             }
-
         }
         return offspring;
     }
@@ -82,6 +393,7 @@ pub enum BuilderError {
    SelectorMissing,
    SamplerMissing,
    MapMissing,
+   PopulationDependentEvaluator,
 }
 
 pub struct EABuilder<C: EAComponents<G>, G: Genotype> {
@@ -91,7 +403,10 @@ pub struct EABuilder<C: EAComponents<G>, G: Genotype> {
     evaluator:      Option<C::Eval>,
     selector:       Option<C::Sel>,
     sampler:        Option<OperatorSampler>,
-    map:            Option<HashMap<String, (usize, VectorFunction)>>
+    map:            Option<HashMap<String, (usize, VectorFunction)>>,
+    replacement:    Option<Replacement>,
+    offspring_size: Option<usize>,
+    cache_capacity: Option<usize>,
 }
 
  impl<C, G> Default for EABuilder<C, G>
@@ -108,10 +423,24 @@ where
     C: EAComponents<G>,
 {
     pub fn new() -> Self {
-        return Self { initializer: None, mutator: None, crossoverer: None, evaluator: None, selector: None, sampler: None, map: None };
+        return Self { 
+            initializer: None, mutator: None, crossoverer: None, evaluator: None, selector: None, sampler: None, map: None,
+            replacement: None, offspring_size: None, cache_capacity: None
+        };
     }
 
+    /// Builds [`EA`], warning if a population-dependent evaluator (see
+    /// [`Evaluator::population_dependent`]) is combined with fitness cache or with replacement
+    /// keeping parents, whose fitness was computed within an earlier population.
     pub fn build(self) -> Result<EA<C, G>, BuilderError> {
+       if self.evaluator.as_ref().is_some_and(|evaluator| evaluator.population_dependent()) {
+           if self.cache_capacity.is_some() {
+               warn!("Fitness cache passes only unseen genotypes to population-dependent evaluator!");
+           }
+           if matches!(self.replacement, Some(Replacement::Elitism(_) | Replacement::MuPlusLambda | Replacement::ReplaceWorst)) {
+               warn!("Replacement keeping parents compares fitness computed within different populations!");
+           }
+       }
        return Ok(EA {
            initializer: self.initializer.ok_or(BuilderError::InitializerMissing)?,
            mutator:     self.mutator.ok_or(BuilderError::MutatorMissing)?,
@@ -120,6 +449,9 @@ where
            selector:    self.selector.ok_or(BuilderError::SelectorMissing)?,
            sampler:     self.sampler.ok_or(BuilderError::SamplerMissing)?,
            map:         self.map.ok_or(BuilderError::MapMissing)?,
+           replacement: self.replacement.unwrap_or_default(),
+           offspring_size: self.offspring_size,
+           cache: self.cache_capacity.map(|capacity| Mutex::new(FitnessCache::new(capacity))),
        })
    }
 
@@ -158,6 +490,54 @@ where
        return self;
    }
 
+   /// Sets replacement strategy, defaults to [`Replacement::Generational`].
+   pub fn set_replacement(mut self, replacement: Replacement) -> Self {
+       self.replacement = Some(replacement);
+       return self;
+   }
+
+   /// Sets number of offspring (lambda) created per generation, defaults to population size.
+   pub fn set_offspring_size(mut self, offspring_size: usize) -> Self {
+       self.offspring_size = Some(offspring_size);
+       return self;
+   }
+
+   /// Enables fitness cache holding at most `capacity` entries, disabled by default.
+   ///
+   /// The cache is not used with selectors relying on per-case errors (see
+   /// [`Selector::uses_cases`][`crate::common::traits::Selector::uses_cases`]).
+   pub fn set_cache_capacity(mut self, capacity: usize) -> Self {
+       self.cache_capacity = Some(capacity);
+       return self;
+   }
+
+    /// Builds [`SteadyStateEA`][`crate::optimizers::steady_state::SteadyStateEA`] from the same
+    /// components. Replacement, offspring size and cache settings are ignored.
+    ///
+    /// # Arguments
+    /// * `replacement: SteadyStateReplacement` - strategy for choosing individual replaced by a child
+    /// * `offspring_per_step: usize` - number of children (1 or 2) inserted after each crossover
+    ///
+    /// # Returns
+    /// * `Result<SteadyStateEA<C, G>, BuilderError>` - steady-state EA or error if a component is
+    ///   missing or the evaluator is population-dependent (see [`Evaluator::population_dependent`])
+    pub fn build_steady_state(self, replacement: SteadyStateReplacement, offspring_per_step: usize) 
+        -> Result<SteadyStateEA<C, G>, BuilderError> {
+        if self.evaluator.as_ref().is_some_and(|evaluator| evaluator.population_dependent()) {
+            return Err(BuilderError::PopulationDependentEvaluator);
+        }
+        return Ok(SteadyStateEA::new(
+            self.initializer.ok_or(BuilderError::InitializerMissing)?,
+            self.mutator.ok_or(BuilderError::MutatorMissing)?,
+            self.crossoverer.ok_or(BuilderError::CrossovererMissing)?,
+            self.evaluator.ok_or(BuilderError::EvaluatorMissing)?,
+            self.selector.ok_or(BuilderError::SelectorMissing)?,
+            self.sampler.ok_or(BuilderError::SamplerMissing)?,
+            self.map.ok_or(BuilderError::MapMissing)?,
+            replacement, offspring_per_step
+        ));
+    }
+
 }
 
 #[macro_export]
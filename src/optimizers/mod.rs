@@ -1 +1,10 @@
 pub mod ga;
+
+pub mod checkpoint;
+
+pub mod nsga2;
+
+pub mod replacement;
+pub mod stats;
+pub mod steady_state;
+pub mod termination;
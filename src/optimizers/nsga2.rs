@@ -0,0 +1,472 @@
+//! NSGA-II multi-objective evolutionary algorithm.
+//!
+//! This module provides [`NSGA2`], which evolves a population towards the Pareto front of several
+//! [`Objectives`][`crate::common::objectives::Objectives`] (e.g. error and tree size), together
+//! with its building blocks usable on their own:
+//! - [`non_dominated_sort`] and [`crowding_distance`] - ranking of fitness vectors
+//! - [`CrowdedTournament`] - [`Selector`][`crate::common::traits::Selector`] preferring lower
+//!   rank and then less crowded individuals
+//! - [`select_survivors`] - replacement keeping best fronts of parents and offspring combined
+//! - [`pareto_front`] - extraction of non-dominated individuals from a population
+//!
+//! Reference: Deb et al., "A fast and elitist multiobjective genetic algorithm: NSGA-II", 2002.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use log::{debug, info};
+use rand::Rng;
+use rand::prelude::IndexedRandom;
+
+use crate::common::objectives::{dominates, Direction, Objectives};
+use crate::common::traits::{Crossoverer, Data, Genotype, Individual, Mutator, Optimizer, PopulationInitializer, Selector};
+use crate::common::types::VectorFunction;
+use crate::operators::sampler::OperatorSampler;
+use crate::optimizers::stats::GenerationStats;
+use crate::optimizers::termination::{RunState, TerminationCriterion};
+
+/// Individual with a vector of objective values.
+///
+/// Rank and crowding distance are assigned by [`rank_population`] (and [`select_survivors`]),
+/// until then both are 0. As an [`Individual`][`crate::common::traits::Individual`], its scalar
+/// fitness is the rank, so single-objective components compare fronts.
+///
+/// # Fields
+/// * `genotype: G` - [`Genotype`][`crate::common::traits::Genotype`] representation
+/// * `objectives: Vec<f64>` - objective values
+/// * `rank: usize` - index of the non-dominated front, 0 for the Pareto front
+/// * `crowding_distance: f64` - crowding distance within the front, infinite for boundary points
+#[derive(Debug, Clone)]
+pub struct MultiObjectiveIndividual<G: Genotype> {
+    genotype: G,
+    objectives: Vec<f64>,
+    rank: usize,
+    crowding_distance: f64
+}
+
+impl<G: Genotype> MultiObjectiveIndividual<G> {
+    /// Creates new unranked individual with given genotype and objective values.
+    pub fn new(genotype: G, objectives: Vec<f64>) -> Self {
+        return Self { genotype, objectives, rank: 0, crowding_distance: 0.0 };
+    }
+
+    pub fn objectives(&self) -> &[f64] { return &self.objectives; }
+    pub fn rank(&self) -> usize { return self.rank; }
+    pub fn crowding_distance(&self) -> f64 { return self.crowding_distance; }
+}
+
+impl<G: Genotype> Individual<G> for MultiObjectiveIndividual<G> {
+    fn genotype(&self) -> &G { return &self.genotype; }
+    fn phenotype(&self) -> f64 { return self.rank as f64; }
+
+    /// Creates single-objective individuals.
+    fn from_vecs(genotypes: &[G], fitness: &[f64]) -> Vec<Self> {
+        return genotypes.iter().zip(fitness.iter()).map(|(g, &f)| Self::new(g.clone(), vec![f])).collect();
+    }
+    fn from_genotype_vec(genotypes: &[G]) -> Vec<Self> {
+        return genotypes.iter().map(|g| Self::new(g.clone(), Vec::new())).collect();
+    }
+    fn to_genotype_vec(individuals: &[Self]) -> Vec<G> {
+        return individuals.iter().map(|i| i.genotype().clone()).collect();
+    }
+}
+
+/// Sorts fitness vectors into non-dominated fronts (fast non-dominated sort).
+///
+/// # Arguments
+/// * `objectives: &[Vec<f64>]` - fitness vector of every individual
+/// * `directions: &[Direction]` - direction of every objective
+///
+/// # Returns
+/// * `Vec<Vec<usize>>` - fronts of indices into `objectives`, from the Pareto front on, each in
+///   ascending order
+///
+/// # Examples
+/// ```
+/// use mycoforge::common::objectives::Direction;
+/// use mycoforge::optimizers::nsga2::non_dominated_sort;
+///
+/// let objectives = vec![vec![1.0, 4.0], vec![2.0, 2.0], vec![3.0, 3.0], vec![4.0, 1.0], vec![4.0, 4.0]];
+/// let fronts = non_dominated_sort(&objectives, &[Direction::Minimize; 2]);
+///
+/// assert_eq!(fronts, vec![vec![0, 1, 3], vec![2], vec![4]]);
+/// ```
+pub fn non_dominated_sort(objectives: &[Vec<f64>], directions: &[Direction]) -> Vec<Vec<usize>> {
+    let n = objectives.len();
+    let mut dominated: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut domination_count = vec![0usize; n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if dominates(&objectives[i], &objectives[j], directions) {
+                dominated[i].push(j);
+                domination_count[j] += 1;
+            } else if dominates(&objectives[j], &objectives[i], directions) {
+                dominated[j].push(i);
+                domination_count[i] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+    let mut current = (0..n).filter(|&i| domination_count[i] == 0).collect::<Vec<usize>>();
+    while !current.is_empty() {
+        let mut next = Vec::new();
+        for &i in &current {
+            for &j in &dominated[i] {
+                domination_count[j] -= 1;
+                if domination_count[j] == 0 { next.push(j); }
+            }
+        }
+        next.sort_unstable();
+        fronts.push(current);
+        current = next;
+    }
+    return fronts;
+}
+
+/// Computes crowding distance of individuals in a single front.
+///
+/// Distance is the sum over objectives of the normalized gap between the two neighbours, boundary
+/// individuals of every objective get infinity. Objectives with zero or non-finite range in the
+/// front do not contribute.
+///
+/// # Arguments
+/// * `objectives: &[Vec<f64>]` - fitness vector of every individual
+/// * `front: &[usize]` - indices of individuals in the front
+/// * `directions: &[Direction]` - direction of every objective
+///
+/// # Returns
+/// * `Vec<f64>` - crowding distance of every individual of `front`, in the same order
+pub fn crowding_distance(objectives: &[Vec<f64>], front: &[usize], directions: &[Direction]) -> Vec<f64> {
+    let size = front.len();
+    if size <= 2 { return vec![f64::INFINITY; size]; }
+
+    let mut distances = vec![0.0; size];
+    for (k, direction) in directions.iter().enumerate() {
+        let keys = front.iter().map(|&i| direction.key(objectives[i][k])).collect::<Vec<f64>>();
+        let mut order = (0..size).collect::<Vec<usize>>();
+        order.sort_by(|&a, &b| keys[a].total_cmp(&keys[b]));
+
+        distances[order[0]] = f64::INFINITY;
+        distances[order[size - 1]] = f64::INFINITY;
+        let range = keys[order[size - 1]] - keys[order[0]];
+        if !(range.is_finite() && range > 0.0) { continue; }
+        for position in 1..(size - 1) {
+            distances[order[position]] += (keys[order[position + 1]] - keys[order[position - 1]]) / range;
+        }
+    }
+    return distances;
+}
+
+fn objective_vectors<G: Genotype>(individuals: &[MultiObjectiveIndividual<G>]) -> Vec<Vec<f64>> {
+    return individuals.iter().map(|i| i.objectives.clone()).collect();
+}
+
+/// Assigns rank and crowding distance to every individual of the population.
+pub fn rank_population<G: Genotype>(population: &mut [MultiObjectiveIndividual<G>], directions: &[Direction]) {
+    let objectives = objective_vectors(population);
+    for (rank, front) in non_dominated_sort(&objectives, directions).into_iter().enumerate() {
+        let distances = crowding_distance(&objectives, &front, directions);
+        for (&i, distance) in front.iter().zip(distances) {
+            population[i].rank = rank;
+            population[i].crowding_distance = distance;
+        }
+    }
+}
+
+/// NSGA-II replacement, selects `count` survivors out of parents and offspring combined.
+///
+/// Whole fronts are taken from the Pareto front on, the front that does not fit is truncated by
+/// keeping its least crowded individuals. Survivors are ranked within `individuals`.
+///
+/// # Arguments
+/// * `individuals: Vec<MultiObjectiveIndividual<G>>` - parents and offspring combined
+/// * `count: usize` - number of survivors
+/// * `directions: &[Direction]` - direction of every objective
+///
+/// # Returns
+/// * `Vec<MultiObjectiveIndividual<G>>` - survivors ordered by rank
+pub fn select_survivors<G: Genotype>(
+    individuals: Vec<MultiObjectiveIndividual<G>>, count: usize, directions: &[Direction]
+) -> Vec<MultiObjectiveIndividual<G>> {
+    let objectives = objective_vectors(&individuals);
+    let mut individuals = individuals.into_iter().map(Some).collect::<Vec<Option<MultiObjectiveIndividual<G>>>>();
+    let mut survivors = Vec::with_capacity(count.min(individuals.len()));
+
+    for (rank, front) in non_dominated_sort(&objectives, directions).into_iter().enumerate() {
+        if survivors.len() >= count { break; }
+        let distances = crowding_distance(&objectives, &front, directions);
+        let mut order = (0..front.len()).collect::<Vec<usize>>();
+        if survivors.len() + front.len() > count {
+            order.sort_by(|&a, &b| distances[b].total_cmp(&distances[a]));
+            order.truncate(count - survivors.len());
+        }
+        for position in order {
+            let mut survivor = individuals[front[position]].take().expect("Individual should be selected once!");
+            survivor.rank = rank;
+            survivor.crowding_distance = distances[position];
+            survivors.push(survivor);
+        }
+    }
+    return survivors;
+}
+
+/// Extracts non-dominated individuals of the population.
+///
+/// # Arguments
+/// * `population: &[MultiObjectiveIndividual<G>]` - evaluated individuals
+/// * `directions: &[Direction]` - direction of every objective
+///
+/// # Returns
+/// * `Vec<MultiObjectiveIndividual<G>>` - Pareto front sorted from best to worst in the first
+///   objective
+pub fn pareto_front<G: Genotype>(population: &[MultiObjectiveIndividual<G>], directions: &[Direction]) -> Vec<MultiObjectiveIndividual<G>> {
+    let objectives = objective_vectors(population);
+    let Some(front) = non_dominated_sort(&objectives, directions).into_iter().next() else { return Vec::new(); };
+    let mut front = front.into_iter().map(|i| population[i].clone()).collect::<Vec<MultiObjectiveIndividual<G>>>();
+    if let Some(direction) = directions.first() {
+        front.sort_by(|a, b| direction.compare(a.objectives[0], b.objectives[0]));
+    }
+    return front;
+}
+
+/// Crowded tournament selection operator of NSGA-II.
+///
+/// Samples random subset and selects individual with the lowest rank, ties are broken by larger
+/// crowding distance. Population must be ranked, see [`rank_population`].
+///
+/// # Fields
+/// * `tournament_size: usize` - number of individuals randomly sampled for tournament, 2 by default
+///
+/// # Examples
+/// ```
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+/// use mycoforge::common::objectives::Direction;
+/// use mycoforge::common::traits::Selector;
+/// use mycoforge::optimizers::nsga2::{rank_population, CrowdedTournament, MultiObjectiveIndividual};
+/// use mycoforge::tree::core::tree::TreeGenotype;
+///
+/// let mut population = vec![
+///     MultiObjectiveIndividual::new(TreeGenotype::default(), vec![1.0, 1.0]),
+///     MultiObjectiveIndividual::new(TreeGenotype::default(), vec![2.0, 2.0]),
+/// ];
+/// rank_population(&mut population, &[Direction::Minimize; 2]);
+/// assert_eq!((population[0].rank(), population[1].rank()), (0, 1));
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// // Rank already accounts for directions of objectives, so the direction argument is ignored
+/// let selected = CrowdedTournament::default().select(&mut rng, &population, Direction::Minimize);
+/// ```
+pub struct CrowdedTournament {
+    tournament_size: usize
+}
+
+impl CrowdedTournament {
+    pub fn new(tournament_size: usize) -> Self { return Self { tournament_size }; }
+
+    pub fn tournament_size(&self) -> usize { return self.tournament_size; }
+}
+
+impl Default for CrowdedTournament {
+    fn default() -> Self { return Self::new(2); }
+}
+
+impl<G: Genotype> Selector<G> for CrowdedTournament {
+    type I = MultiObjectiveIndividual<G>;
+
+    fn select<R: Rng>(&self, rng: &mut R, population: &[Self::I], _direction: Direction) -> G {
+        assert!(self.tournament_size > 0 && self.tournament_size <= population.len(),
+            "Invalid tournament size {} for population of size {}!", self.tournament_size, population.len()
+        );
+        let mut best: Option<&MultiObjectiveIndividual<G>> = None;
+        for candidate in population.choose_multiple(rng, self.tournament_size) {
+            let better = match best {
+                None => true,
+                Some(current) => candidate.rank < current.rank
+                    || (candidate.rank == current.rank && candidate.crowding_distance > current.crowding_distance),
+            };
+            if better { best = Some(candidate); }
+        }
+        return best.expect("Tournament selection failed!").genotype.clone();
+    }
+}
+
+/// Outcome of a complete multi-objective run.
+///
+/// # Fields
+/// * `population: Vec<MultiObjectiveIndividual<G>>` - final ranked population
+/// * `directions: Vec<Direction>` - direction of every objective
+/// * `history: Vec<GenerationStats>` - per-generation statistics of the first objective
+/// * `evaluations: usize` - total number of genotype evaluations performed
+/// * `elapsed: Duration` - wall-clock duration of the run
+pub struct MultiObjectiveResult<G: Genotype> {
+    population: Vec<MultiObjectiveIndividual<G>>,
+    directions: Vec<Direction>,
+    history: Vec<GenerationStats>,
+    evaluations: usize,
+    elapsed: Duration
+}
+
+impl<G: Genotype> MultiObjectiveResult<G> {
+    pub fn new(
+        population: Vec<MultiObjectiveIndividual<G>>, directions: Vec<Direction>,
+        history: Vec<GenerationStats>, evaluations: usize, elapsed: Duration
+    ) -> Self {
+        return Self { population, directions, history, evaluations, elapsed };
+    }
+
+    pub fn population(&self) -> &[MultiObjectiveIndividual<G>] { return &self.population; }
+    pub fn directions(&self) -> &[Direction] { return &self.directions; }
+    pub fn history(&self) -> &[GenerationStats] { return &self.history; }
+    pub fn evaluations(&self) -> usize { return self.evaluations; }
+    pub fn elapsed(&self) -> Duration { return self.elapsed; }
+    /// Returns number of generations performed after initialization.
+    pub fn generations(&self) -> usize { return self.history.len() - 1; }
+
+    /// Returns non-dominated individuals of the final population, see [`pareto_front`].
+    pub fn pareto_front(&self) -> Vec<MultiObjectiveIndividual<G>> { return pareto_front(&self.population, &self.directions); }
+}
+
+/// NSGA-II multi-objective evolutionary algorithm.
+///
+/// Every generation creates as many offspring as there are parents using [`CrowdedTournament`]
+/// selection, crossover and mutation, and keeps the best half of parents and offspring combined
+/// by [`select_survivors`]. History summarizes the first objective in its own direction, which
+/// termination criteria also use to compare fitness.
+///
+/// # Fields
+/// * `initializer`, `mutator`, `crossoverer` - variation components, as in
+///   [`EAComponents`][`crate::optimizers::ga::EAComponents`]
+/// * `objectives: Objectives<G, D>` - [`objectives`][`crate::common::objectives::Objectives`]
+///   and their directions
+/// * `selector: CrowdedTournament` - parent selection
+/// * `sampler: OperatorSampler` - sampler used by initialization and variation operators
+/// * `map: HashMap<String, (usize, VectorFunction)>` - function map used by the objectives
+pub struct NSGA2<G: Genotype, D: Data, Init, Mut, Cross> {
+    initializer: Init,
+    mutator:     Mut,
+    crossoverer: Cross,
+    objectives:  Objectives<G, D>,
+    selector:    CrowdedTournament,
+    sampler:     OperatorSampler,
+    map:         HashMap<String, (usize, VectorFunction)>,
+}
+
+impl<G, D, Init, Mut, Cross> NSGA2<G, D, Init, Mut, Cross>
+where
+    G: Genotype,
+    D: Data,
+    Init: PopulationInitializer<G>,
+    Mut: Mutator<G>,
+    Cross: Crossoverer<G>,
+{
+    /// Creates new NSGA-II optimizer with binary crowded tournament selection.
+    ///
+    /// # Panics
+    /// * If no objectives are provided
+    pub fn new(initializer: Init, mutator: Mut, crossoverer: Cross, objectives: Objectives<G, D>,
+        sampler: OperatorSampler, map: HashMap<String, (usize, VectorFunction)>) -> Self
+    {
+        assert!(!objectives.is_empty(), "At least one objective is required!");
+        return Self { initializer, mutator, crossoverer, objectives, selector: CrowdedTournament::default(), sampler, map };
+    }
+
+    /// Sets parent selection, defaults to binary crowded tournament.
+    pub fn set_selector(mut self, selector: CrowdedTournament) -> Self {
+        self.selector = selector;
+        return self;
+    }
+
+    pub fn objectives(&self) -> &Objectives<G, D> { return &self.objectives; }
+    pub fn selector(&self) -> &CrowdedTournament { return &self.selector; }
+    pub fn map(&self) -> &HashMap<String, (usize, VectorFunction)> { return &self.map; }
+
+    /// Evaluates genotypes into unranked individuals.
+    fn evaluate(&self, genotypes: Vec<G>, data: &D) -> Vec<MultiObjectiveIndividual<G>> {
+        let objectives = self.objectives.evaluate_population(&genotypes, data, &self.map);
+        return genotypes.into_iter().zip(objectives)
+            .map(|(genotype, objectives)| MultiObjectiveIndividual::new(genotype, objectives))
+            .collect();
+    }
+
+    fn stats(&self, generation: usize, evaluations: usize, population: &[MultiObjectiveIndividual<G>], directions: &[Direction]) -> GenerationStats {
+        let values = population.iter().map(|i| i.objectives[0]).collect::<Vec<f64>>();
+        return GenerationStats::from_fitnesses(generation, evaluations, &values, directions[0]);
+    }
+
+    /// Runs NSGA-II loop until any of the termination criteria is met.
+    ///
+    /// # Arguments
+    /// * `rng: &mut R` - random number generator
+    /// * `population_size: usize` - size of the population
+    /// * `data: &D` - dataset used by the objectives
+    /// * `criteria: &[Box<dyn TerminationCriterion>]` - stopping rules, see
+    ///   [`TerminationCriterion`][`crate::optimizers::termination::TerminationCriterion`]
+    ///
+    /// # Returns
+    /// * `MultiObjectiveResult<G>` - final population, its Pareto front and per-generation history
+    ///
+    /// # Panics
+    /// * If no termination criteria are provided
+    pub fn run<R: Rng>(&self,
+        rng: &mut R, population_size: usize, data: &D,
+        criteria: &[Box<dyn TerminationCriterion>]
+    ) -> MultiObjectiveResult<G> {
+        assert!(!criteria.is_empty(), "At least one termination criterion is required!");
+        let start = Instant::now();
+        let directions = self.objectives.directions();
+
+        let mut population = self.evaluate(self.init_population(rng, population_size), data);
+        rank_population(&mut population, &directions);
+        let mut evaluations = population.len();
+        let mut generation = 0;
+        let mut history = vec![self.stats(generation, evaluations, &population, &directions)];
+
+        while !criteria.iter().any(|c| c.should_terminate(&RunState::new(generation, evaluations, start.elapsed(), &history, directions[0]))) {
+            let offspring = self.evaluate(self.optimize(rng, &population), data);
+            evaluations += offspring.len();
+            generation += 1;
+
+            let mut combined = population;
+            combined.extend(offspring);
+            population = select_survivors(combined, population_size, &directions);
+
+            let stats = self.stats(generation, evaluations, &population, &directions);
+            debug!("Generation {}: Pareto front of {} individuals, best first objective {}",
+                generation, population.iter().filter(|i| i.rank == 0).count(), stats.best_fitness()
+            );
+            history.push(stats);
+        }
+        info!("Finished NSGA-II run after {} generations and {} evaluations", generation, evaluations);
+
+        return MultiObjectiveResult::new(population, directions, history, evaluations, start.elapsed());
+    }
+}
+
+impl<G, D, Init, Mut, Cross> Optimizer<G> for NSGA2<G, D, Init, Mut, Cross>
+where
+    G: Genotype,
+    D: Data,
+    Init: PopulationInitializer<G>,
+    Mut: Mutator<G>,
+    Cross: Crossoverer<G>,
+{
+    type I = MultiObjectiveIndividual<G>;
+    fn init_population<R: Rng>(&self, rng: &mut R, population_size: usize) -> Vec<G> {
+        return self.initializer.initialize_population(rng, &self.sampler, population_size);
+    }
+
+    fn optimize<R: Rng>(&self, rng: &mut R, population: &[Self::I]) -> Vec<G> {
+        let mut offspring: Vec<G> = Vec::with_capacity(population.len());
+        while offspring.len() < population.len() {
+            let parents = self.selector.select_many(rng, population, 2, Direction::Minimize);
+
+            for child in self.crossoverer.variate(rng, &parents[0], &parents[1], &self.sampler) {
+                offspring.push(self.mutator.variate(rng, &child, &self.sampler));
+                if offspring.len() >= population.len() { break; }
+            }
+        }
+        return offspring;
+    }
+}
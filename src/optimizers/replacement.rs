@@ -0,0 +1,102 @@
+//! Replacement strategies for evolutionary algorithms.
+//!
+//! This module provides [`Replacement`] which decides how parents and evaluated offspring are
+//! combined into the next population. Strategies rely only on the
+//! [`Individual`][`crate::common::traits::Individual`] trait, so they apply to any
+//! [`Genotype`][`crate::common::traits::Genotype`]. Best and worst individuals are determined by
+//! the optimization [`Direction`].
+
+use crate::common::objectives::Direction;
+use crate::common::traits::{Genotype, Individual};
+
+/// Strategy for constructing next population from parents (mu) and offspring (lambda).
+///
+/// # Variants
+/// * `Generational` - offspring replace the whole population
+/// * `Elitism(usize)` - `k` best parents survive, the rest is filled with offspring
+/// * `MuPlusLambda` - best `mu` individuals out of parents and offspring combined survive
+/// * `MuCommaLambda` - best `mu` offspring survive, requires `lambda >= mu`
+/// * `ReplaceWorst` - offspring replace worst parents, useful with `lambda < mu`
+///
+/// # Examples
+/// ```
+/// use mycoforge::common::objectives::Direction;
+/// use mycoforge::common::traits::Individual;
+/// use mycoforge::optimizers::replacement::Replacement;
+/// use mycoforge::tree::core::individual::TreeIndividual;
+/// use mycoforge::tree::core::tree::TreeGenotype;
+///
+/// let parents = TreeIndividual::from_vecs(&vec![TreeGenotype::default(); 3], &[0.5, 2.0, 3.0]);
+/// let offspring = TreeIndividual::from_vecs(&vec![TreeGenotype::default(); 3], &[1.0, 4.0, 5.0]);
+///
+/// let next = Replacement::Elitism(1).replace(&parents, &offspring, Direction::Minimize);
+/// let fitness = next.iter().map(|i| i.phenotype()).collect::<Vec<f64>>();
+///
+/// assert_eq!(fitness, vec![0.5, 1.0, 4.0]);
+///
+/// let next = Replacement::Elitism(1).replace(&parents, &offspring, Direction::Maximize);
+/// let fitness = next.iter().map(|i| i.phenotype()).collect::<Vec<f64>>();
+///
+/// assert_eq!(fitness, vec![3.0, 1.0, 4.0]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Replacement {
+    #[default]
+    Generational,
+    Elitism(usize),
+    MuPlusLambda,
+    MuCommaLambda,
+    ReplaceWorst,
+}
+
+impl Replacement {
+    /// Creates next population from parents and evaluated offspring.
+    ///
+    /// # Arguments
+    /// * `parents: &[I]` - current population, its size determines size of the next one
+    /// * `offspring: &[I]` - evaluated offspring
+    /// * `direction: Direction` - whether lower or higher fitness is better
+    ///
+    /// # Returns
+    /// * `Vec<I>` - next population of the same size as `parents` (or smaller, if `Generational`
+    ///   replacement receives fewer offspring)
+    ///
+    /// # Panics
+    /// * If `MuCommaLambda` receives fewer offspring than parents
+    pub fn replace<G: Genotype, I: Individual<G> + Clone>(&self, parents: &[I], offspring: &[I], direction: Direction) -> Vec<I> {
+        let mu = parents.len();
+        return match self {
+            Self::Generational => offspring.iter().take(mu).cloned().collect(),
+            Self::Elitism(k) => {
+                let k = (*k).min(mu);
+                let mut next = sorted::<G, I>(parents, direction).into_iter().take(k).collect::<Vec<I>>();
+                next.extend(offspring.iter().take(mu - k).cloned());
+                next
+            },
+            Self::MuPlusLambda => {
+                let combined = parents.iter().chain(offspring.iter()).cloned().collect::<Vec<I>>();
+                sorted::<G, I>(&combined, direction).into_iter().take(mu).collect()
+            },
+            Self::MuCommaLambda => {
+                assert!(offspring.len() >= mu,
+                    "(mu, lambda) replacement requires lambda >= mu! Found mu {}, lambda {}", mu, offspring.len()
+                );
+                sorted::<G, I>(offspring, direction).into_iter().take(mu).collect()
+            },
+            Self::ReplaceWorst => {
+                let mut next = sorted::<G, I>(parents, direction);
+                let n = offspring.len().min(mu);
+                next.truncate(mu - n);
+                next.extend(offspring.iter().take(n).cloned());
+                next
+            },
+        };
+    }
+}
+
+/// Returns copy of individuals sorted from best to worst, NaN fitness last.
+fn sorted<G: Genotype, I: Individual<G> + Clone>(individuals: &[I], direction: Direction) -> Vec<I> {
+    let mut sorted = individuals.to_vec();
+    sorted.sort_by(|a, b| direction.compare(a.phenotype(), b.phenotype()));
+    return sorted;
+}
@@ -0,0 +1,85 @@
+//! Statistics collected during evolutionary runs.
+//!
+//! This module provides [`GenerationStats`] which summarizes fitness of a single generation.
+//! Best and worst values follow the optimization [`Direction`], e.g. the best value of an error
+//! to minimize is the lowest one.
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::common::objectives::Direction;
+
+/// Summary of population fitness for a single generation.
+///
+/// # Fields
+/// * `generation: usize` - generation index (0 for the initial population)
+/// * `evaluations: usize` - total number of fitness evaluations performed so far
+/// * `best_fitness: f64` - best fitness found in the generation
+/// * `mean_fitness: f64` - average fitness over finite values
+/// * `worst_fitness: f64` - worst fitness found in the generation
+///
+/// # Examples
+/// ```
+/// use mycoforge::common::objectives::Direction;
+/// use mycoforge::optimizers::stats::GenerationStats;
+///
+/// let stats = GenerationStats::from_fitnesses(0, 3, &[1.0, 2.0, f64::INFINITY], Direction::Minimize);
+///
+/// assert_eq!(stats.best_fitness(), 1.0);
+/// assert_eq!(stats.mean_fitness(), 1.5);
+/// assert_eq!(stats.worst_fitness(), f64::INFINITY);
+///
+/// let stats = GenerationStats::from_fitnesses(0, 3, &[1.0, 2.0, f64::NAN], Direction::Maximize);
+///
+/// assert_eq!(stats.best_fitness(), 2.0);
+/// assert_eq!(stats.worst_fitness(), 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GenerationStats {
+    generation: usize,
+    evaluations: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::float_serde"))]
+    best_fitness: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::float_serde"))]
+    mean_fitness: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::float_serde"))]
+    worst_fitness: f64
+}
+
+impl GenerationStats {
+    pub fn new(generation: usize, evaluations: usize, best_fitness: f64, mean_fitness: f64, worst_fitness: f64) -> Self {
+        return Self { generation, evaluations, best_fitness, mean_fitness, worst_fitness };
+    }
+
+    /// Computes statistics from fitness values of a single generation.
+    ///
+    /// # Arguments
+    /// * `generation: usize` - generation index
+    /// * `evaluations: usize` - total number of evaluations performed so far
+    /// * `fitnesses: &[f64]` - fitness values of the population
+    /// * `direction: Direction` - whether lower or higher fitness is better
+    ///
+    /// # Returns
+    /// * `GenerationStats` - summary of given fitness values; `NaN` values are ignored
+    pub fn from_fitnesses(generation: usize, evaluations: usize, fitnesses: &[f64], direction: Direction) -> Self {
+        let best_fitness = direction.best(fitnesses.iter().copied());
+        let worst_fitness = fitnesses.iter().copied()
+            .fold(-direction.worst(), |worst, f| if direction.is_better(worst, f) && !f.is_nan() { f } else { worst });
+
+        let finite = fitnesses.iter().filter(|f| f.is_finite()).collect::<Vec<&f64>>();
+        let mean_fitness = if finite.is_empty() { 
+            f64::NAN 
+        } else { 
+            finite.iter().copied().sum::<f64>() / finite.len() as f64 
+        };
+
+        return Self::new(generation, evaluations, best_fitness, mean_fitness, worst_fitness);
+    }
+
+    pub fn generation(&self) -> usize { return self.generation; }
+    pub fn evaluations(&self) -> usize { return self.evaluations; }
+    pub fn best_fitness(&self) -> f64 { return self.best_fitness; }
+    pub fn mean_fitness(&self) -> f64 { return self.mean_fitness; }
+    pub fn worst_fitness(&self) -> f64 { return self.worst_fitness; }
+}
@@ -0,0 +1,228 @@
+//! Steady-state evolutionary algorithm.
+//!
+//! This module provides [`SteadyStateEA`] which, instead of replacing the whole population at
+//! once, selects parents by the configured selector, creates one or two children and immediately
+//! replaces worst (or inverse tournament loser) individuals with them. It reuses
+//! [`EAComponents`][`crate::optimizers::ga::EAComponents`], so the same components as in
+//! generational [`EA`][`crate::optimizers::ga::EA`] plug in unchanged, except evaluators whose
+//! fitness depends on the evaluated population (see
+//! [`Evaluator::population_dependent`][`crate::common::traits::Evaluator::population_dependent`]).
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use log::{debug, info};
+use rand::Rng;
+use rand::seq::index::sample;
+
+use crate::common::objectives::Direction;
+use crate::common::traits::{Crossoverer, Evaluator, Genotype, Individual, Mutator, Optimizer, PopulationInitializer, Selector};
+use crate::common::types::VectorFunction;
+use crate::operators::sampler::OperatorSampler;
+use crate::optimizers::ga::{best_individual, EAComponents, RunResult};
+use crate::optimizers::stats::GenerationStats;
+use crate::optimizers::termination::{RunState, TerminationCriterion};
+
+/// Strategy for choosing individual replaced by a newly created child.
+///
+/// # Variants
+/// * `Worst` - individual with the worst fitness is replaced
+/// * `InverseTournament(usize)` - worst individual out of randomly sampled subset is replaced
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SteadyStateReplacement {
+    #[default]
+    Worst,
+    InverseTournament(usize),
+}
+
+impl SteadyStateReplacement {
+    /// Returns index of individual to be replaced, worst according to `direction`.
+    ///
+    /// # Panics
+    /// * If population is empty or inverse tournament size is 0 or exceeds population size
+    pub fn loser<R: Rng, G: Genotype, I: Individual<G>>(&self, rng: &mut R, population: &[I], direction: Direction) -> usize {
+        let worst = |indices: &mut dyn Iterator<Item = usize>| -> usize {
+            return indices
+                .max_by(|&a, &b| direction.compare(population[a].phenotype(), population[b].phenotype()))
+                .expect("Population should not be empty!");
+        };
+        return match self {
+            Self::Worst => worst(&mut (0..population.len())),
+            Self::InverseTournament(size) => {
+                assert!(*size > 0 && *size <= population.len(),
+                    "Invalid inverse tournament size {} for population of size {}!", size, population.len()
+                );
+                worst(&mut sample(rng, population.len(), *size).into_iter())
+            }
+        };
+    }
+}
+
+/// Steady-state evolutionary algorithm.
+///
+/// # Fields
+/// * `initializer`, `mutator`, `crossoverer`, `evaluator`, `selector` - components defined by
+///   [`EAComponents`][`crate::optimizers::ga::EAComponents`]
+/// * `sampler: OperatorSampler` - sampler used by initialization and variation operators
+/// * `map: HashMap<String, (usize, VectorFunction)>` - function map used by the evaluator
+/// * `replacement: SteadyStateReplacement` - strategy for choosing replaced individuals
+/// * `offspring_per_step: usize` - number of children (1 or 2) inserted after each crossover
+pub struct SteadyStateEA<C: EAComponents<G>, G: Genotype> {
+    initializer:        C::Init,
+    mutator:            C::Mut,
+    crossoverer:        C::Cross,
+    evaluator:          C::Eval,
+    selector:           C::Sel,
+    sampler:            OperatorSampler,
+    map:                HashMap<String, (usize, VectorFunction)>,
+    replacement:        SteadyStateReplacement,
+    offspring_per_step: usize,
+}
+
+impl<C, G> SteadyStateEA<C, G>
+where
+    G: Genotype,
+    C: EAComponents<G>,
+{
+    /// Creates new steady-state EA.
+    ///
+    /// # Panics
+    /// * If `offspring_per_step` is not 1 or 2
+    /// * If fitness computed by `evaluator` depends on the evaluated population (see
+    ///   [`Evaluator::population_dependent`]), children are evaluated one at a time
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(initializer: C::Init,
+        mutator: C::Mut, crossoverer: C::Cross, evaluator: C::Eval, selector: C::Sel,
+        sampler: OperatorSampler, map: HashMap<String, (usize, VectorFunction)>,
+        replacement: SteadyStateReplacement, offspring_per_step: usize) -> Self
+    {
+        assert!((1..=2).contains(&offspring_per_step),
+            "Steady-state EA creates 1 or 2 children per step, found {}", offspring_per_step
+        );
+        assert!(!evaluator.population_dependent(),
+            "Steady-state EA evaluates children one at a time, population-dependent evaluators are not supported!"
+        );
+        return Self {
+            initializer, mutator, crossoverer, evaluator, selector, sampler, map,
+            replacement, offspring_per_step
+        };
+    }
+
+    pub fn evaluator(&self) -> &C::Eval { return &self.evaluator; }
+    pub fn map(&self) -> &HashMap<String, (usize, VectorFunction)> { return &self.map; }
+    pub fn replacement(&self) -> SteadyStateReplacement { return self.replacement; }
+    pub fn offspring_per_step(&self) -> usize { return self.offspring_per_step; }
+
+    /// Evaluates genotypes into individuals, with per-case errors if the selector uses them.
+    fn evaluate_individuals(&self, genotypes: &[G], data: &<C::Eval as Evaluator<G>>::D) -> Vec<C::I> {
+        if self.selector.uses_cases() {
+            let evaluations = self.evaluator.evaluate_population_cases(genotypes, data, &self.map);
+            return C::I::from_evaluations(genotypes, evaluations);
+        }
+        let fitnesses = self.evaluator.evaluate_population(genotypes, data, &self.map);
+        return C::I::from_vecs(genotypes, &fitnesses);
+    }
+
+    /// Performs single steady-state step: creates children, evaluates them and inserts them into
+    /// the population in place of individuals chosen by the replacement strategy.
+    ///
+    /// # Arguments
+    /// * `rng: &mut R` - random number generator
+    /// * `population: &mut [C::I]` - current population, modified in place
+    /// * `data: &D` - dataset used by the evaluator
+    ///
+    /// # Returns
+    /// * `usize` - number of fitness evaluations performed
+    pub fn step<R: Rng>(&self, rng: &mut R, population: &mut [C::I], data: &<C::Eval as Evaluator<G>>::D) -> usize {
+        let children = self.optimize(rng, population);
+        for child in &children {
+            let individual = self.evaluate_individuals(std::slice::from_ref(child), data)
+                .pop().expect("Failed to create individual!");
+            let loser = self.replacement.loser(rng, population, self.evaluator.direction());
+            population[loser] = individual;
+        }
+        return children.len();
+    }
+
+    /// Runs steady-state loop until any of the termination criteria is met.
+    ///
+    /// A generation is counted every `population_size` evaluations, so history and termination
+    /// criteria are comparable with generational [`EA::run`][`crate::optimizers::ga::EA::run`].
+    ///
+    /// # Arguments
+    /// * `rng: &mut R` - random number generator
+    /// * `population_size: usize` - size of the population
+    /// * `data: &D` - dataset used by the evaluator
+    /// * `criteria: &[Box<dyn TerminationCriterion>]` - stopping rules
+    ///
+    /// # Returns
+    /// * `RunResult<C::I>` - best individual found, final population and per-generation history
+    ///
+    /// # Panics
+    /// * If no termination criteria are provided
+    pub fn run<R: Rng>(&self,
+        rng: &mut R, population_size: usize, data: &<C::Eval as Evaluator<G>>::D,
+        criteria: &[Box<dyn TerminationCriterion>]
+    ) -> RunResult<C::I>
+    where
+        C::I: Clone,
+    {
+        assert!(!criteria.is_empty(), "At least one termination criterion is required!");
+        let start = Instant::now();
+        let direction = self.evaluator.direction();
+
+        let genotypes = self.init_population(rng, population_size);
+        let mut population = self.evaluate_individuals(&genotypes, data);
+        let fitnesses = population.iter().map(|i| i.phenotype()).collect::<Vec<f64>>();
+        let mut evaluations = fitnesses.len();
+        let mut generation = 0;
+
+        let mut history = vec![GenerationStats::from_fitnesses(generation, evaluations, &fitnesses, direction)];
+        let mut best = best_individual(&population, direction).clone();
+
+        while !criteria.iter().any(|c| c.should_terminate(&RunState::new(generation, evaluations, start.elapsed(), &history, direction))) {
+            let mut produced = 0;
+            while produced < population_size {
+                produced += self.step(rng, &mut population, data);
+            }
+            evaluations += produced;
+            generation += 1;
+
+            let candidate = best_individual(&population, direction);
+            if direction.is_better(candidate.phenotype(), best.phenotype()) { best = candidate.clone(); }
+
+            let fitnesses = population.iter().map(|i| i.phenotype()).collect::<Vec<f64>>();
+            let stats = GenerationStats::from_fitnesses(generation, evaluations, &fitnesses, direction);
+            debug!("Generation {}: best {}, mean {}, worst {}",
+                generation, stats.best_fitness(), stats.mean_fitness(), stats.worst_fitness()
+            );
+            history.push(stats);
+        }
+        info!("Finished steady-state run after {} generations and {} evaluations with best fitness {}",
+            generation, evaluations, best.phenotype()
+        );
+
+        return RunResult::new(best, population, history, evaluations, start.elapsed());
+    }
+}
+
+impl<C, G> Optimizer<G> for SteadyStateEA<C, G>
+where
+    G: Genotype,
+    C: EAComponents<G>,
+{
+    type I = C::I;
+    fn init_population<R: Rng>(&self, rng: &mut R, population_size: usize) -> Vec<G> {
+        return self.initializer.initialize_population(rng, &self.sampler, population_size);
+    }
+
+    /// Creates children of a single steady-state step (not evaluated).
+    fn optimize<R: Rng>(&self, rng: &mut R, population: &[Self::I]) -> Vec<G> {
+        let parents = self.selector.select_many(rng, population, 2, self.evaluator.direction());
+
+        return self.crossoverer.variate(rng, &parents[0], &parents[1], &self.sampler).into_iter()
+            .take(self.offspring_per_step)
+            .map(|child| self.mutator.variate(rng, &child, &self.sampler))
+            .collect();
+    }
+}
@@ -0,0 +1,177 @@
+//! Termination criteria for evolutionary runs.
+//!
+//! This module provides [`TerminationCriterion`] trait and standard stopping rules:
+//! - [`MaxGenerations`] - stops after given number of generations
+//! - [`FitnessThreshold`] - stops once best fitness reaches given threshold
+//! - [`TimeLimit`] - stops once wall-clock budget is exhausted
+//! - [`MaxEvaluations`] - stops once given number of fitness evaluations was performed
+//! - [`Stagnation`] - stops when best fitness did not improve for given number of generations
+//!
+//! Criteria are checked between generations, so a run may slightly exceed time and evaluation
+//! budgets. Fitness-based criteria follow the optimization direction of the run. Also serves as a
+//! template for custom stopping rules.
+
+use std::time::Duration;
+
+use crate::common::objectives::Direction;
+use crate::optimizers::stats::GenerationStats;
+
+/// Snapshot of an ongoing run passed to termination criteria.
+///
+/// # Fields
+/// * `generation: usize` - number of completed generations (0 after initialization)
+/// * `evaluations: usize` - total number of fitness evaluations performed
+/// * `elapsed: Duration` - wall-clock time since the start of the run
+/// * `history: &[GenerationStats]` - per-generation statistics, including initial population
+/// * `direction: Direction` - whether lower or higher fitness is better
+pub struct RunState<'a> {
+    generation: usize,
+    evaluations: usize,
+    elapsed: Duration,
+    history: &'a [GenerationStats],
+    direction: Direction
+}
+
+impl<'a> RunState<'a> {
+    pub fn new(
+        generation: usize, evaluations: usize, elapsed: Duration,
+        history: &'a [GenerationStats], direction: Direction
+    ) -> Self {
+        return Self { generation, evaluations, elapsed, history, direction };
+    }
+
+    pub fn generation(&self) -> usize { return self.generation; }
+    pub fn evaluations(&self) -> usize { return self.evaluations; }
+    pub fn elapsed(&self) -> Duration { return self.elapsed; }
+    pub fn history(&self) -> &[GenerationStats] { return self.history; }
+    pub fn direction(&self) -> Direction { return self.direction; }
+
+    /// Returns best fitness found so far, or [`Direction::worst`] if history is empty.
+    pub fn best_fitness(&self) -> f64 {
+        return self.direction.best(self.history.iter().map(|s| s.best_fitness()));
+    }
+}
+
+/// Decides whether an evolutionary run should stop.
+///
+/// # Arguments
+/// * `state: &RunState` - current [`state`][`RunState`] of the run
+///
+/// # Returns
+/// * `bool` - `true` if the run should terminate
+pub trait TerminationCriterion {
+    fn should_terminate(&self, state: &RunState) -> bool;
+}
+
+/// Stops the run after given number of generations.
+///
+/// # Examples
+/// ```
+/// use mycoforge::common::objectives::Direction;
+/// use mycoforge::optimizers::termination::{MaxGenerations, RunState, TerminationCriterion};
+/// use std::time::Duration;
+///
+/// let criterion = MaxGenerations::new(10);
+///
+/// assert!(!criterion.should_terminate(&RunState::new(9, 0, Duration::ZERO, &[], Direction::Minimize)));
+/// assert!(criterion.should_terminate(&RunState::new(10, 0, Duration::ZERO, &[], Direction::Minimize)));
+/// ```
+pub struct MaxGenerations {
+    generations: usize
+}
+
+impl MaxGenerations {
+    pub fn new(generations: usize) -> Self { return Self { generations }; }
+
+    pub fn generations(&self) -> usize { return self.generations; }
+}
+
+impl TerminationCriterion for MaxGenerations {
+    fn should_terminate(&self, state: &RunState) -> bool { return state.generation() >= self.generations; }
+}
+
+/// Stops the run once best fitness found so far is as good as given threshold or better (lower or
+/// equal when minimizing, higher or equal when maximizing).
+pub struct FitnessThreshold {
+    threshold: f64
+}
+
+impl FitnessThreshold {
+    pub fn new(threshold: f64) -> Self { return Self { threshold }; }
+
+    pub fn threshold(&self) -> f64 { return self.threshold; }
+}
+
+impl TerminationCriterion for FitnessThreshold {
+    fn should_terminate(&self, state: &RunState) -> bool { return !state.direction().is_better(self.threshold, state.best_fitness()); }
+}
+
+/// Stops the run once given wall-clock budget is exhausted.
+pub struct TimeLimit {
+    limit: Duration
+}
+
+impl TimeLimit {
+    pub fn new(limit: Duration) -> Self { return Self { limit }; }
+
+    pub fn limit(&self) -> Duration { return self.limit; }
+}
+
+impl TerminationCriterion for TimeLimit {
+    fn should_terminate(&self, state: &RunState) -> bool { return state.elapsed() >= self.limit; }
+}
+
+/// Stops the run once given number of fitness evaluations was performed.
+pub struct MaxEvaluations {
+    evaluations: usize
+}
+
+impl MaxEvaluations {
+    pub fn new(evaluations: usize) -> Self { return Self { evaluations }; }
+
+    pub fn evaluations(&self) -> usize { return self.evaluations; }
+}
+
+impl TerminationCriterion for MaxEvaluations {
+    fn should_terminate(&self, state: &RunState) -> bool { return state.evaluations() >= self.evaluations; }
+}
+
+/// Stops the run when best fitness did not improve over the last `generations` generations.
+///
+/// # Examples
+/// ```
+/// use mycoforge::common::objectives::Direction;
+/// use mycoforge::optimizers::stats::GenerationStats;
+/// use mycoforge::optimizers::termination::{Stagnation, RunState, TerminationCriterion};
+/// use std::time::Duration;
+///
+/// let history = [1.0, 0.5, 0.5, 0.5].iter().enumerate()
+///     .map(|(i, &f)| GenerationStats::new(i, 0, f, f, f))
+///     .collect::<Vec<GenerationStats>>();
+///
+/// assert!(!Stagnation::new(3).should_terminate(&RunState::new(3, 0, Duration::ZERO, &history, Direction::Minimize)));
+/// assert!(Stagnation::new(2).should_terminate(&RunState::new(3, 0, Duration::ZERO, &history, Direction::Minimize)));
+/// ```
+pub struct Stagnation {
+    generations: usize
+}
+
+impl Stagnation {
+    pub fn new(generations: usize) -> Self { return Self { generations }; }
+
+    pub fn generations(&self) -> usize { return self.generations; }
+}
+
+impl TerminationCriterion for Stagnation {
+    fn should_terminate(&self, state: &RunState) -> bool {
+        let history = state.history();
+        if history.len() <= self.generations { return false; }
+
+        let split = history.len() - self.generations;
+        let direction = state.direction();
+        let best_before = direction.best(history[..split].iter().map(|s| s.best_fitness()));
+        let best_recent = direction.best(history[split..].iter().map(|s| s.best_fitness()));
+
+        return !direction.is_better(best_recent, best_before);
+    }
+}
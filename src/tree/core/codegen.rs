@@ -0,0 +1,222 @@
+//! Source code generation of evolved models.
+//!
+//! This module extends [`TreeGenotype`] with export of a standalone function in Rust, C or Python
+//! (NumPy), so found models can be deployed outside mycoforge. Generated code is straight-line:
+//! every operator stores its result in a temporary, reproducing protected semantics of
+//! [`symbolic`][`crate::operators::functions::symbolic`] operators exactly.
+//!
+//! Only built-in functions added by their [`registry`][`crate::operators::registry`] key (see
+//! [`OperatorsBuilder::add_registered`][`crate::operators::builder::OperatorsBuilder::add_registered`])
+//! can be exported, since arbitrary
+//! [`VectorFunction`][`crate::common::types::VectorFunction`]s have no source form. Functions
+//! added as plain function pointers are never written as built-in operators, even if they behave
+//! like one.
+use std::fmt::{Display, Formatter, Result};
+
+use crate::operators::functions::symbolic::Float;
+use crate::operators::set::Operators;
+use crate::tree::core::node::Node;
+use crate::tree::core::tree::TreeGenotype;
+use crate::tree::fitness::compile::{Compiler, Instruction};
+
+/// Errors that can occur during code generation.
+///
+/// # Variants
+/// * `EmptyTree` - tree contains no nodes
+/// * `UnknownFunction(String)` - function is missing from the function map
+/// * `UnsupportedFunction(String)` - function is not a registered built-in and cannot be written
+///   as source
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodegenError {
+    EmptyTree,
+    UnknownFunction(String),
+    UnsupportedFunction(String)
+}
+
+impl std::error::Error for CodegenError {}
+
+impl Display for CodegenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            CodegenError::EmptyTree => write!(f, "Cannot generate code for empty tree!"),
+            CodegenError::UnknownFunction(name) => write!(f, "Unknown function: {}", name),
+            CodegenError::UnsupportedFunction(name) => write!(f, "Function {} cannot be exported to source code!", name),
+        }
+    }
+}
+
+/// Target language of generated code.
+///
+/// # Variants
+/// * `Rust` - `pub fn name(x: &[f64]) -> f64` evaluating a single row
+/// * `C` - `double name(const double *x)` evaluating a single row, requires `math.h`
+/// * `Python` - `def name(X)` evaluating all rows of a 2D NumPy array at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    C,
+    Python
+}
+
+impl Language {
+    fn variable(&self, column: usize) -> String {
+        return match self {
+            Language::Rust | Language::C => format!("x[{}]", column),
+            Language::Python => format!("X[:, {}]", column),
+        };
+    }
+
+    /// Returns literal reproducing the value exactly.
+    fn literal(&self, value: f64) -> String {
+        if value.is_nan() {
+            return match self { Language::Rust => "f64::NAN", Language::C => "NAN", Language::Python => "np.nan" }.to_string();
+        }
+        if value.is_infinite() {
+            let infinity = match self { Language::Rust => "f64::INFINITY", Language::C => "INFINITY", Language::Python => "np.inf" };
+            return if value > 0.0 { infinity.to_string() } else { format!("-{}", infinity) };
+        }
+        // Debug formatting of f64 is the shortest representation that round-trips
+        return match self {
+            Language::Rust => format!("{:?}_f64", value),
+            Language::C => format!("{:?}", value),
+            Language::Python => format!("np.float64({:?})", value),
+        };
+    }
+
+    /// Returns statements storing result of native instruction applied to operands in `result`.
+    fn statements(&self, instruction: Instruction, result: &str, operands: &[String]) -> Vec<String> {
+        let (epsilon, one, zero) = (self.literal(<f64 as Float>::epsilon()), self.literal(1.0), self.literal(0.0));
+        let (max, min) = (self.literal(<f64 as Float>::max_value()), self.literal(<f64 as Float>::min_value()));
+        // Unary minus binds weaker than method calls in Rust
+        let operands = operands.iter()
+            .map(|operand| if *self == Language::Rust && operand.starts_with('-') { format!("({})", operand) } else { operand.clone() })
+            .collect::<Vec<String>>();
+        let a = &operands[0];
+        let b = operands.get(1).map_or("", String::as_str);
+        return match self {
+            Language::Rust => match instruction {
+                Instruction::Add => vec![format!("let {} = {} + {};", result, a, b)],
+                Instruction::Sub => vec![format!("let {} = {} - {};", result, a, b)],
+                Instruction::Mul => vec![
+                    format!("let {} = {} * {};", result, a, b),
+                    format!("let {0} = if {0}.is_finite() {{ {0} }} else {{ {0}.signum() * {1} }};", result, max),
+                ],
+                Instruction::Div => vec![format!("let {} = if {}.abs() < {} {{ {} }} else {{ {} / {} }};", result, b, epsilon, one, a, b)],
+                Instruction::Sin => vec![format!("let {} = if {1}.is_finite() {{ {1}.sin() }} else {{ {2} }};", result, a, zero)],
+                Instruction::Cos => vec![format!("let {} = if {1}.is_finite() {{ {1}.cos() }} else {{ {2} }};", result, a, zero)],
+                Instruction::Ln => vec![format!("let {} = if {1} > {2} {{ {1}.ln() }} else {{ {3} }};", result, a, epsilon, min)],
+                _ => unreachable!("Only native instructions are exported!"),
+            },
+            Language::C => match instruction {
+                Instruction::Add => vec![format!("double {} = {} + {};", result, a, b)],
+                Instruction::Sub => vec![format!("double {} = {} - {};", result, a, b)],
+                Instruction::Mul => vec![
+                    format!("double {} = {} * {};", result, a, b),
+                    format!("if (!isfinite({0}) && !isnan({0})) {0} = copysign({1}, {0});", result, max),
+                ],
+                Instruction::Div => vec![format!("double {} = fabs({}) < {} ? {} : {} / {};", result, b, epsilon, one, a, b)],
+                Instruction::Sin => vec![format!("double {} = isfinite({1}) ? sin({1}) : {2};", result, a, zero)],
+                Instruction::Cos => vec![format!("double {} = isfinite({1}) ? cos({1}) : {2};", result, a, zero)],
+                Instruction::Ln => vec![format!("double {} = {1} > {2} ? log({1}) : {3};", result, a, epsilon, min)],
+                _ => unreachable!("Only native instructions are exported!"),
+            },
+            Language::Python => match instruction {
+                Instruction::Add => vec![format!("{} = {} + {}", result, a, b)],
+                Instruction::Sub => vec![format!("{} = {} - {}", result, a, b)],
+                Instruction::Mul => vec![
+                    format!("{} = {} * {}", result, a, b),
+                    format!("{0} = np.where(np.isfinite({0}), {0}, np.sign({0}) * {1})", result, max),
+                ],
+                Instruction::Div => vec![format!("{} = np.where(np.abs({}) < {}, {}, {} / {})", result, b, epsilon, one, a, b)],
+                Instruction::Sin => vec![format!("{} = np.where(np.isfinite({1}), np.sin({1}), {2})", result, a, zero)],
+                Instruction::Cos => vec![format!("{} = np.where(np.isfinite({1}), np.cos({1}), {2})", result, a, zero)],
+                Instruction::Ln => vec![format!("{} = np.where({1} > {2}, np.log({1}), {3})", result, a, epsilon, min)],
+                _ => unreachable!("Only native instructions are exported!"),
+            },
+        };
+    }
+
+    /// Wraps statements into function definition.
+    fn function(&self, name: &str, description: &str, statements: &[String], result: &str) -> String {
+        return match self {
+            Language::Rust => {
+                let body = statements.iter().map(|line| format!("    {}\n", line)).collect::<String>();
+                format!("/// Model generated by mycoforge: `{}`\npub fn {}(x: &[f64]) -> f64 {{\n{}    return {};\n}}\n",
+                    description, name, body, result)
+            },
+            Language::C => {
+                let body = statements.iter().map(|line| format!("    {}\n", line)).collect::<String>();
+                format!("#include <math.h>\n\n/* Model generated by mycoforge: {} */\ndouble {}(const double *x) {{\n{}    return {};\n}}\n",
+                    description, name, body, result)
+            },
+            Language::Python => {
+                let body = statements.iter().map(|line| format!("        {}\n", line)).collect::<String>();
+                format!("import numpy as np\n\n\ndef {}(X):\n    \"\"\"Model generated by mycoforge: {}\"\"\"\n    X = np.asarray(X, dtype=np.float64)\n    \
+                    with np.errstate(all=\"ignore\"):\n{}        return np.broadcast_to({}, X.shape[:1]).astype(np.float64)\n",
+                    name, description, body, result)
+            },
+        };
+    }
+}
+
+impl TreeGenotype {
+    /// Returns source code of a standalone function computing the tree.
+    ///
+    /// # Arguments
+    /// * `language: Language` - target [`language`][`Language`]
+    /// * `name: &str` - name of generated function
+    /// * `operators: &Operators` - [`operator set`][`crate::operators::set::Operators`] the tree
+    ///   was built with, its registry keys identify protected operators
+    ///
+    /// # Returns
+    /// * `Result<String, CodegenError>` - source code or [`error`][`CodegenError`] if tree uses
+    ///   function without source form
+    ///
+    /// # Examples
+    /// ```
+    /// use mycoforge::operators::builder::OperatorsBuilder;
+    /// use mycoforge::tree::core::codegen::Language;
+    /// use mycoforge::tree::core::tree::TreeGenotype;
+    ///
+    /// let operators = OperatorsBuilder::default()
+    ///     .add_registered("+", "add", 0.5).expect("Failed to add function!")
+    ///     .add_registered("/", "protected_div", 0.5).expect("Failed to add function!")
+    ///     .add_variable("x", 1.0).expect("Failed to add variable!")
+    ///     .build().expect("Failed to build operators!");
+    /// let tree = TreeGenotype::from_infix("x / (x + 1)", &operators).expect("Failed to parse tree!");
+    ///
+    /// let source = tree.to_source(Language::Rust, "model", &operators).expect("Failed to generate code!");
+    ///
+    /// assert!(source.contains("pub fn model(x: &[f64]) -> f64 {"));
+    /// assert!(source.contains("let t0 = x[0] + 1.0_f64;"));
+    /// assert!(source.contains("let t1 = if t0.abs() < 1e-6_f64 { 1.0_f64 } else { x[0] / t0 };"));
+    /// ```
+    pub fn to_source(&self, language: Language, name: &str, operators: &Operators) -> std::result::Result<String, CodegenError> {
+        if self.arena().is_empty() { return Err(CodegenError::EmptyTree); }
+
+        let compiler = Compiler::new(operators);
+        let mut operands: Vec<String> = Vec::new();
+        let mut statements = Vec::new();
+        for index in self.postorder() {
+            let node = self.arena()[index];
+            let operand = match node {
+                Node::Variable(column) => language.variable(column),
+                Node::Constant(value) => language.literal(value),
+                Node::Function(_, arity) => {
+                    let instruction = compiler.instruction(&node).ok_or_else(|| CodegenError::UnknownFunction(node.name()))?;
+                    if matches!(instruction, Instruction::Features(_) | Instruction::Call(_, _)) {
+                        return Err(CodegenError::UnsupportedFunction(node.name()));
+                    }
+                    let arguments = operands.split_off(operands.len() - arity);
+                    let result = format!("t{}", statements.len());
+                    statements.push(language.statements(instruction, &result, &arguments));
+                    result
+                }
+            };
+            operands.push(operand);
+        }
+
+        let result = operands.pop().expect("Complete tree should leave exactly one operand!");
+        return Ok(language.function(name, &self.to_infix(), &statements.concat(), &result));
+    }
+}
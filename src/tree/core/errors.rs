@@ -0,0 +1,80 @@
+//! Error types for tree validation and parsing.
+
+use std::fmt;
+use std::error::Error;
+
+/// Errors that can be found by validating a [`TreeGenotype`][`crate::tree::core::tree::TreeGenotype`].
+///
+/// # Variants
+/// * `EmptyTree` - arena contains no nodes
+/// * `UnknownSymbol { index, symbol }` - node at `index` is not part of the operator set
+/// * `ArityMismatch { index, symbol, expected, found }` - node at `index` has different arity
+///   than the operator with the same name
+/// * `MissingOperands(usize)` - arena ends while given number of operands is still missing
+/// * `ExtraNodes(usize)` - given number of nodes follows an already complete tree
+/// * `InconsistentChildren { index, expected, found }` - children map entry of node at `index`
+///   does not match the arena
+#[derive(Debug, Clone, PartialEq)]
+pub enum TreeError {
+    EmptyTree,
+    UnknownSymbol { index: usize, symbol: String },
+    ArityMismatch { index: usize, symbol: String, expected: usize, found: usize },
+    MissingOperands(usize),
+    ExtraNodes(usize),
+    InconsistentChildren { index: usize, expected: Vec<usize>, found: Vec<usize> }
+}
+
+impl Error for TreeError {}
+
+impl fmt::Display for TreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TreeError::EmptyTree => write!(f, "Tree is empty!"),
+            TreeError::UnknownSymbol { index, symbol }
+                => write!(f, "Unknown symbol {} at node {}", symbol, index),
+            TreeError::ArityMismatch { index, symbol, expected, found }
+                => write!(f, "Arity mismatch for {} at node {}: expected {}, found {}", symbol, index, expected, found),
+            TreeError::MissingOperands(missing)
+                => write!(f, "Tree is missing {} operand(s)!", missing),
+            TreeError::ExtraNodes(count)
+                => write!(f, "Found {} node(s) after complete tree!", count),
+            TreeError::InconsistentChildren { index, expected, found }
+                => write!(f, "Inconsistent children of node {}: expected {:?}, found {:?}", index, expected, found),
+        }
+    }
+}
+
+/// Errors that can occur while parsing textual form of a
+/// [`TreeGenotype`][`crate::tree::core::tree::TreeGenotype`].
+///
+/// # Variants
+/// * `UnexpectedEnd` - input ended while expression was still incomplete
+/// * `UnexpectedToken(String)` - token is not allowed at its position
+/// * `UnknownSymbol(String)` - token is neither part of the operator set nor a number
+/// * `ArityMismatch { symbol, expected, found }` - function is applied to different number of
+///   arguments than its arity
+/// * `TrailingTokens(usize)` - given number of tokens left after the expression was complete
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    UnexpectedEnd,
+    UnexpectedToken(String),
+    UnknownSymbol(String),
+    ArityMismatch { symbol: String, expected: usize, found: usize },
+    TrailingTokens(usize)
+}
+
+impl Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedEnd => write!(f, "Unexpected end of expression!"),
+            ParseError::UnexpectedToken(token) => write!(f, "Unexpected token: {}", token),
+            ParseError::UnknownSymbol(symbol) => write!(f, "Unknown symbol: {}", symbol),
+            ParseError::ArityMismatch { symbol, expected, found }
+                => write!(f, "Arity mismatch for {}: expected {}, found {}", symbol, expected, found),
+            ParseError::TrailingTokens(count)
+                => write!(f, "Found {} token(s) after complete expression!", count),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Exporters rendering trees for documents.
+//!
+//! This module extends [`TreeGenotype`] with:
+//! - Graphviz DOT export, drawing the tree with node labels taken from the arena
+//! - LaTeX math export with fractions, named functions and only necessary parentheses
+use crate::tree::core::expression::{operator_precedence, ATOM_PRECEDENCE};
+use crate::tree::core::node::Node;
+use crate::tree::core::tree::TreeGenotype;
+
+/// Functions with dedicated LaTeX commands.
+const LATEX_FUNCTIONS: [&str; 10] = ["sin", "cos", "tan", "ln", "log", "exp", "sinh", "cosh", "tanh", "arctan"];
+
+/// Fill color of highlighted nodes in DOT export.
+const HIGHLIGHT_COLOR: &str = "lightblue";
+
+fn escape_dot(label: &str) -> String { return label.replace('\\', "\\\\").replace('"', "\\\""); }
+
+fn escape_latex(name: &str) -> String { return name.replace('_', "\\_"); }
+
+impl TreeGenotype {
+    /// Returns tree as Graphviz DOT graph.
+    ///
+    /// Nodes are named after their arena index (`n0`, `n1`, ...) and labeled with their string
+    /// form. Functions are drawn as ellipses and terminals as boxes.
+    ///
+    /// # Arguments
+    /// * `highlight: Option<usize>` - index of subtree root whose nodes and edges are highlighted,
+    ///   indices outside the arena highlight nothing
+    ///
+    /// # Returns
+    /// * `String` - DOT source of the tree
+    ///
+    /// # Examples
+    /// ```
+    /// use mycoforge::tree::core::node::Node;
+    /// use mycoforge::tree::core::tree::TreeGenotype;
+    ///
+    /// let tree = TreeGenotype::with_arena(vec![Node::function("sin", 1), Node::Variable(0)]);
+    ///
+    /// assert_eq!(tree.to_dot(None), "digraph tree {\n    n0 [label=\"sin\", shape=ellipse];\n    \
+    ///     n1 [label=\"x0\", shape=box];\n    n0 -> n1;\n}\n");
+    /// ```
+    pub fn to_dot(&self, highlight: Option<usize>) -> String {
+        let highlighted = highlight
+            .filter(|&root| root < self.arena().len())
+            .map(|root| root..=self.subtree(root));
+        let is_highlighted = |index: usize| highlighted.as_ref().is_some_and(|range| range.contains(&index));
+
+        let mut dot = String::from("digraph tree {\n");
+        for (index, node) in self.arena().iter().enumerate() {
+            let shape = if node.is_terminal() { "box" } else { "ellipse" };
+            let style = if is_highlighted(index) {
+                format!(", style=filled, fillcolor={}", HIGHLIGHT_COLOR)
+            } else {
+                String::new()
+            };
+            dot.push_str(&format!("    n{} [label=\"{}\", shape={}{}];\n", index, escape_dot(&node.to_string()), shape, style));
+        }
+        for index in self.preorder() {
+            for &child in self.children().get(&index).into_iter().flatten() {
+                let style = if is_highlighted(child) && is_highlighted(index) {
+                    format!(" [color={}, penwidth=2]", HIGHLIGHT_COLOR)
+                } else {
+                    String::new()
+                };
+                dot.push_str(&format!("    n{} -> n{}{};\n", index, child, style));
+            }
+        }
+        dot.push_str("}\n");
+        return dot;
+    }
+
+    /// Returns tree as LaTeX math, e.g. `\frac{x_{0} + \sin\left(x_{1}\right)}{2.5}`.
+    ///
+    /// Division is written as fraction, multiplication as `\cdot` and parentheses are only added
+    /// where precedence requires them. Common functions use their LaTeX commands, the rest is
+    /// written with `\operatorname`.
+    pub fn to_latex(&self) -> String {
+        if self.arena().is_empty() { return String::new(); }
+        return self.latex_node(0).0;
+    }
+
+    /// Returns LaTeX form of subtree together with its binding strength.
+    fn latex_node(&self, index: usize) -> (String, u8) {
+        let node = &self.arena()[index];
+        let children = self.children().get(&index).map(Vec::as_slice).unwrap_or(&[]);
+        let name = node.name();
+
+        match node {
+            Node::Variable(index) => return (format!("x_{{{}}}", index), ATOM_PRECEDENCE),
+            Node::Constant(value) if value.is_sign_negative() => return (node.to_string(), 0),
+            Node::Constant(_) => return (node.to_string(), ATOM_PRECEDENCE),
+            Node::Function(_, 0) => return (format!("\\operatorname{{{}}}", escape_latex(&name)), ATOM_PRECEDENCE),
+            Node::Function(_, _) => {}
+        }
+
+        if node.arity() == 2 {
+            if name == "/" {
+                let (numerator, denominator) = (self.latex_node(children[0]).0, self.latex_node(children[1]).0);
+                return (format!("\\frac{{{}}}{{{}}}", numerator, denominator), ATOM_PRECEDENCE);
+            }
+            if let Some(precedence) = operator_precedence(&name) {
+                let (left, left_precedence) = self.latex_node(children[0]);
+                let (right, right_precedence) = self.latex_node(children[1]);
+                let left = if left_precedence < precedence { format!("\\left({}\\right)", left) } else { left };
+                let right = if right_precedence <= precedence { format!("\\left({}\\right)", right) } else { right };
+                let symbol = if name == "*" { "\\cdot" } else { name.as_str() };
+                return (format!("{} {} {}", left, symbol, right), precedence);
+            }
+        }
+
+        let arguments = children.iter()
+            .map(|&child| self.latex_node(child).0)
+            .collect::<Vec<String>>()
+            .join(", ");
+        if name == "sqrt" && node.arity() == 1 {
+            return (format!("\\sqrt{{{}}}", arguments), ATOM_PRECEDENCE);
+        }
+        let function = if LATEX_FUNCTIONS.contains(&name.as_str()) {
+            format!("\\{}", name)
+        } else {
+            format!("\\operatorname{{{}}}", escape_latex(&name))
+        };
+        return (format!("{}\\left({}\\right)", function, arguments), ATOM_PRECEDENCE);
+    }
+}
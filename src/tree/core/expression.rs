@@ -0,0 +1,316 @@
+//! Textual forms of trees.
+//!
+//! This module extends [`TreeGenotype`] with printing and parsing of two formats:
+//! - infix math, e.g. `(x0 + sin(x1)) / 2.5`, where binary `+`, `-`, `*` and `/` are written
+//!   between their operands and every other function is written as a call
+//! - S-expressions, e.g. `(/ (+ x0 (sin x1)) 2.5)`
+//!
+//! Variables are printed as `x{index}`. Parsing resolves symbols against an
+//! [`Operators`][`crate::operators::set::Operators`] set, accepting names of its functions and
+//! terminals, `x{index}` for its variables and numeric constants.
+use crate::operators::set::{NodeType, Operators};
+use crate::tree::core::errors::ParseError;
+use crate::tree::core::node::Node;
+use crate::tree::core::tree::TreeGenotype;
+
+/// Functions written between their operands, with binding strength.
+const INFIX_OPERATORS: [(&str, u8); 4] = [("+", 1), ("-", 1), ("*", 2), ("/", 2)];
+/// Binding strength of terminals and function calls.
+pub(crate) const ATOM_PRECEDENCE: u8 = 3;
+/// Characters that always form a token on their own in infix form.
+const INFIX_DELIMITERS: &str = "(),+-*/";
+
+/// Returns binding strength of functions written between their operands.
+pub(crate) fn operator_precedence(symbol: &str) -> Option<u8> {
+    return INFIX_OPERATORS.iter().find(|(name, _)| *name == symbol).map(|(_, precedence)| *precedence);
+}
+
+impl TreeGenotype {
+    /// Returns tree in infix form, e.g. `(x0 + sin(x1)) / 2.5`.
+    ///
+    /// Parentheses are only added where required to preserve tree structure, so parsing the
+    /// result with [`TreeGenotype::from_infix`] yields the same tree.
+    pub fn to_infix(&self) -> String {
+        if self.arena().is_empty() { return String::new(); }
+        return self.infix_node(0).0;
+    }
+
+    /// Returns tree as S-expression, e.g. `(/ (+ x0 (sin x1)) 2.5)`.
+    pub fn to_sexpr(&self) -> String {
+        if self.arena().is_empty() { return String::new(); }
+        return self.sexpr_node(0);
+    }
+
+    /// Creates new tree from infix form.
+    ///
+    /// Binary `+`, `-`, `*` and `/` are left associative, with `*` and `/` binding stronger than
+    /// `+` and `-`. Other functions are written as calls, e.g. `sin(x0)`.
+    ///
+    /// # Arguments
+    /// * `expression: &str` - tree in infix form
+    /// * `operators: &Operators` - [`operator set`][`crate::operators::set::Operators`] used to
+    ///   resolve symbols
+    ///
+    /// # Returns
+    /// * `Result<Self, ParseError>` - new tree or [`error`][`crate::tree::core::errors::ParseError`]
+    ///   if expression is malformed or uses symbols outside of the operator set
+    ///
+    /// # Examples
+    /// ```
+    /// use mycoforge::operators::builder::OperatorsBuilder;
+    /// use mycoforge::operators::functions::symbolic::{add, div, sin};
+    /// use mycoforge::tree::core::tree::TreeGenotype;
+    ///
+    /// let operators = OperatorsBuilder::default()
+    ///     .add_function("+", add, 2, 1.0 / 3.0).expect("Failed to add function!")
+    ///     .add_function("/", div, 2, 1.0 / 3.0).expect("Failed to add function!")
+    ///     .add_function("sin", sin, 1, 1.0 / 3.0).expect("Failed to add function!")
+    ///     .add_variable("x", 0.5).expect("Failed to add variable!")
+    ///     .add_variable("y", 0.5).expect("Failed to add variable!")
+    ///     .build().expect("Failed to build operators!");
+    ///
+    /// let tree = TreeGenotype::from_infix("(x + sin(y)) / 2.5", &operators).expect("Failed to parse tree!");
+    ///
+    /// assert_eq!(tree.to_infix(), "(x0 + sin(x1)) / 2.5");
+    /// assert_eq!(tree.to_sexpr(), "(/ (+ x0 (sin x1)) 2.5)");
+    /// ```
+    pub fn from_infix(expression: &str, operators: &Operators) -> Result<Self, ParseError> {
+        let mut parser = Parser::new(tokenize_infix(expression), operators);
+        let arena = parser.infix(0)?;
+        return parser.finish(arena);
+    }
+
+    /// Creates new tree from S-expression, see [`TreeGenotype::from_infix`] for symbol resolution.
+    ///
+    /// # Arguments
+    /// * `expression: &str` - tree as S-expression
+    /// * `operators: &Operators` - [`operator set`][`crate::operators::set::Operators`] used to
+    ///   resolve symbols
+    ///
+    /// # Returns
+    /// * `Result<Self, ParseError>` - new tree or [`error`][`crate::tree::core::errors::ParseError`]
+    ///   if expression is malformed or uses symbols outside of the operator set
+    pub fn from_sexpr(expression: &str, operators: &Operators) -> Result<Self, ParseError> {
+        let mut parser = Parser::new(tokenize_sexpr(expression), operators);
+        let arena = parser.sexpr()?;
+        return parser.finish(arena);
+    }
+
+    /// Returns infix form of subtree together with its binding strength.
+    fn infix_node(&self, index: usize) -> (String, u8) {
+        let node = &self.arena()[index];
+        let children = self.children().get(&index).map(Vec::as_slice).unwrap_or(&[]);
+
+        if node.arity() == 2 {
+            if let Some(precedence) = operator_precedence(&node.name()) {
+                let (left, left_precedence) = self.infix_node(children[0]);
+                let (right, right_precedence) = self.infix_node(children[1]);
+                let left = if left_precedence < precedence { format!("({})", left) } else { left };
+                let right = if right_precedence <= precedence { format!("({})", right) } else { right };
+                return (format!("{} {} {}", left, node, right), precedence);
+            }
+        }
+        if node.arity() > 0 {
+            let arguments = children.iter()
+                .map(|&child| self.infix_node(child).0)
+                .collect::<Vec<String>>();
+            return (format!("{}({})", node, arguments.join(", ")), ATOM_PRECEDENCE);
+        }
+        return match node {
+            // Negative constants are parenthesized when used as operands, e.g. `x0 - (-1)`
+            Node::Constant(value) if value.is_sign_negative() => (node.to_string(), 0),
+            _ => (node.to_string(), ATOM_PRECEDENCE),
+        };
+    }
+
+    fn sexpr_node(&self, index: usize) -> String {
+        let node = &self.arena()[index];
+        let Some(children) = self.children().get(&index) else { return node.to_string(); };
+
+        let arguments = children.iter()
+            .map(|&child| self.sexpr_node(child))
+            .collect::<Vec<String>>();
+        return format!("({} {})", node, arguments.join(" "));
+    }
+}
+
+/// Converts token into a node of the operator set.
+///
+/// Function and terminal names of the set take precedence over `x{index}` variables and numeric
+/// constants.
+fn resolve(token: &str, operators: &Operators) -> Result<Node, ParseError> {
+    if let Some(functor) = operators.functions().get(token) {
+        return Ok(Node::function(token, functor.arity()));
+    }
+
+    let mut variables = 0;
+    for terminal in operators.terminals() {
+        match terminal {
+            NodeType::Variable(name) if name == token => return Ok(Node::Variable(variables)),
+            NodeType::Function(name, arity) if name == token => return Ok(Node::function(name, *arity)),
+            NodeType::Variable(_) => variables += 1,
+            _ => {}
+        }
+    }
+    if let Some(index) = token.strip_prefix('x').and_then(|index| index.parse::<usize>().ok()) {
+        if index < variables { return Ok(Node::Variable(index)); }
+    }
+
+    return token.parse::<f64>()
+        .map(Node::Constant)
+        .map_err(|_| ParseError::UnknownSymbol(token.to_string()));
+}
+
+fn is_word(c: char) -> bool { return c.is_alphanumeric() || c == '_'; }
+
+fn tokenize_infix(expression: &str) -> Vec<String> {
+    let chars = expression.chars().collect::<Vec<char>>();
+    let mut tokens = Vec::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() { i += 1; continue; }
+
+        let start = i;
+        if INFIX_DELIMITERS.contains(c) {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            // Numbers may contain signed exponent, e.g. `1e-6`
+            while i < chars.len() && (is_word(chars[i]) || chars[i] == '.'
+                || (matches!(chars[i], '+' | '-') && matches!(chars[i - 1], 'e' | 'E'))) {
+                i += 1;
+            }
+        } else if is_word(c) {
+            while i < chars.len() && is_word(chars[i]) { i += 1; }
+        } else {
+            while i < chars.len() && !chars[i].is_whitespace() && !is_word(chars[i])
+                && !INFIX_DELIMITERS.contains(chars[i]) {
+                i += 1;
+            }
+        }
+        tokens.push(chars[start..i].iter().collect());
+    }
+    return tokens;
+}
+
+fn tokenize_sexpr(expression: &str) -> Vec<String> {
+    return expression.replace('(', " ( ")
+        .replace(')', " ) ")
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+}
+
+/// Recursive descent parser producing nodes in prefix order.
+struct Parser<'a> {
+    tokens: Vec<String>,
+    position: usize,
+    operators: &'a Operators
+}
+
+impl<'a> Parser<'a> {
+    fn new(tokens: Vec<String>, operators: &'a Operators) -> Self { return Self { tokens, position: 0, operators }; }
+
+    fn peek(&self) -> Option<&str> { return self.tokens.get(self.position).map(String::as_str); }
+
+    fn next(&mut self) -> Result<String, ParseError> {
+        let token = self.tokens.get(self.position).cloned().ok_or(ParseError::UnexpectedEnd)?;
+        self.position += 1;
+        return Ok(token);
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), ParseError> {
+        let token = self.next()?;
+        if token != expected { return Err(ParseError::UnexpectedToken(token)); }
+        return Ok(());
+    }
+
+    fn finish(self, arena: Vec<Node>) -> Result<TreeGenotype, ParseError> {
+        if self.position < self.tokens.len() {
+            return Err(ParseError::TrailingTokens(self.tokens.len() - self.position));
+        }
+        return Ok(TreeGenotype::with_arena(arena));
+    }
+
+    /// Returns node with its arguments, checking that the argument count matches arity.
+    fn apply(symbol: &str, node: Node, arguments: Vec<Vec<Node>>) -> Result<Vec<Node>, ParseError> {
+        if node.arity() != arguments.len() {
+            return Err(ParseError::ArityMismatch {
+                symbol: symbol.to_string(), expected: node.arity(), found: arguments.len()
+            });
+        }
+        let mut arena = vec![node];
+        arguments.into_iter().for_each(|argument| arena.extend(argument));
+        return Ok(arena);
+    }
+
+    /// Parses binary operations binding at least as strong as `min_precedence`.
+    fn infix(&mut self, min_precedence: u8) -> Result<Vec<Node>, ParseError> {
+        let mut left = self.primary()?;
+        while let Some(precedence) = self.peek().and_then(operator_precedence) {
+            if precedence < min_precedence { break; }
+
+            let symbol = self.next()?;
+            let node = resolve(&symbol, self.operators)?;
+            let right = self.infix(precedence + 1)?;
+            left = Self::apply(&symbol, node, vec![left, right])?;
+        }
+        return Ok(left);
+    }
+
+    fn primary(&mut self) -> Result<Vec<Node>, ParseError> {
+        let token = self.next()?;
+        match token.as_str() {
+            "(" => {
+                let inner = self.infix(0)?;
+                self.expect(")")?;
+                return Ok(inner);
+            },
+            ")" | "," => return Err(ParseError::UnexpectedToken(token)),
+            "-" if self.peek().is_some_and(|value| value.parse::<f64>().is_ok()) => {
+                let value = self.next()?.parse::<f64>().expect("Token should be a number!");
+                return Ok(vec![Node::Constant(-value)]);
+            },
+            _ => {}
+        }
+
+        let node = resolve(&token, self.operators)?;
+        let mut arguments = Vec::new();
+        if self.peek() == Some("(") {
+            self.next()?;
+            if self.peek() != Some(")") {
+                arguments.push(self.infix(0)?);
+                while self.peek() == Some(",") {
+                    self.next()?;
+                    arguments.push(self.infix(0)?);
+                }
+            }
+            self.expect(")")?;
+        }
+        return Self::apply(&token, node, arguments);
+    }
+
+    fn sexpr(&mut self) -> Result<Vec<Node>, ParseError> {
+        let token = self.next()?;
+        match token.as_str() {
+            "(" => {
+                let symbol = self.next()?;
+                if symbol == "(" || symbol == ")" { return Err(ParseError::UnexpectedToken(symbol)); }
+
+                let node = resolve(&symbol, self.operators)?;
+                let mut arguments = Vec::new();
+                while self.peek() != Some(")") {
+                    arguments.push(self.sexpr()?);
+                }
+                self.next()?;
+                return Self::apply(&symbol, node, arguments);
+            },
+            ")" => return Err(ParseError::UnexpectedToken(token)),
+            _ => {
+                let node = resolve(&token, self.operators)?;
+                return Self::apply(&token, node, Vec::new());
+            }
+        }
+    }
+}
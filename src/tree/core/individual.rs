@@ -1,7 +1,7 @@
 //! Core individual structure for evolutionary algorithms.
 //!
 //! This module provides the [`TreeIndividual`] structure that combines genotype with its fitness
-//! value.
+//! value and, optionally, its errors on individual data rows (cases).
 use crate::common::traits::{Genotype, Individual};
 #[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize};
@@ -14,6 +14,7 @@ use serde::{Serialize, Deserialize};
 /// # Fields
 /// * `genotype: G` - [`Genotype`][`crate::common::traits::Genotype`] representation
 /// * `fitness: f64` - fitness value
+/// * `cases: Vec<f64>` - per-case errors, empty unless evaluated for case-based selection
 ///
 /// # Examples
 /// ```
@@ -36,7 +37,10 @@ use serde::{Serialize, Deserialize};
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TreeIndividual<G: Genotype> {
     genotype: G,
-    fitness: f64
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::float_serde"))]
+    fitness: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::float_serde::vec", default))]
+    cases: Vec<f64>
 }
 
 impl<G: Genotype> TreeIndividual<G> {
@@ -46,21 +50,37 @@ impl<G: Genotype> TreeIndividual<G> {
     /// * `genotype: G` - genotype representation
     /// * `fitness: f64` - fitness value
     pub fn new(genotype: G, fitness: f64) -> Self {
-        return Self { genotype, fitness };
+        return Self { genotype, fitness, cases: Vec::new() };
+    }
+
+    /// Attaches per-case errors to individual.
+    ///
+    /// # Arguments
+    /// * `cases: Vec<f64>` - error on every data row
+    pub fn with_cases(mut self, cases: Vec<f64>) -> Self {
+        self.cases = cases;
+        return self;
     }
 }
 
 impl<G: Genotype> Individual<G> for TreeIndividual<G> {
     fn genotype(&self) -> &G { return &self.genotype; }
     fn phenotype(&self) -> f64 { return self.fitness; }
+    fn cases(&self) -> &[f64] { return &self.cases; }
 
     fn from_vecs(genotypes: &[G], fitness: &[f64]) -> Vec<Self> {
         return genotypes.iter().zip(fitness.iter()).map(|(g, &f)| Self::new(g.clone(), f)).collect();
     }
     fn from_genotype_vec(genotypes: &[G]) -> Vec<Self> {
-        return genotypes.iter().map(|g| Self::new(g.clone(), f64::NEG_INFINITY)).collect();
+        return genotypes.iter().map(|g| Self::new(g.clone(), f64::NAN)).collect();
     }
     fn to_genotype_vec(individuals: &[Self]) -> Vec<G> {
         return individuals.iter().map(|i| i.genotype().clone()).collect();
     }
+
+    fn from_evaluations(genotypes: &[G], evaluations: Vec<(f64, Vec<f64>)>) -> Vec<Self> {
+        return genotypes.iter().zip(evaluations)
+            .map(|(g, (fitness, cases))| Self::new(g.clone(), fitness).with_cases(cases))
+            .collect();
+    }
 }
@@ -1,8 +1,18 @@
 //! Core structures for tree-based Genetic Programming.
 //!
 //! This module provides:
+//! - [`node`] - Typed tree nodes and their string form
+//! - [`errors`] - Error types for tree validation and parsing
 //! - [`tree`] - Tree genotype representation using arena
+//! - [`expression`] - Infix and S-expression printing and parsing of trees
+//! - [`export`] - Graphviz DOT and LaTeX export of trees
+//! - [`codegen`] - Rust, C and NumPy source code generation of trees
 //! - [`individual`] - Tree individual combining genotype and fitness
 
+pub mod node;
+pub mod errors;
 pub mod tree;
+pub mod expression;
+pub mod export;
+pub mod codegen;
 pub mod individual;
@@ -0,0 +1,212 @@
+//! Typed node representation for tree-based Genetic Programming.
+//!
+//! This module provides the [`Node`] enum stored in
+//! [`TreeGenotype`][`crate::tree::core::tree::TreeGenotype`] arena and the conversion layer between
+//! nodes and their string form. Function names are interned in a process-wide symbol table, so
+//! nodes are small, `Copy` and compared without touching strings, while every function node can
+//! still be turned back into its name.
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result};
+use std::hash::{Hash, Hasher};
+use std::sync::{OnceLock, RwLock};
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::operators::sampler::OperatorSampler;
+
+/// Process-wide table of interned function names.
+#[derive(Default)]
+struct SymbolTable {
+    names: Vec<String>,
+    ids: HashMap<String, usize>
+}
+
+fn symbols() -> &'static RwLock<SymbolTable> {
+    static SYMBOLS: OnceLock<RwLock<SymbolTable>> = OnceLock::new();
+    return SYMBOLS.get_or_init(|| RwLock::new(SymbolTable::default()));
+}
+
+/// Returns id of function with given name, registering the name if it was not seen before.
+///
+/// # Arguments
+/// * `name: &str` - function name
+///
+/// # Returns
+/// * `usize` - id unique for the name within the process
+pub fn intern(name: &str) -> usize {
+    if let Some(&id) = symbols().read().expect("Symbol table lock should not be poisoned!").ids.get(name) {
+        return id;
+    }
+    let mut table = symbols().write().expect("Symbol table lock should not be poisoned!");
+    if let Some(&id) = table.ids.get(name) { return id; }
+
+    let id = table.names.len();
+    table.names.push(name.to_string());
+    table.ids.insert(name.to_string(), id);
+    return id;
+}
+
+/// Returns name of interned function.
+///
+/// # Panics
+/// * If `id` was not returned by [`intern`]
+pub fn symbol(id: usize) -> String {
+    let table = symbols().read().expect("Symbol table lock should not be poisoned!");
+    return table.names.get(id).unwrap_or_else(|| panic!("Unknown function id {}!", id)).clone();
+}
+
+/// Errors that can occur during conversion from string form.
+///
+/// # Variants
+/// * `UnknownSymbol(String)` - token is neither an operator known to the sampler nor a number
+/// * `IncompleteTree(usize)` - tokens ended while given number of operands was still missing
+/// * `TrailingTokens(usize)` - given number of tokens left after the tree was complete
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeError {
+    UnknownSymbol(String),
+    IncompleteTree(usize),
+    TrailingTokens(usize)
+}
+
+impl std::error::Error for NodeError {}
+
+impl Display for NodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            NodeError::UnknownSymbol(token) => write!(f, "Unknown symbol: {}", token),
+            NodeError::IncompleteTree(missing) => write!(f, "Tree is missing {} operand(s)!", missing),
+            NodeError::TrailingTokens(count) => write!(f, "Found {} token(s) after complete tree!", count),
+        }
+    }
+}
+
+/// Single node of a tree.
+///
+/// # Variants
+/// * `Function(usize, usize)` - function with interned id (see [`intern`]) and arity; functions
+///   of arity 0 are evaluated on the whole feature set
+/// * `Variable(usize)` - input feature with given column index
+/// * `Constant(f64)` - constant numeric value
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::core::node::Node;
+///
+/// let add = Node::function("+", 2);
+///
+/// assert_eq!(add.arity(), 2);
+/// assert_eq!(add.name(), "+");
+/// assert_eq!(Node::Variable(1).to_string(), "x1");
+/// assert_eq!(Node::Constant(0.5).to_string(), "0.5");
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "NodeRepr", from = "NodeRepr"))]
+pub enum Node {
+    Function(usize, usize),
+    Variable(usize),
+    Constant(f64)
+}
+
+impl Node {
+    /// Creates function node, interning its name.
+    pub fn function(name: &str, arity: usize) -> Self { return Node::Function(intern(name), arity); }
+
+    /// Returns number of children the node requires.
+    pub fn arity(&self) -> usize {
+        return match self {
+            Node::Function(_, arity) => *arity,
+            Node::Variable(_) | Node::Constant(_) => 0,
+        };
+    }
+
+    /// Returns string form of the node, variables are named after their column index.
+    pub fn name(&self) -> String {
+        return match self {
+            Node::Function(id, _) => symbol(*id),
+            Node::Variable(index) => format!("x{}", index),
+            Node::Constant(value) => format!("{}", value),
+        };
+    }
+
+    pub fn is_function(&self) -> bool { return matches!(self, Node::Function(_, _)); }
+    pub fn is_terminal(&self) -> bool { return self.arity() == 0; }
+
+    /// Converts token into a node.
+    ///
+    /// Tokens are first looked up among sampler operators, so a variable named `"1"` stays a
+    /// variable. Remaining tokens are parsed as constants.
+    ///
+    /// # Arguments
+    /// * `token: &str` - string form of the node
+    /// * `sampler: &OperatorSampler` - operators available in the tree
+    ///
+    /// # Returns
+    /// * `Result<Node, NodeError>` - node or [`error`][`NodeError`] if token is unknown
+    pub fn from_token(token: &str, sampler: &OperatorSampler) -> std::result::Result<Self, NodeError> {
+        if let Some(node) = sampler.node(token) { return Ok(node); }
+        return token.parse::<f64>()
+            .map(Node::Constant)
+            .map_err(|_| NodeError::UnknownSymbol(token.to_string()));
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result { return write!(f, "{}", self.name()); }
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        return match (self, other) {
+            (Node::Function(a, n), Node::Function(b, m)) => a == b && n == m,
+            (Node::Variable(a), Node::Variable(b)) => a == b,
+            (Node::Constant(a), Node::Constant(b)) => a.to_bits() == b.to_bits(),
+            _ => false,
+        };
+    }
+}
+
+impl Eq for Node {}
+
+impl Hash for Node {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Node::Function(id, arity) => { id.hash(state); arity.hash(state); },
+            Node::Variable(index) => index.hash(state),
+            Node::Constant(value) => value.to_bits().hash(state),
+        }
+    }
+}
+
+/// Serialized form of [`Node`], storing function names instead of process-local ids.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+enum NodeRepr {
+    Function(String, usize),
+    Variable(usize),
+    Constant(f64)
+}
+
+#[cfg(feature = "serde")]
+impl From<Node> for NodeRepr {
+    fn from(node: Node) -> Self {
+        return match node {
+            Node::Function(id, arity) => NodeRepr::Function(symbol(id), arity),
+            Node::Variable(index) => NodeRepr::Variable(index),
+            Node::Constant(value) => NodeRepr::Constant(value),
+        };
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<NodeRepr> for Node {
+    fn from(repr: NodeRepr) -> Self {
+        return match repr {
+            NodeRepr::Function(name, arity) => Node::function(&name, arity),
+            NodeRepr::Variable(index) => Node::Variable(index),
+            NodeRepr::Constant(value) => Node::Constant(value),
+        };
+    }
+}
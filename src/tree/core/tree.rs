@@ -1,8 +1,8 @@
 //! Core tree structure for tree-based Genetic Programming
 //!
 //! This module provides the [`TreeGenotype`] structure that represents programs as trees using a
-//! linear array (arena) in postfix notation with explicit child references.
-use std::fmt::Write;
+//! linear array (arena) of typed [`nodes`][`crate::tree::core::node::Node`] in prefix notation
+//! with explicit child references.
 use std::hash::{Hash, Hasher};
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter, Result};
@@ -12,17 +12,21 @@ use serde::{Serialize, Deserialize};
 
 use crate::common::traits::Genotype;
 use crate::operators::sampler::OperatorSampler;
+use crate::operators::set::{NodeType, Operators};
+use crate::tree::core::errors::TreeError;
+use crate::tree::core::node::{symbol, Node, NodeError};
 
 /// Tree structure for representing programs in Genetic Programming.
 /// Uses arena=based representation with hashmap of parent-child relationships.
 ///
 /// # Fields
-/// * `arena: Vec<String>` - flat array storing nodes (operators and terminals) in postfix order
+/// * `arena: Vec<Node>` - flat array storing [`nodes`][`crate::tree::core::node::Node`]
+///   (functions and terminals) in prefix order
 /// * `children: HashMap<usize, Vec<usize>>` - maps parent indices to their children indices
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct TreeGenotype {
-    arena: Vec<String>,
+    arena: Vec<Node>,
     children: HashMap<usize, Vec<usize>>,
 }
 
@@ -30,12 +34,61 @@ impl Genotype for TreeGenotype {}
 
 impl TreeGenotype {
     /// Creates new tree with provided arena and children mapping.
-    pub fn new(arena: Vec<String>, children: HashMap<usize, Vec<usize>>) -> Self { return Self { arena, children }; }
-    /// Creates new tree with provided arena and empty children mapping.
-    pub fn with_arena(arena: Vec<String>) -> Self { return Self { arena, children: HashMap::new() }; }
+    pub fn new(arena: Vec<Node>, children: HashMap<usize, Vec<usize>>) -> Self { return Self { arena, children }; }
+    /// Creates new tree with provided arena, children mapping is derived from node arities.
+    pub fn with_arena(arena: Vec<Node>) -> Self {
+        let mut tree = Self { arena, children: HashMap::new() };
+        tree.children = tree.construct_children();
+        return tree;
+    }
 
-    pub fn arena(&self) -> &Vec<String> { return &self.arena; }
-    pub fn arena_mut(&mut self) -> &mut Vec<String> { return &mut self.arena; }
+    /// Creates new tree from string form of its nodes in prefix order.
+    ///
+    /// # Arguments
+    /// * `tokens: &[S]` - node names, see [`Node::from_token`][`crate::tree::core::node::Node::from_token`]
+    /// * `sampler: &OperatorSampler` - operators available in the tree
+    ///
+    /// # Returns
+    /// * `Result<Self, NodeError>` - new tree or [`error`][`crate::tree::core::node::NodeError`]
+    ///   if a token is unknown or tokens do not form exactly one tree
+    ///
+    /// # Examples
+    /// ```
+    /// use mycoforge::operators::sampler::OperatorSampler;
+    /// use mycoforge::tree::core::tree::TreeGenotype;
+    ///
+    /// let operators = ["+", "x"].iter().map(|&w| w.to_string()).collect();
+    /// let sampler = OperatorSampler::new(operators, vec![2, 0], vec![0.5, 0.5]);
+    ///
+    /// let tree = TreeGenotype::from_tokens(&["+", "x", "1.5"], &sampler).expect("Failed to parse tree!");
+    ///
+    /// assert_eq!(tree.children()[&0], vec![1, 2]);
+    /// assert_eq!(tree.to_tokens(&sampler), vec!["+", "x", "1.5"]);
+    /// ```
+    pub fn from_tokens<S: AsRef<str>>(tokens: &[S], sampler: &OperatorSampler) -> std::result::Result<Self, NodeError> {
+        let arena = tokens.iter()
+            .map(|token| Node::from_token(token.as_ref(), sampler))
+            .collect::<std::result::Result<Vec<Node>, NodeError>>()?;
+
+        let mut missing: usize = 1;
+        for (i, node) in arena.iter().enumerate() {
+            if missing == 0 { return Err(NodeError::TrailingTokens(arena.len() - i)); }
+            missing = missing - 1 + node.arity();
+        }
+        if missing > 0 { return Err(NodeError::IncompleteTree(missing)); }
+
+        return Ok(Self::with_arena(arena));
+    }
+
+    /// Returns string form of nodes in prefix order, using sampler names where available.
+    pub fn to_tokens(&self, sampler: &OperatorSampler) -> Vec<String> {
+        return self.arena.iter()
+            .map(|node| sampler.name(node).map(str::to_string).unwrap_or_else(|| node.name()))
+            .collect();
+    }
+
+    pub fn arena(&self) -> &Vec<Node> { return &self.arena; }
+    pub fn arena_mut(&mut self) -> &mut Vec<Node> { return &mut self.arena; }
     pub fn children(&self) -> &HashMap<usize, Vec<usize>> { return &self.children; }
     pub fn children_mut(&mut self) -> &mut HashMap<usize, Vec<usize>> { return &mut self.children; }
 
@@ -59,35 +112,185 @@ impl TreeGenotype {
         return last_visited;
     }
     
-    /// Constructs children mapping from flat arena representation.
+    /// Returns number of nodes in subtree rooted at given index (including the root).
+    pub fn subtree_size(&self, root: usize) -> usize { return PreOrder::new(self, root).count(); }
+
+    /// Returns index of parent of given node, `None` for the root.
+    ///
+    /// Scans the whole children map, for many queries build the index once with
+    /// [`parents`][`TreeGenotype::parents`].
+    pub fn parent(&self, index: usize) -> Option<usize> {
+        return self.children.iter()
+            .find(|(_, children)| children.contains(&index))
+            .map(|(&parent, _)| parent);
+    }
+
+    /// Returns parent index of every node in the arena, `None` for the root.
+    pub fn parents(&self) -> Vec<Option<usize>> {
+        let mut parents = vec![None; self.arena.len()];
+        for (&parent, children) in &self.children {
+            for &child in children {
+                if let Some(slot) = parents.get_mut(child) { *slot = Some(parent); }
+            }
+        }
+        return parents;
+    }
+
+    /// Returns depth of given node, the root has depth 0.
+    pub fn node_depth(&self, index: usize) -> usize {
+        let parents = self.parents();
+        let mut depth = 0;
+        let mut current = index;
+        while let Some(parent) = parents.get(current).copied().flatten() {
+            depth += 1;
+            current = parent;
+        }
+        return depth;
+    }
+
+    /// Returns depth of the tree, i.e. the largest depth of any node (0 for empty tree).
+    pub fn depth(&self) -> usize {
+        if self.arena.is_empty() { return 0; }
+
+        let mut depth = 0;
+        let mut stack = vec![(0, 0)];
+        while let Some((index, node_depth)) = stack.pop() {
+            depth = depth.max(node_depth);
+            if let Some(children) = self.children.get(&index) {
+                stack.extend(children.iter().map(|&child| (child, node_depth + 1)));
+            }
+        }
+        return depth;
+    }
+
+    /// Returns iterator over node indices in pre-order (parent before its children).
+    ///
+    /// # Examples
+    /// ```
+    /// use mycoforge::operators::sampler::OperatorSampler;
+    /// use mycoforge::tree::core::tree::TreeGenotype;
+    ///
+    /// let operators = ["+", "sin", "x"].iter().map(|&w| w.to_string()).collect();
+    /// let sampler = OperatorSampler::new(operators, vec![2, 1, 0], vec![1.0 / 3.0; 3]);
+    /// let tree = TreeGenotype::from_tokens(&["+", "sin", "x", "x"], &sampler).unwrap();
+    ///
+    /// assert_eq!(tree.preorder().collect::<Vec<usize>>(), vec![0, 1, 2, 3]);
+    /// assert_eq!(tree.postorder().collect::<Vec<usize>>(), vec![2, 1, 3, 0]);
+    /// assert_eq!(tree.leaves().collect::<Vec<usize>>(), vec![2, 3]);
+    /// assert_eq!((tree.depth(), tree.node_depth(2), tree.parent(2)), (2, 2, Some(1)));
+    /// ```
+    pub fn preorder(&self) -> PreOrder<'_> { return PreOrder::new(self, 0); }
+
+    /// Returns iterator over node indices in post-order (children before their parent).
+    pub fn postorder(&self) -> PostOrder<'_> { return PostOrder::new(self, 0); }
+
+    /// Returns iterator over indices of leaf nodes in pre-order.
+    pub fn leaves(&self) -> impl Iterator<Item = usize> + '_ {
+        return self.preorder().filter(|index| self.children.get(index).is_none_or(|c| c.is_empty()));
+    }
+
+    /// Returns number of function nodes (nodes with at least one child).
+    pub fn function_count(&self) -> usize { return self.arena.iter().filter(|node| node.arity() > 0).count(); }
+
+    /// Returns number of terminal nodes (nodes without children).
+    pub fn terminal_count(&self) -> usize { return self.arena.iter().filter(|node| node.arity() == 0).count(); }
+
+    /// Validates tree against operator set.
+    ///
+    /// Checks that every function is present in the set with the same arity, every variable index
+    /// refers to a variable of the set, nodes form exactly one tree and children map matches the
+    /// arena. Constants are always valid.
     ///
     /// # Arguments
-    /// * `sampler: &OperatorSampler` - provides operator arities and labels for tree construction
+    /// * `operators: &Operators` - [`operator set`][`crate::operators::set::Operators`] the tree
+    ///   was built from
     ///
     /// # Returns
-    /// * `HashMap<usize, Vec<usiz>>` - mapping of parent indices to children indices
-    pub fn construct_children(&self, sampler: &OperatorSampler) -> HashMap<usize, Vec<usize>> {
+    /// * `Result<(), TreeError>` - `Ok` for valid tree or first found
+    ///   [`error`][`crate::tree::core::errors::TreeError`]
+    pub fn validate(&self, operators: &Operators) -> std::result::Result<(), TreeError> {
+        let variables = operators.terminals().iter().filter(|t| matches!(t, NodeType::Variable(_))).count();
+        self.validate_symbols(|node| match node {
+            Node::Function(id, _) => operators.functions().get(&symbol(*id)).map(|f| f.arity()),
+            Node::Variable(index) => (*index < variables).then_some(0),
+            Node::Constant(_) => Some(0),
+        })?;
+        return self.validate_structure();
+    }
+
+    /// Validates tree against operators of a sampler, see [`TreeGenotype::validate`].
+    pub fn validate_with_sampler(&self, sampler: &OperatorSampler) -> std::result::Result<(), TreeError> {
+        self.validate_symbols(|node| match node {
+            Node::Function(id, _) => sampler.nodes().iter()
+                .find(|n| matches!(n, Node::Function(other, _) if other == id))
+                .map(|n| n.arity()),
+            Node::Variable(_) => sampler.nodes().contains(node).then_some(0),
+            Node::Constant(_) => Some(0),
+        })?;
+        return self.validate_structure();
+    }
+
+    /// Validates that nodes form exactly one tree and children map matches the arena, without
+    /// checking symbols.
+    pub fn validate_structure(&self) -> std::result::Result<(), TreeError> {
+        if self.arena.is_empty() { return Err(TreeError::EmptyTree); }
+
+        let mut missing: usize = 1;
+        for (i, node) in self.arena.iter().enumerate() {
+            if missing == 0 { return Err(TreeError::ExtraNodes(self.arena.len() - i)); }
+            missing = missing - 1 + node.arity();
+        }
+        if missing > 0 { return Err(TreeError::MissingOperands(missing)); }
+
+        let expected = self.construct_children();
+        let mut indices = expected.keys().chain(self.children.keys()).copied().collect::<Vec<usize>>();
+        indices.sort_unstable();
+        indices.dedup();
+        for index in indices {
+            let (expected, found) = (
+                expected.get(&index).cloned().unwrap_or_default(),
+                self.children.get(&index).cloned().unwrap_or_default()
+            );
+            if expected != found { return Err(TreeError::InconsistentChildren { index, expected, found }); }
+        }
+        return Ok(());
+    }
+
+    /// Checks every node against arity reported by `lookup`, `None` meaning unknown symbol.
+    fn validate_symbols<F: Fn(&Node) -> Option<usize>>(&self, lookup: F) -> std::result::Result<(), TreeError> {
+        for (index, node) in self.arena.iter().enumerate() {
+            match lookup(node) {
+                None => return Err(TreeError::UnknownSymbol { index, symbol: node.name() }),
+                Some(expected) if expected != node.arity() => return Err(TreeError::ArityMismatch {
+                    index, symbol: node.name(), expected, found: node.arity()
+                }),
+                Some(_) => {}
+            }
+        }
+        return Ok(());
+    }
+
+    /// Constructs children mapping from flat arena representation using node arities.
+    ///
+    /// # Returns
+    /// * `HashMap<usize, Vec<usize>>` - mapping of parent indices to children indices
+    pub fn construct_children(&self) -> HashMap<usize, Vec<usize>> {
         let mut children = HashMap::new();
-        let operators = sampler.operators();
-        let arities = sampler.arities();
+        if self.arena.is_empty() { return children; }
 
         let mut stack = vec![0]; // Stack of nodes to generate children for
         let mut current = 0;
 
         while let Some(parent) = stack.pop() {
+            if current >= self.arena.len() { break; }
             if parent != current {
                 children.entry(parent)
                     .and_modify(|vec: &mut Vec<usize>| vec.push(current))
                     .or_insert(vec![current]);
             }
 
-            if let Some(op_idx) = operators.iter().position(|op| *op == self.arena()[current]) {
-                let arity = arities[op_idx];
-                if arity > 0 { 
-                    for _ in 0..arity {
-                        stack.push(current);
-                    }
-                }
+            for _ in 0..self.arena[current].arity() {
+                stack.push(current);
             }
             current += 1;
         }
@@ -135,14 +338,7 @@ impl Default for TreeGenotype {
 }
 
 impl Hash for TreeGenotype {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        let hashable = self.arena.iter().fold(
-            String::new(), |mut hashable, word| {
-                hashable.push_str(word);
-                hashable
-        });
-        hashable.hash(state);
-    }
+    fn hash<H: Hasher>(&self, state: &mut H) { self.arena.hash(state); }
 }
 
 impl Eq for TreeGenotype {}
@@ -152,3 +348,57 @@ impl PartialEq for TreeGenotype {
         return self.arena == other.arena;
     }
 }
+
+/// Pre-order iterator over node indices of a [`TreeGenotype`], see [`TreeGenotype::preorder`].
+pub struct PreOrder<'a> {
+    tree: &'a TreeGenotype,
+    stack: Vec<usize>
+}
+
+impl<'a> PreOrder<'a> {
+    fn new(tree: &'a TreeGenotype, root: usize) -> Self {
+        let stack = if root < tree.arena.len() { vec![root] } else { Vec::new() };
+        return Self { tree, stack };
+    }
+}
+
+impl Iterator for PreOrder<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let index = self.stack.pop()?;
+        if let Some(children) = self.tree.children.get(&index) {
+            self.stack.extend(children.iter().rev());
+        }
+        return Some(index);
+    }
+}
+
+/// Post-order iterator over node indices of a [`TreeGenotype`], see [`TreeGenotype::postorder`].
+pub struct PostOrder<'a> {
+    tree: &'a TreeGenotype,
+    stack: Vec<(usize, bool)>
+}
+
+impl<'a> PostOrder<'a> {
+    fn new(tree: &'a TreeGenotype, root: usize) -> Self {
+        let stack = if root < tree.arena.len() { vec![(root, false)] } else { Vec::new() };
+        return Self { tree, stack };
+    }
+}
+
+impl Iterator for PostOrder<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while let Some((index, expanded)) = self.stack.pop() {
+            if expanded { return Some(index); }
+
+            self.stack.push((index, true));
+            if let Some(children) = self.tree.children.get(&index) {
+                self.stack.extend(children.iter().rev().map(|&child| (child, false)));
+            }
+        }
+        return None;
+    }
+}
@@ -0,0 +1,427 @@
+//! Classification fitness functions for tree-based Genetic Programming.
+//!
+//! Trees output raw scores; [`BinaryClassifier`] maps them to probabilities with the logistic
+//! sigmoid and to labels 0 and 1 by thresholding the probability. Binary metrics implement
+//! [`Loss`][`crate::tree::fitness::evaluate::Loss`] over raw scores and labels, so they evaluate
+//! trees like any regression metric and are minimized:
+//! - [`Accuracy`] - classification error, `1 - accuracy`
+//! - [`BalancedAccuracy`] - `1 - balanced accuracy`, robust to imbalanced classes
+//! - [`F1`] - `1 - F1 score` of the positive class
+//! - [`LogLoss`] - binary cross-entropy of predicted probabilities
+//! - [`AUC`] - `1 - area under ROC curve`, independent of the threshold
+//!
+//! Per-case errors of label-based metrics and [`AUC`] are misclassifications (1.0 for a wrong
+//! label, 0.0 otherwise), [`LogLoss`] reports cross-entropy of every sample.
+//!
+//! Multiclass problems are decomposed by [`OneVsRest`] into one binary problem per class, each
+//! solved by a separate run. Scores of the resulting trees are mapped to class labels by
+//! [`OneVsRest::labels`], models expose the mapping through
+//! [`OneVsRestModel::predict_labels`][`crate::tree::model::OneVsRestModel::predict_labels`].
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::dataset::core::Dataset;
+use crate::tree::fitness::evaluate::{EvaluatorError, Loss};
+
+/// Logistic sigmoid, evaluated without overflow for scores of any magnitude.
+fn sigmoid(score: f64) -> f64 {
+    if score >= 0.0 { return 1.0 / (1.0 + (-score).exp()); }
+    let exp = score.exp();
+    return exp / (1.0 + exp);
+}
+
+fn is_positive(target: f64) -> bool { return target > 0.5; }
+
+/// Counts of true positives, false positives, true negatives and false negatives.
+fn confusion(classifier: &BinaryClassifier, scores: &[f64], targets: &[f64]) -> (f64, f64, f64, f64) {
+    let (mut tp, mut fp, mut tn, mut r#fn) = (0.0, 0.0, 0.0, 0.0);
+    for (&score, &target) in scores.iter().zip(targets.iter()) {
+        match (classifier.is_positive(score), is_positive(target)) {
+            (true, true) => tp += 1.0,
+            (true, false) => fp += 1.0,
+            (false, false) => tn += 1.0,
+            (false, true) => r#fn += 1.0,
+        }
+    }
+    return (tp, fp, tn, r#fn);
+}
+
+/// Misclassification of every sample, 1.0 when predicted label differs from target.
+fn misclassifications(classifier: &BinaryClassifier, scores: &[f64], targets: &[f64]) -> Vec<f64> {
+    return scores.iter()
+        .zip(targets.iter())
+        .map(|(&score, &target)| if classifier.is_positive(score) == is_positive(target) { 0.0 } else { 1.0 })
+        .collect();
+}
+
+/// Maps raw tree scores to probabilities and binary labels.
+///
+/// Probability of the positive class is the logistic sigmoid of the score, label is 1.0 when the
+/// probability reaches `threshold` and 0.0 otherwise. NaN scores are labelled negative.
+///
+/// # Fields
+/// * `threshold: f64` - probability threshold in (0.0, 1.0) range, 0.5 by default (score 0)
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::classify::BinaryClassifier;
+///
+/// let classifier = BinaryClassifier::default();
+///
+/// assert_eq!(classifier.probability(0.0), 0.5);
+/// assert_eq!(classifier.labels(&[-2.0, 0.0, 3.0]), vec![0.0, 1.0, 1.0]);
+/// assert_eq!(BinaryClassifier::new(0.9).expect("Failed to create classifier!").label(2.0), 0.0);
+/// assert!(BinaryClassifier::new(1.0).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BinaryClassifier {
+    threshold: f64
+}
+
+impl BinaryClassifier {
+    /// Creates new classifier.
+    ///
+    /// # Arguments
+    /// * `threshold: f64` - probability threshold of the positive class
+    ///
+    /// # Returns
+    /// * `Result<Self, EvaluatorError>` - new classifier or error if threshold is outside
+    ///   (0.0, 1.0) range
+    pub fn new(threshold: f64) -> Result<Self, EvaluatorError> {
+        if !(threshold > 0.0 && threshold < 1.0) { return Err(EvaluatorError::InvalidThreshold(threshold)); }
+        return Ok(Self { threshold });
+    }
+
+    pub fn threshold(&self) -> f64 { return self.threshold; }
+
+    /// Returns probability of the positive class for raw score.
+    pub fn probability(&self, score: f64) -> f64 { return sigmoid(score); }
+
+    /// Returns label, 1.0 or 0.0, for raw score.
+    pub fn label(&self, score: f64) -> f64 { return if self.is_positive(score) { 1.0 } else { 0.0 }; }
+
+    /// Returns probabilities of the positive class for raw scores.
+    pub fn probabilities(&self, scores: &[f64]) -> Vec<f64> {
+        return scores.iter().map(|&score| self.probability(score)).collect();
+    }
+
+    /// Returns labels for raw scores.
+    pub fn labels(&self, scores: &[f64]) -> Vec<f64> {
+        return scores.iter().map(|&score| self.label(score)).collect();
+    }
+
+    fn is_positive(&self, score: f64) -> bool { return self.probability(score) >= self.threshold; }
+}
+
+impl Default for BinaryClassifier {
+    fn default() -> Self { return Self { threshold: 0.5 }; }
+}
+
+/// Accuracy evaluator that computes fitness as the fraction of misclassified samples.
+///
+/// Targets are class labels 0 and 1.
+///
+/// # Fields
+/// * `classifier: BinaryClassifier` - [`mapping`][`BinaryClassifier`] of scores to labels
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::classify::Accuracy;
+/// use mycoforge::tree::fitness::evaluate::Loss;
+///
+/// assert_eq!(Accuracy::default().loss(&[-1.0, 2.0, 0.5, -3.0], &[0.0, 1.0, 0.0, 0.0]), 0.25);
+/// ```
+#[derive(Default)]
+pub struct Accuracy {
+    classifier: BinaryClassifier
+}
+
+impl Accuracy {
+    pub fn new(classifier: BinaryClassifier) -> Self { return Self { classifier }; }
+
+    pub fn classifier(&self) -> &BinaryClassifier { return &self.classifier; }
+}
+
+impl Loss for Accuracy {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        let (tp, fp, tn, r#fn) = confusion(&self.classifier, predictions, targets);
+        return (fp + r#fn) / (tp + fp + tn + r#fn);
+    }
+
+    fn case_errors(&self, predictions: &[f64], targets: &[f64]) -> Vec<f64> {
+        return misclassifications(&self.classifier, predictions, targets);
+    }
+}
+
+/// Balanced accuracy evaluator that computes fitness as `1 - (TPR + TNR) / 2`, the complement of
+/// average recall of both classes.
+///
+/// Unlike [`Accuracy`] it does not reward predicting the majority class on imbalanced data. If
+/// one class is missing from targets, only recall of the present class counts.
+///
+/// # Fields
+/// * `classifier: BinaryClassifier` - [`mapping`][`BinaryClassifier`] of scores to labels
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::classify::BalancedAccuracy;
+/// use mycoforge::tree::fitness::evaluate::Loss;
+///
+/// // Always predicting the majority class
+/// assert_eq!(BalancedAccuracy::default().loss(&[-1.0, -1.0, -1.0, -1.0], &[0.0, 0.0, 0.0, 1.0]), 0.5);
+/// ```
+#[derive(Default)]
+pub struct BalancedAccuracy {
+    classifier: BinaryClassifier
+}
+
+impl BalancedAccuracy {
+    pub fn new(classifier: BinaryClassifier) -> Self { return Self { classifier }; }
+
+    pub fn classifier(&self) -> &BinaryClassifier { return &self.classifier; }
+}
+
+impl Loss for BalancedAccuracy {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        let (tp, fp, tn, r#fn) = confusion(&self.classifier, predictions, targets);
+        let recalls = [(tp, tp + r#fn), (tn, tn + fp)].into_iter()
+            .filter(|&(_, total)| total > 0.0)
+            .map(|(hits, total)| hits / total)
+            .collect::<Vec<f64>>();
+        return 1.0 - recalls.iter().sum::<f64>() / (recalls.len() as f64);
+    }
+
+    fn case_errors(&self, predictions: &[f64], targets: &[f64]) -> Vec<f64> {
+        return misclassifications(&self.classifier, predictions, targets);
+    }
+}
+
+/// F1 evaluator that computes fitness as `1 - F1`, where F1 is the harmonic mean of precision
+/// and recall of the positive class.
+///
+/// If there are neither positive targets nor positive predictions, F1 is 1.
+///
+/// # Fields
+/// * `classifier: BinaryClassifier` - [`mapping`][`BinaryClassifier`] of scores to labels
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::classify::F1;
+/// use mycoforge::tree::fitness::evaluate::Loss;
+///
+/// // Precision 1/2, recall 1
+/// assert_eq!(F1::default().loss(&[1.0, 1.0, -1.0], &[1.0, 0.0, 0.0]), 1.0 - 2.0 / 3.0);
+/// ```
+#[derive(Default)]
+pub struct F1 {
+    classifier: BinaryClassifier
+}
+
+impl F1 {
+    pub fn new(classifier: BinaryClassifier) -> Self { return Self { classifier }; }
+
+    pub fn classifier(&self) -> &BinaryClassifier { return &self.classifier; }
+}
+
+impl Loss for F1 {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        let (tp, fp, _, r#fn) = confusion(&self.classifier, predictions, targets);
+        if tp + fp + r#fn == 0.0 { return 0.0; }
+        return 1.0 - 2.0 * tp / (2.0 * tp + fp + r#fn);
+    }
+
+    fn case_errors(&self, predictions: &[f64], targets: &[f64]) -> Vec<f64> {
+        return misclassifications(&self.classifier, predictions, targets);
+    }
+}
+
+/// Log-loss (binary cross-entropy) evaluator that computes fitness as average negative
+/// log-likelihood of targets under sigmoid probabilities of scores.
+///
+/// Computed directly from scores as `ln(1 + e^s) - y * s`, so confident mistakes yield large but
+/// finite values instead of infinity.
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::classify::LogLoss;
+/// use mycoforge::tree::fitness::evaluate::Loss;
+///
+/// assert_eq!(LogLoss::new().loss(&[0.0, 0.0], &[0.0, 1.0]), 2f64.ln());
+/// assert!(LogLoss::new().loss(&[1000.0], &[0.0]).is_finite());
+/// ```
+pub struct LogLoss {}
+
+impl LogLoss {
+    pub fn new() -> Self { return Self {}; }
+}
+
+impl Default for LogLoss {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl Loss for LogLoss {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        return self.case_errors(predictions, targets).iter().sum::<f64>() / (targets.len() as f64);
+    }
+
+    fn case_errors(&self, predictions: &[f64], targets: &[f64]) -> Vec<f64> {
+        return predictions.iter()
+            .zip(targets.iter())
+            .map(|(&score, &target)| {
+                let softplus = score.max(0.0) + (-score.abs()).exp().ln_1p();
+                return if is_positive(target) { softplus - score } else { softplus };
+            }).collect();
+    }
+}
+
+/// Area under ROC curve (AUC) evaluator that computes fitness as `1 - AUC`.
+///
+/// AUC is the probability that a random positive sample scores higher than a random negative
+/// one, ties count as half, so it depends only on the ordering of scores. If targets contain a
+/// single class AUC is undefined and fitness is 0.5, as for random scores. NaN scores yield NaN.
+/// Per-case errors are misclassifications under the default [`BinaryClassifier`].
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::classify::AUC;
+/// use mycoforge::tree::fitness::evaluate::Loss;
+///
+/// assert_eq!(AUC::new().loss(&[0.1, 0.4, 0.35, 0.8], &[0.0, 0.0, 1.0, 1.0]), 0.25);
+/// ```
+pub struct AUC {}
+
+impl AUC {
+    pub fn new() -> Self { return Self {}; }
+}
+
+impl Default for AUC {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl Loss for AUC {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        if predictions.iter().any(|score| score.is_nan()) { return f64::NAN; }
+        let positives = targets.iter().filter(|&&target| is_positive(target)).count() as f64;
+        let negatives = targets.len() as f64 - positives;
+        if positives == 0.0 || negatives == 0.0 { return 0.5; }
+
+        let mut order = (0..predictions.len()).collect::<Vec<usize>>();
+        order.sort_by(|&a, &b| predictions[a].total_cmp(&predictions[b]));
+
+        // Mann-Whitney U statistic, tied scores share their average rank
+        let mut rank_sum = 0.0;
+        let mut start = 0;
+        while start < order.len() {
+            let mut end = start + 1;
+            while end < order.len() && predictions[order[end]] == predictions[order[start]] { end += 1; }
+            let rank = (start + end + 1) as f64 / 2.0;
+            rank_sum += rank * order[start..end].iter().filter(|&&i| is_positive(targets[i])).count() as f64;
+            start = end;
+        }
+        let auc = (rank_sum - positives * (positives + 1.0) / 2.0) / (positives * negatives);
+        return 1.0 - auc;
+    }
+
+    fn case_errors(&self, predictions: &[f64], targets: &[f64]) -> Vec<f64> {
+        return misclassifications(&BinaryClassifier::default(), predictions, targets);
+    }
+}
+
+/// One-vs-rest decomposition of multiclass classification into binary problems.
+///
+/// Each class gets its own binary problem, where the class is positive and all others negative,
+/// solved by a separate run with a binary metric. A sample is then assigned to the class whose
+/// tree gives the highest score.
+///
+/// # Fields
+/// * `classes: Vec<f64>` - distinct class labels in ascending order
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::classify::OneVsRest;
+///
+/// let scheme = OneVsRest::from_targets(&[2.0, 0.0, 1.0, 2.0]).expect("Failed to create scheme!");
+///
+/// assert_eq!(scheme.classes(), &[0.0, 1.0, 2.0]);
+/// assert_eq!(scheme.binary_targets(&[2.0, 0.0, 1.0, 2.0], 2), vec![1.0, 0.0, 0.0, 1.0]);
+///
+/// // Scores of per-class trees, one vector per class
+/// let scores = vec![vec![0.5, -1.0], vec![2.0, -3.0], vec![-1.0, 0.0]];
+/// assert_eq!(scheme.labels(&scores), vec![1.0, 2.0]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OneVsRest {
+    classes: Vec<f64>
+}
+
+impl OneVsRest {
+    /// Creates new scheme for given classes.
+    ///
+    /// # Arguments
+    /// * `classes: Vec<f64>` - class labels, duplicates are removed
+    ///
+    /// # Returns
+    /// * `Result<Self, EvaluatorError>` - new scheme or error if a label is not finite or there
+    ///   are fewer than two classes
+    pub fn new(mut classes: Vec<f64>) -> Result<Self, EvaluatorError> {
+        if let Some(&class) = classes.iter().find(|class| !class.is_finite()) {
+            return Err(EvaluatorError::InvalidClass(class));
+        }
+        classes.sort_by(|a, b| a.total_cmp(b));
+        classes.dedup();
+        if classes.len() < 2 { return Err(EvaluatorError::TooFewClasses(classes.len())); }
+        return Ok(Self { classes });
+    }
+
+    /// Creates new scheme for classes present in targets.
+    pub fn from_targets(targets: &[f64]) -> Result<Self, EvaluatorError> { return Self::new(targets.to_vec()); }
+
+    pub fn classes(&self) -> &[f64] { return &self.classes; }
+
+    /// Returns targets of binary problem of given class, 1.0 for the class and 0.0 for the rest.
+    ///
+    /// # Arguments
+    /// * `targets: &[f64]` - multiclass targets
+    /// * `class: usize` - index of the class in [`classes`][`OneVsRest::classes`]
+    pub fn binary_targets(&self, targets: &[f64], class: usize) -> Vec<f64> {
+        let label = self.classes[class];
+        return targets.iter().map(|&target| if target == label { 1.0 } else { 0.0 }).collect();
+    }
+
+    /// Returns dataset of binary problem of given class, with the same features and binary targets.
+    ///
+    /// # Arguments
+    /// * `dataset: &Dataset` - multiclass dataset
+    /// * `class: usize` - index of the class in [`classes`][`OneVsRest::classes`]
+    pub fn binary_dataset(&self, dataset: &Dataset, class: usize) -> Dataset {
+        return Dataset::new(
+            dataset.feature_names().clone(), dataset.target_name().clone(),
+            dataset.features().clone(), self.binary_targets(dataset.targets(), class)
+        );
+    }
+
+    /// Maps scores of per-class trees to class labels.
+    ///
+    /// Each sample is assigned the class with the highest score, NaN scores never win and ties go
+    /// to the class listed first, as does a sample with all scores NaN.
+    ///
+    /// # Arguments
+    /// * `scores: &[Vec<f64>]` - scores of each class tree, in the order of
+    ///   [`classes`][`OneVsRest::classes`], all of the same length
+    ///
+    /// # Returns
+    /// * `Vec<f64>` - class label of every sample
+    pub fn labels(&self, scores: &[Vec<f64>]) -> Vec<f64> {
+        let samples = scores.first().map_or(0, |column| column.len());
+        return (0..samples).map(|sample| {
+            let mut best = 0;
+            for class in 1..scores.len() {
+                let (score, highest) = (scores[class][sample], scores[best][sample]);
+                if !score.is_nan() && (score > highest || highest.is_nan()) { best = class; }
+            }
+            return self.classes[best];
+        }).collect();
+    }
+}
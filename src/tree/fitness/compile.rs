@@ -0,0 +1,412 @@
+//! Compilation of trees into stack-machine bytecode.
+//!
+//! This module provides [`Program`], a [`TreeGenotype`][`crate::tree::core::tree::TreeGenotype`]
+//! compiled by [`Compiler`] with functions of its operator set into a flat sequence of
+//! [`Instruction`]s, and [`Workspace`] holding preallocated buffers reused between executions.
+//! Functions of [`symbolic`][`crate::operators::functions::symbolic`] set are executed by native
+//! kernels in place, so repeated evaluation of a program does not hash names or allocate. Other
+//! functions are called through their [`VectorFunction`][`crate::common::types::VectorFunction`].
+//!
+//! [`Compiled`] wraps any [`Loss`][`crate::tree::fitness::evaluate::Loss`] into an evaluator
+//! running trees this way, so optimizers get the speed-up as well.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result};
+
+use crate::common::traits::{Data, Evaluator};
+use crate::common::types::VectorFunction;
+use crate::dataset::core::Dataset;
+use crate::operators::functions::symbolic::scalar;
+use crate::operators::set::Operators;
+use crate::tree::core::node::{intern, Node};
+use crate::tree::core::tree::TreeGenotype;
+use crate::tree::fitness::evaluate::Loss;
+
+/// Errors that can occur during compilation.
+///
+/// # Variants
+/// * `EmptyTree` - tree contains no nodes
+/// * `UnknownFunction(String)` - function is missing from the operator set
+/// * `MalformedTree(usize)` - children map entry of node with given index does not match its
+///   arity, refers to node outside the arena or makes the tree cyclic
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompileError {
+    EmptyTree,
+    UnknownFunction(String),
+    MalformedTree(usize)
+}
+
+impl std::error::Error for CompileError {}
+
+impl Display for CompileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            CompileError::EmptyTree => write!(f, "Cannot compile empty tree!"),
+            CompileError::UnknownFunction(name) => write!(f, "Unknown function: {}", name),
+            CompileError::MalformedTree(index) => write!(f, "Malformed tree at node {}", index),
+        }
+    }
+}
+
+/// Single instruction of a [`Program`].
+///
+/// # Variants
+/// * `Variable(usize)` - pushes feature column with given index
+/// * `Constant(f64)` - pushes column filled with constant
+/// * `Add`, `Sub`, `Mul`, `Div` - replace two topmost columns with result of native binary kernel
+/// * `Sin`, `Cos`, `Ln` - replace topmost column with result of native unary kernel
+/// * `Features(VectorFunction)` - pushes result of function applied to the whole feature set
+/// * `Call(VectorFunction, usize)` - replaces given number of topmost columns with result of
+///   function
+#[derive(Debug, Clone, Copy)]
+pub enum Instruction {
+    Variable(usize),
+    Constant(f64),
+    Add, Sub, Mul, Div,
+    Sin, Cos, Ln,
+    Features(VectorFunction),
+    Call(VectorFunction, usize)
+}
+
+/// Scalar kernels of native instructions.
+type UnaryKernel = fn(f64) -> f64;
+type BinaryKernel = fn(f64, f64) -> f64;
+
+impl Instruction {
+    /// Returns native instruction of function registered under given
+    /// [`registry`][`crate::operators::registry`] key, if there is one.
+    ///
+    /// Only keys of built-in [`symbolic`][`crate::operators::functions::symbolic`] functions are
+    /// native, the registry never lets another function take them.
+    fn native(key: &str, arity: usize) -> Option<Self> {
+        return match (key, arity) {
+            ("add", 2) => Some(Instruction::Add),
+            ("sub", 2) => Some(Instruction::Sub),
+            ("mul", 2) => Some(Instruction::Mul),
+            ("protected_div", 2) => Some(Instruction::Div),
+            ("sin", 1) => Some(Instruction::Sin),
+            ("cos", 1) => Some(Instruction::Cos),
+            ("protected_ln", 1) => Some(Instruction::Ln),
+            _ => None,
+        };
+    }
+}
+
+/// Resolved operator set used to compile many trees.
+///
+/// Every function of the set is resolved once into its [`Instruction`], so compiling a tree does
+/// not hash function names. Functions added by registry key (see
+/// [`OperatorsBuilder::add_registered`][`crate::operators::builder::OperatorsBuilder::add_registered`])
+/// of a built-in operator become native instructions, all other functions are called.
+///
+/// Compiler is built from the [`Operators`][`crate::operators::set::Operators`] rather than from
+/// the function map used by evaluators: the map holds only function pointers, while native
+/// instructions are chosen by registry key, which only the operator set carries.
+///
+/// # Fields
+/// * `functions: Vec<Option<Instruction>>` - instructions indexed by interned function id, see
+///   [`intern`][`crate::tree::core::node::intern`]
+#[derive(Debug, Clone)]
+pub struct Compiler {
+    functions: Vec<Option<Instruction>>
+}
+
+impl Compiler {
+    /// Creates new compiler for operator set.
+    ///
+    /// # Arguments
+    /// * `operators: &Operators` - [`operator set`][`crate::operators::set::Operators`] defining
+    ///   functions of compiled trees
+    pub fn new(operators: &Operators) -> Self {
+        let mut functions = Vec::new();
+        for (name, functor) in operators.functions() {
+            let id = intern(name);
+            let (arity, function) = (functor.arity(), *functor.func());
+            if id >= functions.len() { functions.resize(id + 1, None); }
+            functions[id] = Some(match functor.key().and_then(|key| Instruction::native(key, arity)) {
+                Some(native) => native,
+                None if arity == 0 => Instruction::Features(function),
+                None => Instruction::Call(function, arity),
+            });
+        }
+        return Self { functions };
+    }
+
+    /// Returns instruction executing the node, `None` for functions missing from the set.
+    pub fn instruction(&self, node: &Node) -> Option<Instruction> {
+        return match *node {
+            Node::Variable(column) => Some(Instruction::Variable(column)),
+            Node::Constant(value) => Some(Instruction::Constant(value)),
+            Node::Function(id, _) => self.functions.get(id).copied().flatten(),
+        };
+    }
+
+    /// Compiles tree into [`Program`].
+    ///
+    /// # Arguments
+    /// * `tree: &TreeGenotype` - [`TreeGenotype`][`crate::tree::core::tree::TreeGenotype`] to
+    ///   compile
+    ///
+    /// # Returns
+    /// * `Result<Program, CompileError>` - compiled program or [`error`][`CompileError`] if tree
+    ///   is empty, malformed or uses function missing from the operator set
+    pub fn compile(&self, tree: &TreeGenotype) -> std::result::Result<Program, CompileError> {
+        let arena = tree.arena();
+        if arena.is_empty() { return Err(CompileError::EmptyTree); }
+
+        // Post-order traversal checking the children map on the way, trees are not validated
+        // beforehand (e.g. after deserialization) and a full validation would dominate compile time
+        let mut instructions = Vec::with_capacity(arena.len());
+        let (mut depth, mut stack_size, mut visited) = (0usize, 0usize, 0usize);
+        let mut pending = vec![(0, false)];
+        while let Some((index, expanded)) = pending.pop() {
+            let node = arena[index];
+            if expanded {
+                let instruction = self.instruction(&node).ok_or_else(|| CompileError::UnknownFunction(node.name()))?;
+                depth = depth.checked_sub(node.arity()).ok_or(CompileError::MalformedTree(index))? + 1;
+                stack_size = stack_size.max(depth);
+                instructions.push(instruction);
+                continue;
+            }
+
+            visited += 1;
+            let children = tree.children().get(&index).map_or(&[][..], Vec::as_slice);
+            if visited > arena.len() || children.len() != node.arity() { return Err(CompileError::MalformedTree(index)); }
+            if let Some(&child) = children.iter().find(|&&child| child >= arena.len()) {
+                return Err(CompileError::MalformedTree(child));
+            }
+            pending.push((index, true));
+            pending.extend(children.iter().rev().map(|&child| (child, false)));
+        }
+        return Ok(Program { instructions, stack_size });
+    }
+}
+
+/// Preallocated column buffers used by [`Program::execute`].
+///
+/// A single workspace can be reused across executions of different programs, buffers only grow
+/// when a program needs more of them or a dataset has more rows.
+#[derive(Debug, Default)]
+pub struct Workspace {
+    stack: Vec<Vec<f64>>
+}
+
+impl Workspace {
+    pub fn new() -> Self { return Self::default(); }
+
+    /// Ensures at least `size` buffers able to hold `rows` values each.
+    fn prepare(&mut self, size: usize, rows: usize) {
+        if self.stack.len() < size { self.stack.resize_with(size, Vec::new); }
+        for buffer in &mut self.stack {
+            buffer.reserve(rows.saturating_sub(buffer.len()));
+        }
+    }
+}
+
+/// Tree compiled into stack-machine bytecode in postfix order.
+///
+/// # Fields
+/// * `instructions: Vec<Instruction>` - [`instructions`][`Instruction`] executed in order
+/// * `stack_size: usize` - maximum number of columns on the stack during execution
+///
+/// # Examples
+/// ```
+/// use mycoforge::operators::builder::OperatorsBuilder;
+/// use mycoforge::tree::core::tree::TreeGenotype;
+/// use mycoforge::tree::fitness::compile::{Program, Workspace};
+///
+/// let operators = OperatorsBuilder::default()
+///     .add_registered("+", "add", 0.5).expect("Failed to add function!")
+///     .add_registered("sin", "sin", 0.5).expect("Failed to add function!")
+///     .add_variable("x", 1.0).expect("Failed to add variable!")
+///     .build().expect("Failed to build operators!");
+/// let tree = TreeGenotype::from_infix("x + sin(0)", &operators).expect("Failed to parse tree!");
+///
+/// let program = Program::compile(&tree, &operators).expect("Failed to compile tree!");
+/// let mut workspace = Workspace::new();
+///
+/// assert_eq!(program.stack_size(), 2);
+/// assert_eq!(program.execute(&[vec![1.0, 2.0]], &mut workspace), &[1.0, 2.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Program {
+    instructions: Vec<Instruction>,
+    stack_size: usize
+}
+
+impl Program {
+    /// Compiles tree using operator set, see [`Compiler`] for compiling many trees.
+    pub fn compile(tree: &TreeGenotype, operators: &Operators) -> std::result::Result<Self, CompileError> {
+        return Compiler::new(operators).compile(tree);
+    }
+
+    pub fn instructions(&self) -> &Vec<Instruction> { return &self.instructions; }
+    pub fn stack_size(&self) -> usize { return self.stack_size; }
+
+    /// Executes program on feature columns.
+    ///
+    /// # Arguments
+    /// * `features: &[Vec<f64>]` - feature columns indexed by variable index
+    /// * `workspace: &mut Workspace` - [`buffers`][`Workspace`] reused between executions
+    ///
+    /// # Returns
+    /// * `&[f64]` - predictions for every row, borrowed from the workspace
+    pub fn execute<'a>(&self, features: &[Vec<f64>], workspace: &'a mut Workspace) -> &'a [f64] {
+        return self.execute_rows(features, features.first().map_or(0, Vec::len), workspace);
+    }
+
+    /// Executes program on given number of rows, see [`Program::execute`]. Needed when there are
+    /// no feature columns to take the number of rows from.
+    pub fn execute_rows<'a>(&self, features: &[Vec<f64>], rows: usize, workspace: &'a mut Workspace) -> &'a [f64] {
+        workspace.prepare(self.stack_size, rows);
+        let stack = &mut workspace.stack;
+
+        let mut top = 0;
+        for instruction in &self.instructions {
+            match *instruction {
+                Instruction::Variable(column) => {
+                    stack[top].clear();
+                    stack[top].extend_from_slice(&features[column]);
+                    top += 1;
+                },
+                Instruction::Constant(value) => {
+                    stack[top].clear();
+                    stack[top].resize(rows, value);
+                    top += 1;
+                },
+                Instruction::Add => { top -= 1; binary(stack, top, scalar::add); },
+                Instruction::Sub => { top -= 1; binary(stack, top, scalar::sub); },
+                Instruction::Mul => { top -= 1; binary(stack, top, scalar::mul); },
+                Instruction::Div => { top -= 1; binary(stack, top, scalar::div); },
+                Instruction::Sin => unary(&mut stack[top - 1], scalar::sin),
+                Instruction::Cos => unary(&mut stack[top - 1], scalar::cos),
+                Instruction::Ln => unary(&mut stack[top - 1], scalar::ln),
+                Instruction::Features(function) => {
+                    let operands = features.iter().map(Vec::as_slice).collect::<Vec<&[f64]>>();
+                    stack[top] = function(&operands);
+                    top += 1;
+                },
+                Instruction::Call(function, arity) => {
+                    let operands = stack[top - arity..top].iter().map(Vec::as_slice).collect::<Vec<&[f64]>>();
+                    let result = function(&operands);
+                    top -= arity;
+                    stack[top] = result;
+                    top += 1;
+                }
+            }
+        }
+        return &workspace.stack[0];
+    }
+
+    /// Executes program with a fresh workspace, see [`Program::execute`].
+    pub fn evaluate(&self, features: &[Vec<f64>]) -> Vec<f64> {
+        return self.execute(features, &mut Workspace::new()).to_vec();
+    }
+}
+
+/// Applies kernel to columns `top - 1` and `top` in place, storing result in column `top - 1`.
+fn binary(stack: &mut [Vec<f64>], top: usize, kernel: BinaryKernel) {
+    let (lower, upper) = stack.split_at_mut(top);
+    let (left, right) = (&mut lower[top - 1], &upper[0]);
+    left.truncate(right.len());
+    left.iter_mut().zip(right.iter()).for_each(|(a, &b)| *a = kernel(*a, b));
+}
+
+fn unary(column: &mut [f64], kernel: UnaryKernel) {
+    column.iter_mut().for_each(|a| *a = kernel(*a));
+}
+
+thread_local! {
+    /// Buffers reused by [`Compiled`] evaluators on the current thread.
+    static WORKSPACE: RefCell<Workspace> = RefCell::new(Workspace::new());
+}
+
+/// Evaluator computing [`Loss`][`crate::tree::fitness::evaluate::Loss`] of trees compiled into
+/// [`Program`]s.
+///
+/// Gives the same fitness as the wrapped loss evaluating trees directly, but every tree is
+/// compiled by a [`Compiler`] resolved once from the operator set and executed in a
+/// [`Workspace`] reused by all evaluations on the same thread, so hot loops of optimizers neither
+/// hash function names nor allocate per node. Works with
+/// [`ParallelEvaluator`][`crate::tree::fitness::parallel::ParallelEvaluator`], every thread has
+/// its own workspace. Trees that fail to compile get NaN fitness.
+///
+/// # Fields
+/// * `loss: L` - [`Loss`][`crate::tree::fitness::evaluate::Loss`] reducing predictions into
+///   fitness
+/// * `compiler: Compiler` - [`compiler`][`Compiler`] of the operator set trees are built from
+///
+/// # Examples
+/// ```
+/// use mycoforge::common::traits::Evaluator;
+/// use mycoforge::dataset::core::Dataset;
+/// use mycoforge::operators::builder::OperatorsBuilder;
+/// use mycoforge::tree::core::tree::TreeGenotype;
+/// use mycoforge::tree::fitness::compile::Compiled;
+/// use mycoforge::tree::fitness::evaluate::MSE;
+///
+/// let operators = OperatorsBuilder::default()
+///     .add_registered("+", "add", 1.0).expect("Failed to add function!")
+///     .add_variable("x", 1.0).expect("Failed to add variable!")
+///     .build().expect("Failed to build operators!");
+/// let tree = TreeGenotype::from_infix("x + x", &operators).expect("Failed to parse tree!");
+/// let dataset = Dataset::new(vec!["x".to_string()], "y".to_string(), vec![vec![1.0, 2.0]], vec![2.0, 5.0]);
+///
+/// let evaluator = Compiled::new(MSE::new(), &operators);
+/// let map = operators.create_map();
+///
+/// assert_eq!(evaluator.evaluate(&tree, &dataset, &map), 0.5);
+/// assert_eq!(evaluator.evaluate(&tree, &dataset, &map), MSE::new().evaluate(&tree, &dataset, &map));
+/// ```
+pub struct Compiled<L: Loss> {
+    loss: L,
+    compiler: Compiler
+}
+
+impl<L: Loss> Compiled<L> {
+    /// Creates new compiled evaluator.
+    ///
+    /// # Arguments
+    /// * `loss: L` - [`Loss`][`crate::tree::fitness::evaluate::Loss`] reducing predictions into
+    ///   fitness
+    /// * `operators: &Operators` - [`operator set`][`crate::operators::set::Operators`] evaluated
+    ///   trees are built from
+    pub fn new(loss: L, operators: &Operators) -> Self {
+        return Self { loss, compiler: Compiler::new(operators) };
+    }
+
+    pub fn loss(&self) -> &L { return &self.loss; }
+    pub fn compiler(&self) -> &Compiler { return &self.compiler; }
+
+    /// Compiles and executes tree on features, passing predictions to `reduce`.
+    ///
+    /// # Returns
+    /// * `Option<T>` - reduced predictions, `None` if the tree does not compile
+    fn with_predictions<T>(&self, tree: &TreeGenotype, features: &[Vec<f64>], reduce: impl FnOnce(&[f64]) -> T) -> Option<T> {
+        let program = self.compiler.compile(tree).ok()?;
+        return Some(WORKSPACE.with(|workspace| reduce(program.execute(features, &mut workspace.borrow_mut()))));
+    }
+}
+
+impl<L: Loss> Evaluator<TreeGenotype> for Compiled<L> {
+    type D = Dataset;
+
+    fn evaluate(&self,
+            tree: &TreeGenotype, dataset: &Self::D,
+            _map: &HashMap<String, (usize, VectorFunction)>
+        ) -> f64 {
+        let (features, targets) = dataset.data();
+        return self.with_predictions(tree, features, |predictions| self.loss.loss(predictions, targets))
+            .unwrap_or(f64::NAN);
+    }
+
+    fn evaluate_cases(&self,
+            tree: &TreeGenotype, dataset: &Self::D,
+            _map: &HashMap<String, (usize, VectorFunction)>
+        ) -> (f64, Vec<f64>) {
+        let (features, targets) = dataset.data();
+        return self.with_predictions(tree, features, |predictions| {
+            return (self.loss.loss(predictions, targets), self.loss.case_errors(predictions, targets));
+        }).unwrap_or_else(|| (f64::NAN, vec![f64::NAN; targets.len()]));
+    }
+}
@@ -0,0 +1,74 @@
+//! Complexity objectives for multi-objective Genetic Programming.
+//!
+//! Evaluators in this module ignore data and measure the structure of a
+//! [`TreeGenotype`][`crate::tree::core::tree::TreeGenotype`], so that accuracy can be traded
+//! against model size with [`Objectives`][`crate::common::objectives::Objectives`].
+use std::collections::HashMap;
+
+use crate::common::traits::Evaluator;
+use crate::common::types::VectorFunction;
+use crate::dataset::core::Dataset;
+use crate::tree::core::tree::TreeGenotype;
+
+/// Size evaluator that computes fitness as the number of nodes of the tree.
+///
+/// # Examples
+/// ```
+/// use mycoforge::common::traits::Evaluator;
+/// use mycoforge::dataset::core::Dataset;
+/// use mycoforge::operators::builder::OperatorsBuilder;
+/// use mycoforge::tree::core::tree::TreeGenotype;
+/// use mycoforge::tree::fitness::complexity::{Depth, Size};
+///
+/// let operators = OperatorsBuilder::default()
+///     .add_registered("+", "add", 1.0).expect("Failed to add function!")
+///     .add_variable("x", 1.0).expect("Failed to add variable!")
+///     .build().expect("Failed to build operators!");
+/// let tree = TreeGenotype::from_infix("x + (x + x)", &operators).expect("Failed to parse tree!");
+/// let dataset = Dataset::new(vec!["x".to_string()], "y".to_string(), vec![vec![1.0]], vec![3.0]);
+///
+/// assert_eq!(Size::new().evaluate(&tree, &dataset, &operators.create_map()), 5.0);
+/// assert_eq!(Depth::new().evaluate(&tree, &dataset, &operators.create_map()), 2.0);
+/// ```
+pub struct Size {}
+
+impl Size {
+    pub fn new() -> Self { return Self {}; }
+}
+
+impl Default for Size {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl Evaluator<TreeGenotype> for Size {
+    type D = Dataset;
+
+    fn evaluate(&self,
+            tree: &TreeGenotype, _dataset: &Self::D,
+            _map: &HashMap<String, (usize, VectorFunction)>
+        ) -> f64 {
+        return tree.arena().len() as f64;
+    }
+}
+
+/// Depth evaluator that computes fitness as the depth of the tree, 0 for a single node.
+pub struct Depth {}
+
+impl Depth {
+    pub fn new() -> Self { return Self {}; }
+}
+
+impl Default for Depth {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl Evaluator<TreeGenotype> for Depth {
+    type D = Dataset;
+
+    fn evaluate(&self,
+            tree: &TreeGenotype, _dataset: &Self::D,
+            _map: &HashMap<String, (usize, VectorFunction)>
+        ) -> f64 {
+        return tree.depth() as f64;
+    }
+}
@@ -3,14 +3,31 @@
 //! This module provides evaluator for GP algorithms designed for assessing 
 //! fitness of [`TreeGenotype`][`crate::tree::core::tree::TreeGenotype`]
 //!
-//! Currently implmeneted:
-//! - Mean Squared Error (MSE)
+//! All evaluators share a single prediction path, [`predict`], and differ only in the [`Loss`]
+//! reducing predictions and targets into fitness. Any type implementing [`Loss`] is an
+//! [`Evaluator`][`crate::common::traits::Evaluator`] of trees.
+//!
+//! Currently implemented:
+//! - Sum of Squared Errors (SSE), Mean Squared Error (MSE), Root Mean Squared Error (RMSE)
+//! - Mean Absolute Error (MAE), Mean Absolute Percentage Error (MAPE), Max Absolute Error
+//! - Coefficient of determination (R²), Normalized RMSE (NRMSE), Huber loss
+//!
+//! Any of them can be wrapped in [`LinearScaling`], which fits the optimal intercept and slope of
+//! predictions before computing the error, and in
+//! [`Compiled`][`crate::tree::fitness::compile::Compiled`], which evaluates trees compiled into
+//! bytecode instead of through [`predict`] and is considerably faster in optimizer runs.
+//!
+//! Besides the aggregated fitness, every [`Loss`] reports the error on each data row (case)
+//! through [`Loss::case_errors`], which case-based selection such as
+//! [`LexicaseSelection`][`crate::tree::operators::select::LexicaseSelection`] relies on.
 //!
 //! Also serves as a template for custom evaluation functions.
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result};
 
 use crate::common::traits::Data;
 use crate::common::types::VectorFunction;
+use crate::tree::core::node::{intern, Node};
 use crate::{common::traits::Evaluator, tree::core::tree::TreeGenotype};
 use crate::dataset::core::Dataset;
 
@@ -19,34 +36,186 @@ fn common_evaluate(
     stack: &mut  Vec<Vec<f64>>, tree: &TreeGenotype,
     dataset: &[Vec<f64>], map: &HashMap<String, (usize, VectorFunction)>
 ) {
-    for i in (0..tree.arena().len()).rev() {
-        let node = &tree.arena()[i];
-
-        if let Some((arity, op)) = map.get(node) {
-            match arity {
-                0 => {
-                    let operands = dataset.iter().map(|v| v.as_slice()).collect::<Vec<&[f64]>>();
-                    let result = op(&operands);
-                    stack.push(result);
-                },
-                n => {
-                    let mut operands = Vec::new();
-                    for _ in 0..*n {
-                        operands.push(stack.pop().unwrap());
+    let functions = resolve(map);
+    let rows = dataset.first().map_or(0, |column| column.len());
+
+    for node in tree.arena().iter().rev() {
+        match node {
+            Node::Function(id, _) => {
+                if let Some(Some((arity, op))) = functions.get(*id) {
+                    match arity {
+                        0 => {
+                            let operands = dataset.iter().map(|v| v.as_slice()).collect::<Vec<&[f64]>>();
+                            let result = op(&operands);
+                            stack.push(result);
+                        },
+                        n => {
+                            let mut operands = Vec::new();
+                            for _ in 0..*n {
+                                operands.push(stack.pop().unwrap());
+                            }
+                            let operands = operands.iter().map(|v| v.as_slice()).collect::<Vec<&[f64]>>();
+                            let result = op(&operands);
+                            stack.push(result);
+                        },
                     }
-                    let operands = operands.iter().map(|v| v.as_slice()).collect::<Vec<&[f64]>>();
-                    let result = op(&operands);
-                    stack.push(result);
-                },
-            }
+                }
+            },
+            Node::Variable(index) => stack.push(dataset[*index].clone()),
+            Node::Constant(value) => stack.push(vec![*value; rows]),
+        }
+    }
+}
+
+/// Resolves function map into a table indexed by interned function ids, so evaluation does not
+/// hash strings for every node.
+fn resolve(map: &HashMap<String, (usize, VectorFunction)>) -> Vec<Option<(usize, VectorFunction)>> {
+    let mut functions = Vec::new();
+    for (name, &entry) in map {
+        let id = intern(name);
+        if id >= functions.len() { functions.resize(id + 1, None); }
+        functions[id] = Some(entry);
+    }
+    return functions;
+}
+
+/// Errors that can occur when creating evaluators.
+///
+/// # Variants
+/// * `InvalidDelta(f64)` - Huber loss threshold is not positive and finite
+/// * `InvalidThreshold(f64)` - classification probability threshold outside (0.0, 1.0) range
+/// * `InvalidClass(f64)` - class label is not finite
+/// * `TooFewClasses(usize)` - classification needs at least two distinct classes
+/// * `InvalidParsimonyCoefficient(f64)` - parsimony coefficient is negative or not finite
+/// * `InvalidTarpeianRate(f64)` - Tarpeian rate outside [0.0, 1.0] range
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvaluatorError {
+    InvalidDelta(f64),
+    InvalidThreshold(f64),
+    InvalidClass(f64),
+    TooFewClasses(usize),
+    InvalidParsimonyCoefficient(f64),
+    InvalidTarpeianRate(f64)
+}
+
+impl std::error::Error for EvaluatorError {}
+
+impl Display for EvaluatorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            EvaluatorError::InvalidDelta(delta) => write!(f, "Invalid Huber loss delta: {}", delta),
+            EvaluatorError::InvalidThreshold(threshold) => write!(f, "Invalid classification threshold: {}", threshold),
+            EvaluatorError::InvalidClass(class) => write!(f, "Invalid class label: {}", class),
+            EvaluatorError::TooFewClasses(count) => write!(f, "Expected at least 2 classes, found {}", count),
+            EvaluatorError::InvalidParsimonyCoefficient(coefficient) => write!(f, "Invalid parsimony coefficient: {}", coefficient),
+            EvaluatorError::InvalidTarpeianRate(rate) => write!(f, "Invalid Tarpeian rate: {}", rate),
         }
     }
 }
 
-//Sum of Square Errors (SSE)
-//Mean Squared Error (MSE) - most popular
-//Root Mean Squared Error (RMSE)
-//Mean Absolute Error (MAE)
+/// Computes predictions of tree for every row of feature set.
+///
+/// # Arguments
+/// * `tree: &TreeGenotype` - tree to evaluate
+/// * `features: &[Vec<f64>]` - feature columns
+/// * `map: &HashMap<String, (usize, VectorFunction)>` - mapping of function names to their
+///   implementations
+///
+/// # Returns
+/// * `Vec<f64>` - one prediction per row
+pub fn predict(
+    tree: &TreeGenotype, features: &[Vec<f64>],
+    map: &HashMap<String, (usize, VectorFunction)>
+) -> Vec<f64> {
+    let mut stack: Vec<Vec<f64>> = Vec::new();
+    common_evaluate(&mut stack, tree, features, map);
+    return stack.pop().unwrap();
+}
+
+/// Reduces predictions and targets into a single fitness value, lower is better.
+///
+/// Every type implementing `Loss` evaluates trees through [`predict`], so a custom metric only
+/// has to define the reduction. Per-case errors default to absolute differences of predictions
+/// and targets, metrics with a different notion of error on a single row override
+/// [`case_errors`][`Loss::case_errors`].
+///
+/// # Arguments
+/// * `predictions: &[f64]` - predicted values
+/// * `targets: &[f64]` - actual values, same length as `predictions`
+///
+/// # Returns
+/// * `f64` - fitness value
+/// * `Vec<f64>` - (`case_errors` only) error on every row, lower is better
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::evaluate::Loss;
+///
+/// struct MedianError;
+///
+/// impl Loss for MedianError {
+///     fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+///         let mut errors = predictions.iter().zip(targets).map(|(p, y)| (p - y).abs()).collect::<Vec<f64>>();
+///         errors.sort_by(|a, b| a.total_cmp(b));
+///         return errors[errors.len() / 2];
+///     }
+/// }
+///
+/// assert_eq!(MedianError.loss(&[1.0, 2.0, 6.0], &[1.0, 1.0, 1.0]), 1.0);
+/// assert_eq!(MedianError.case_errors(&[1.0, 2.0, 6.0], &[1.0, 1.0, 1.0]), vec![0.0, 1.0, 5.0]);
+/// ```
+pub trait Loss {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64;
+
+    fn case_errors(&self, predictions: &[f64], targets: &[f64]) -> Vec<f64> {
+        return predictions.iter().zip(targets.iter()).map(|(t, y)| (t - y).abs()).collect();
+    }
+}
+
+impl<L: Loss> Evaluator<TreeGenotype> for L {
+    type D = Dataset;
+
+    fn evaluate(&self,
+            tree: &TreeGenotype, dataset: &Self::D,
+            map: &HashMap<String, (usize, VectorFunction)>
+        ) -> f64 {
+        let (features, targets) = dataset.data();
+        let predictions = predict(tree, features, map);
+        return self.loss(&predictions, targets);
+    }
+
+    fn evaluate_cases(&self,
+            tree: &TreeGenotype, dataset: &Self::D,
+            map: &HashMap<String, (usize, VectorFunction)>
+        ) -> (f64, Vec<f64>) {
+        let (features, targets) = dataset.data();
+        let predictions = predict(tree, features, map);
+        return (self.loss(&predictions, targets), self.case_errors(&predictions, targets));
+    }
+}
+
+fn sum_squared_errors(predictions: &[f64], targets: &[f64]) -> f64 {
+    return predictions.iter()
+        .zip(targets.iter())
+        .map(|(t, y)| {
+            let diff = t - y;
+            return diff.powi(2);
+        }).sum::<f64>();
+}
+
+fn mean_squared_error(predictions: &[f64], targets: &[f64]) -> f64 {
+    return sum_squared_errors(predictions, targets) / (targets.len() as f64);
+}
+
+fn mean(values: impl Iterator<Item = f64>, len: usize) -> f64 {
+    return values.sum::<f64>() / (len as f64);
+}
+
+/// Divides error by normalizer, zero normalizer yields zero for zero error and infinity otherwise.
+fn normalized(error: f64, normalizer: f64) -> f64 {
+    if normalizer == 0.0 { return if error == 0.0 { 0.0 } else { f64::INFINITY }; }
+    return error / normalizer;
+}
 
 /// Sum of Square Errors (SSE) evaluator that computes fitness as the sum of squared
 /// difference between predicted and actual values.
@@ -67,37 +236,9 @@ impl Default for SSE {
     fn default() -> Self { return Self::new(); }
 }
 
-impl Evaluator<TreeGenotype> for SSE {
-    type D = Dataset;
-
-    fn evaluate(&self, 
-            tree: &TreeGenotype, dataset: &Self::D, 
-            map: &HashMap<String, (usize, VectorFunction)>
-        ) -> f64 {
-        let mut stack: Vec<Vec<f64>> = Vec::new();
-        let (features, target) = dataset.data();
-
-        common_evaluate(&mut stack, tree, features, map);
-
-        let predictions = stack.pop().unwrap();
-        let result = predictions.iter()
-            .zip(target.iter())
-            .map(|(t,y )| {
-                let diff = t - y;
-                let sq = diff.powi(2);
-                return sq;
-            }).sum::<f64>();
-        return result;
-    }
-
-    fn memoized_evaluate(&self, 
-            tree: &TreeGenotype, data: &Self::D, 
-            map: &HashMap<String, (usize, fn(&[&[f64]])-> Vec<f64>)>,
-            cache: &HashMap<TreeGenotype, f64>
-        ) -> f64 {
-        if let Some(&value) = cache.get(tree) { return value; }
-
-        return self.evaluate(tree, data, map);
+impl Loss for SSE {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        return sum_squared_errors(predictions, targets);
     }
 }
 
@@ -121,37 +262,9 @@ impl Default for MSE {
     fn default() -> Self { return Self {}; }
 }
 
-impl Evaluator<TreeGenotype> for MSE {
-    type D = Dataset;
-
-    fn evaluate(&self, 
-            tree: &TreeGenotype, dataset: &Self::D, 
-            map: &HashMap<String, (usize, VectorFunction)>
-        ) -> f64 {
-        let mut stack: Vec<Vec<f64>> = Vec::new();
-        let (features, target) = dataset.data();
-
-        common_evaluate(&mut stack, tree, features, map);
-
-        let predictions = stack.pop().unwrap();
-        let result = predictions.iter()
-            .zip(target.iter())
-            .map(|(t,y )| {
-                let diff = t - y;
-                let sq = diff.powi(2);
-                return sq;
-            }).sum::<f64>();
-        return result / (target.len() as f64);
-    }
-
-    fn memoized_evaluate(&self, 
-            tree: &TreeGenotype, data: &Self::D, 
-            map: &HashMap<String, (usize, VectorFunction)>,
-            cache: &HashMap<TreeGenotype, f64>
-        ) -> f64 {
-        if let Some(&value) = cache.get(tree) { return value; }
-
-        return self.evaluate(tree, data, map);
+impl Loss for MSE {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        return mean_squared_error(predictions, targets);
     }
 }
 
@@ -175,36 +288,344 @@ impl Default for RMSE {
     fn default() -> Self { return Self {}; }
 }
 
-impl Evaluator<TreeGenotype> for RMSE {
-    type D = Dataset;
+impl Loss for RMSE {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        return mean_squared_error(predictions, targets).sqrt();
+    }
+}
 
-    fn evaluate(&self, 
-        tree: &TreeGenotype, dataset: &Self::D, 
-        map: &HashMap<String, (usize, VectorFunction)>
-    ) -> f64 {
-        let mut stack: Vec<Vec<f64>> = Vec::new();
-        let (features, target) = dataset.data();
-
-        common_evaluate(&mut stack, tree, features, map);
-
-        let predictions = stack.pop().unwrap();
-        let result = predictions.iter()
-            .zip(target.iter())
-            .map(|(t,y )| {
-                let diff = t - y;
-                let sq = diff.powi(2);
-                return sq;
-            }).sum::<f64>();
-        return (result / (target.len() as f64)).sqrt();
-    }
-
-    fn memoized_evaluate(&self, 
-            tree: &TreeGenotype, data: &Self::D, 
-            map: &HashMap<String, (usize, VectorFunction)>,
-            cache: &HashMap<TreeGenotype, f64>
-        ) -> f64 {
-        if let Some(&value) = cache.get(tree) { return value; }
+/// Mean Absolute Error (MAE) evaluator that computes fitness as average absolute difference
+/// between predicted and actual values.
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::evaluate::{Loss, MAE};
+///
+/// assert_eq!(MAE::new().loss(&[1.0, 4.0], &[2.0, 2.0]), 1.5);
+/// ```
+pub struct MAE {}
+
+impl MAE {
+    pub fn new() -> Self { return Self {}; }
+}
+
+impl Default for MAE {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl Loss for MAE {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        let errors = predictions.iter().zip(targets.iter()).map(|(t, y)| (t - y).abs());
+        return mean(errors, targets.len());
+    }
+}
+
+/// Coefficient of determination (R²) evaluator.
+///
+/// Fitness is `1 - R²`, i.e. sum of squared errors divided by total sum of squares of targets,
+/// so that it is minimized like every other fitness: perfect fit scores 0 and predicting the mean
+/// of targets scores 1. For constant targets imperfect predictions score 1.
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::evaluate::{Loss, R2};
+///
+/// let targets = [1.0, 2.0, 3.0];
+///
+/// assert_eq!(R2::new().loss(&targets, &targets), 0.0);
+/// assert_eq!(R2::new().loss(&[2.0, 2.0, 2.0], &targets), 1.0);
+/// ```
+pub struct R2 {}
+
+impl R2 {
+    pub fn new() -> Self { return Self {}; }
+}
+
+impl Default for R2 {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl Loss for R2 {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        let average = mean(targets.iter().copied(), targets.len());
+        let total = targets.iter().map(|y| (y - average).powi(2)).sum::<f64>();
+        let residual = sum_squared_errors(predictions, targets);
+        if total == 0.0 { return if residual == 0.0 { 0.0 } else { 1.0 }; }
+        return residual / total;
+    }
+}
+
+/// Quantity dividing RMSE in [`NRMSE`].
+///
+/// # Variants
+/// * `Range` - difference between maximum and minimum target
+/// * `StdDev` - population standard deviation of targets
+/// * `Mean` - absolute value of mean target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Normalization {
+    Range,
+    StdDev,
+    Mean
+}
+
+/// Normalized Root Mean Square Error (NRMSE) evaluator that computes fitness as RMSE divided by
+/// [`Normalization`] of targets, making it comparable between datasets of different scale.
+///
+/// If the normalizer is zero, perfect predictions score 0 and any other infinity.
+///
+/// # Fields
+/// * `normalization: Normalization` - quantity dividing RMSE, [`Normalization::Range`] by default
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::evaluate::{Loss, Normalization, NRMSE};
+///
+/// let evaluator = NRMSE::new(Normalization::Range);
+///
+/// assert_eq!(evaluator.loss(&[1.0, 5.0], &[0.0, 4.0]), 0.25);
+/// ```
+pub struct NRMSE {
+    normalization: Normalization
+}
+
+impl NRMSE {
+    pub fn new(normalization: Normalization) -> Self { return Self { normalization }; }
+
+    pub fn normalization(&self) -> Normalization { return self.normalization; }
+}
+
+impl Default for NRMSE {
+    fn default() -> Self { return Self::new(Normalization::Range); }
+}
+
+impl Loss for NRMSE {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        let rmse = mean_squared_error(predictions, targets).sqrt();
+        let average = mean(targets.iter().copied(), targets.len());
+        let normalizer = match self.normalization {
+            Normalization::Range => {
+                let max = targets.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                let min = targets.iter().copied().fold(f64::INFINITY, f64::min);
+                max - min
+            },
+            Normalization::StdDev => mean(targets.iter().map(|y| (y - average).powi(2)), targets.len()).sqrt(),
+            Normalization::Mean => average.abs()
+        };
+        return normalized(rmse, normalizer);
+    }
+}
+
+/// Mean Absolute Percentage Error (MAPE) evaluator that computes fitness as average absolute
+/// difference relative to actual values.
+///
+/// Result is a fraction, not a percentage: 0.1 means predictions are off by 10% on average.
+/// Targets are bounded away from zero by `f64::EPSILON`, so zero targets produce large but finite
+/// errors.
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::evaluate::{Loss, MAPE};
+///
+/// assert_eq!(MAPE::new().loss(&[1.5, 3.0], &[2.0, 4.0]), 0.25);
+/// ```
+pub struct MAPE {}
+
+impl MAPE {
+    pub fn new() -> Self { return Self {}; }
+}
+
+impl Default for MAPE {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl Loss for MAPE {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        return mean(self.case_errors(predictions, targets).into_iter(), targets.len());
+    }
+
+    fn case_errors(&self, predictions: &[f64], targets: &[f64]) -> Vec<f64> {
+        return predictions.iter()
+            .zip(targets.iter())
+            .map(|(t, y)| (t - y).abs() / y.abs().max(f64::EPSILON))
+            .collect();
+    }
+}
+
+/// Huber loss evaluator, quadratic for small errors and linear for large ones, which makes it
+/// less sensitive to outliers than MSE.
+///
+/// For error `e` the loss is `e² / 2` when `|e| <= delta` and `delta * (|e| - delta / 2)`
+/// otherwise, fitness is the average loss.
+///
+/// # Fields
+/// * `delta: f64` - threshold between quadratic and linear part, 1.0 by default
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::evaluate::{Huber, Loss};
+///
+/// let evaluator = Huber::new(1.0).expect("Failed to create evaluator!");
+///
+/// assert_eq!(evaluator.loss(&[0.5, 3.0], &[0.0, 0.0]), (0.125 + 2.5) / 2.0);
+/// assert!(Huber::new(0.0).is_err());
+/// ```
+pub struct Huber {
+    delta: f64
+}
+
+impl Huber {
+    /// Creates new Huber loss evaluator.
+    ///
+    /// # Arguments
+    /// * `delta: f64` - threshold between quadratic and linear part
+    ///
+    /// # Returns
+    /// * `Result<Self, EvaluatorError>` - new evaluator or error if `delta` is not positive and
+    ///   finite
+    pub fn new(delta: f64) -> std::result::Result<Self, EvaluatorError> {
+        if !(delta > 0.0 && delta.is_finite()) { return Err(EvaluatorError::InvalidDelta(delta)); }
+        return Ok(Self { delta });
+    }
+
+    pub fn delta(&self) -> f64 { return self.delta; }
+}
+
+impl Default for Huber {
+    fn default() -> Self { return Self { delta: 1.0 }; }
+}
+
+impl Loss for Huber {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        return mean(self.case_errors(predictions, targets).into_iter(), targets.len());
+    }
+
+    fn case_errors(&self, predictions: &[f64], targets: &[f64]) -> Vec<f64> {
+        return predictions.iter()
+            .zip(targets.iter())
+            .map(|(t, y)| {
+                let error = (t - y).abs();
+                if error <= self.delta { return 0.5 * error.powi(2); }
+                return self.delta * (error - 0.5 * self.delta);
+            }).collect();
+    }
+}
+
+/// Max Absolute Error evaluator that computes fitness as the largest absolute difference between
+/// predicted and actual values, NaN if any prediction is NaN.
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::evaluate::{Loss, MaxError};
+///
+/// assert_eq!(MaxError::new().loss(&[1.0, 4.0, 2.5], &[2.0, 2.0, 2.0]), 2.0);
+/// assert!(MaxError::new().loss(&[f64::NAN, 4.0], &[2.0, 2.0]).is_nan());
+/// ```
+pub struct MaxError {}
+
+impl MaxError {
+    pub fn new() -> Self { return Self {}; }
+}
+
+impl Default for MaxError {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl Loss for MaxError {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        return predictions.iter()
+            .zip(targets.iter())
+            .map(|(t, y)| (t - y).abs())
+            .fold(0.0, |max, error| if error > max || error.is_nan() { error } else { max });
+    }
+}
+
+/// Linear scaling wrapper that evaluates trees with optimal intercept and slope.
+///
+/// Predictions `p` of a tree are replaced by `a + b * p`, where intercept `a` and slope `b`
+/// minimize squared error against targets and are computed in closed form (Keijzer, 2003). Search
+/// then only has to find the shape of the target function, not its scale and offset. Coefficients
+/// of the final tree are available through [`coefficients`][`LinearScaling::coefficients`] and are
+/// stored by [`SymbolicModel::from_scaled_run`][`crate::tree::model::SymbolicModel::from_scaled_run`].
+///
+/// # Fields
+/// * `loss: L` - wrapped evaluator computing error of scaled predictions
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::evaluate::{LinearScaling, Loss, MSE};
+///
+/// let evaluator = LinearScaling::new(MSE::new());
+///
+/// // Targets are 3 + 2 * predictions
+/// assert_eq!(evaluator.loss(&[0.0, 1.0, 2.0], &[3.0, 5.0, 7.0]), 0.0);
+/// assert_eq!(LinearScaling::<MSE>::fit(&[0.0, 1.0, 2.0], &[3.0, 5.0, 7.0]), (3.0, 2.0));
+/// ```
+pub struct LinearScaling<L: Loss> {
+    loss: L
+}
+
+impl<L: Loss> LinearScaling<L> {
+    pub fn new(loss: L) -> Self { return Self { loss }; }
+
+    pub fn inner(&self) -> &L { return &self.loss; }
+
+    /// Computes least-squares intercept and slope mapping predictions onto targets.
+    ///
+    /// Constant predictions get slope 0 and the mean of targets as intercept. Non-finite
+    /// predictions yield NaN coefficients, so invalid trees keep invalid fitness.
+    ///
+    /// # Arguments
+    /// * `predictions: &[f64]` - predicted values
+    /// * `targets: &[f64]` - actual values
+    ///
+    /// # Returns
+    /// * `(f64, f64)` - intercept and slope
+    pub fn fit(predictions: &[f64], targets: &[f64]) -> (f64, f64) {
+        let prediction_mean = mean(predictions.iter().copied(), predictions.len());
+        let target_mean = mean(targets.iter().copied(), targets.len());
+        let (covariance, variance) = predictions.iter()
+            .zip(targets.iter())
+            .fold((0.0, 0.0), |(covariance, variance), (p, y)| {
+                let centered = p - prediction_mean;
+                return (covariance + centered * (y - target_mean), variance + centered * centered);
+            });
+        if variance == 0.0 { return (target_mean, 0.0); }
+        let slope = covariance / variance;
+        return (target_mean - slope * prediction_mean, slope);
+    }
+
+    /// Computes intercept and slope of tree on dataset.
+    ///
+    /// # Arguments
+    /// * `tree: &TreeGenotype` - evaluated tree, usually the best individual of a run
+    /// * `dataset: &Dataset` - dataset the coefficients are fitted on
+    /// * `map: &HashMap<String, (usize, VectorFunction)>` - mapping of function names to their
+    ///   implementations
+    ///
+    /// # Returns
+    /// * `(f64, f64)` - intercept and slope
+    pub fn coefficients(&self,
+            tree: &TreeGenotype, dataset: &Dataset,
+            map: &HashMap<String, (usize, VectorFunction)>
+        ) -> (f64, f64) {
+        let (features, targets) = dataset.data();
+        return Self::fit(&predict(tree, features, map), targets);
+    }
+}
+
+impl<L: Loss> LinearScaling<L> {
+    fn scaled(predictions: &[f64], targets: &[f64]) -> Vec<f64> {
+        let (intercept, slope) = Self::fit(predictions, targets);
+        return predictions.iter().map(|p| intercept + slope * p).collect();
+    }
+}
+
+impl<L: Loss> Loss for LinearScaling<L> {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 {
+        return self.loss.loss(&Self::scaled(predictions, targets), targets);
+    }
 
-        return self.evaluate(tree, data, map);
+    fn case_errors(&self, predictions: &[f64], targets: &[f64]) -> Vec<f64> {
+        return self.loss.case_errors(&Self::scaled(predictions, targets), targets);
     }
 }
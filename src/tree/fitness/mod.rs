@@ -2,5 +2,20 @@
 //!
 //! This module provides:
 //! - [`evaluate`] - Various fitness functions for evaluating tree performance
+//! - [`classify`] - Fitness functions for binary and multiclass classification
+//! - [`complexity`] - Structural objectives such as tree size
+//! - [`parsimony`] - Bloat control by penalizing fitness of large trees
+//! - [`parallel`] - Parallel evaluation of whole populations
+//! - [`compile`] - Compilation of trees into stack-machine bytecode
 
 pub mod evaluate;
+
+pub mod classify;
+
+pub mod complexity;
+
+pub mod parsimony;
+
+pub mod parallel;
+
+pub mod compile;
@@ -0,0 +1,122 @@
+//! Parallel population evaluation for Genetic Programming.
+//!
+//! This module provides [`ParallelEvaluator`] which wraps any
+//! [`Evaluator`][`crate::common::traits::Evaluator`] and evaluates whole populations across
+//! threads using rayon. Fitness values, and per-case errors when requested, are always returned in
+//! the order of input genotypes.
+
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+
+use crate::common::objectives::Direction;
+use crate::common::traits::{Evaluator, Genotype};
+use crate::common::types::VectorFunction;
+
+/// Evaluator wrapper that evaluates populations in parallel.
+///
+/// Each genotype is evaluated by the wrapped evaluator on a single thread, so fitness values do not
+/// depend on the number of threads. Genotypes are evaluated concurrently in no particular order,
+/// so evaluators with side effects must tolerate concurrent calls.
+///
+/// # Fields
+/// * `evaluator: E` - wrapped evaluator used for single genotypes
+/// * `pool: Option<ThreadPool>` - dedicated thread pool, global rayon pool if `None`
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::evaluate::MSE;
+/// use mycoforge::tree::fitness::parallel::ParallelEvaluator;
+///
+/// let evaluator = ParallelEvaluator::new(MSE::new(), Some(2))
+///     .expect("Failed to create thread pool!");
+///
+/// assert_eq!(evaluator.threads(), 2);
+/// ```
+pub struct ParallelEvaluator<E> {
+    evaluator: E,
+    pool: Option<ThreadPool>
+}
+
+impl<E> ParallelEvaluator<E> {
+    /// Creates new ParallelEvaluator.
+    ///
+    /// # Arguments
+    /// * `evaluator: E` - evaluator used for single genotypes
+    /// * `threads: Option<usize>` - number of threads in dedicated pool, global pool if `None`
+    ///
+    /// # Returns
+    /// * `Result<Self, ThreadPoolBuildError>` - new evaluator or error if thread pool could not be
+    ///   created
+    pub fn new(evaluator: E, threads: Option<usize>) -> Result<Self, ThreadPoolBuildError> {
+        let pool = match threads {
+            Some(threads) => Some(ThreadPoolBuilder::new().num_threads(threads).build()?),
+            None => None
+        };
+        return Ok(Self { evaluator, pool });
+    }
+
+    pub fn evaluator(&self) -> &E { return &self.evaluator; }
+    /// Returns number of threads used for evaluation.
+    pub fn threads(&self) -> usize {
+        return self.pool.as_ref().map_or_else(rayon::current_num_threads, |pool| pool.current_num_threads());
+    }
+}
+
+impl<E> From<E> for ParallelEvaluator<E> {
+    fn from(evaluator: E) -> Self { return Self { evaluator, pool: None }; }
+}
+
+impl<G, E> Evaluator<G> for ParallelEvaluator<E>
+where
+    G: Genotype + Sync,
+    E: Evaluator<G> + Sync,
+    E::D: Sync,
+{
+    type D = E::D;
+
+    fn evaluate(&self, 
+        tree: &G, data: &Self::D, 
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> f64 {
+        return self.evaluator.evaluate(tree, data, map);
+    }
+
+    fn evaluate_population(&self,
+        trees: &[G], data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> Vec<f64> {
+        return self.map_population(trees, |tree| self.evaluator.evaluate(tree, data, map));
+    }
+
+    fn evaluate_cases(&self,
+        tree: &G, data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> (f64, Vec<f64>) {
+        return self.evaluator.evaluate_cases(tree, data, map);
+    }
+
+    fn evaluate_population_cases(&self,
+        trees: &[G], data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> Vec<(f64, Vec<f64>)> {
+        return self.map_population(trees, |tree| self.evaluator.evaluate_cases(tree, data, map));
+    }
+
+    fn direction(&self) -> Direction { return self.evaluator.direction(); }
+
+    fn population_dependent(&self) -> bool { return self.evaluator.population_dependent(); }
+}
+
+impl<E: Sync> ParallelEvaluator<E> {
+    /// Applies `evaluate` to every genotype in parallel, results are in the order of `trees`.
+    fn map_population<G: Sync, T: Send>(&self, trees: &[G], evaluate: impl Fn(&G) -> T + Sync) -> Vec<T> {
+        let run = || -> Vec<T> { return trees.par_iter().map(&evaluate).collect(); };
+
+        return match &self.pool {
+            Some(pool) => pool.install(run),
+            None => run()
+        };
+    }
+}
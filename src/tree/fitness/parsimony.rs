@@ -0,0 +1,333 @@
+//! Fitness-based bloat control for tree-based Genetic Programming.
+//!
+//! Evaluators in this module wrap any tree [`Evaluator`] and discourage growth of trees:
+//! - [`ParsimonyPressure`] - adds penalty proportional to tree size to fitness, with a constant
+//!   coefficient or one recomputed for every population by the covariant method of Poli and McPhee
+//! - [`Tarpeian`] - assigns the worst fitness to a random fraction of trees larger than the
+//!   population average, without evaluating them
+//!
+//! Covariant parsimony and Tarpeian control depend on the population evaluated together by
+//! [`Evaluator::evaluate_population`] (e.g. offspring of a generation), single genotypes evaluated
+//! by [`Evaluator::evaluate`] are not penalized by them, see
+//! [`Evaluator::population_dependent`]. Therefore:
+//! - they should wrap [`ParallelEvaluator`][`crate::tree::fitness::parallel::ParallelEvaluator`]
+//!   rather than be wrapped by it
+//! - [`SteadyStateEA`][`crate::optimizers::steady_state::SteadyStateEA`], evaluating one child
+//!   at a time, rejects them
+//! - they should not be combined with the fitness cache of [`EA`][`crate::optimizers::ga::EA`],
+//!   which passes only unseen genotypes to the evaluator
+//! - with replacements keeping parents ([`Elitism`][`crate::optimizers::replacement::Replacement::Elitism`],
+//!   [`MuPlusLambda`][`crate::optimizers::replacement::Replacement::MuPlusLambda`],
+//!   [`ReplaceWorst`][`crate::optimizers::replacement::Replacement::ReplaceWorst`]), parents keep
+//!   fitness penalized within their own generation and are compared with offspring penalized
+//!   within the current one
+//!
+//! [`EABuilder`][`crate::optimizers::ga::EABuilder`] warns about the last two combinations.
+//!
+//! Selection-based bloat control is provided by
+//! [`LexicographicParsimonyTournament`][`crate::tree::operators::select::LexicographicParsimonyTournament`]
+//! and [`DoubleTournament`][`crate::tree::operators::select::DoubleTournament`].
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::common::objectives::Direction;
+use crate::common::traits::Evaluator;
+use crate::common::types::VectorFunction;
+use crate::tree::core::tree::TreeGenotype;
+use crate::tree::fitness::evaluate::EvaluatorError;
+
+/// Size of tree used by bloat control, number of nodes.
+fn size(tree: &TreeGenotype) -> f64 { return tree.arena().len() as f64; }
+
+/// Computes covariant parsimony coefficient `Cov(size, fitness) / Var(size)`.
+///
+/// Subtracting `coefficient * size` from fitness removes its covariance with size, so that
+/// selection no longer favours larger trees and the expected average size stays constant. Pairs
+/// with non-finite fitness are ignored.
+///
+/// # Arguments
+/// * `sizes: &[f64]` - sizes of trees
+/// * `fitness: &[f64]` - fitness values in the order of `sizes`
+///
+/// # Returns
+/// * `f64` - coefficient, 0.0 if sizes of trees with finite fitness do not vary
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::fitness::parsimony::covariant_coefficient;
+///
+/// assert_eq!(covariant_coefficient(&[1.0, 3.0, 5.0], &[2.0, 6.0, 10.0]), 2.0);
+/// assert_eq!(covariant_coefficient(&[1.0, 3.0, 5.0, 7.0], &[2.0, 6.0, 10.0, f64::NAN]), 2.0);
+/// assert_eq!(covariant_coefficient(&[3.0, 3.0], &[1.0, 2.0]), 0.0);
+/// ```
+pub fn covariant_coefficient(sizes: &[f64], fitness: &[f64]) -> f64 {
+    let pairs = sizes.iter().zip(fitness).filter(|(_, f)| f.is_finite()).map(|(&s, &f)| (s, f)).collect::<Vec<(f64, f64)>>();
+    if pairs.len() < 2 { return 0.0; }
+
+    let count = pairs.len() as f64;
+    let mean_size = pairs.iter().map(|(s, _)| s).sum::<f64>() / count;
+    let mean_fitness = pairs.iter().map(|(_, f)| f).sum::<f64>() / count;
+    let covariance = pairs.iter().map(|(s, f)| (s - mean_size) * (f - mean_fitness)).sum::<f64>();
+    let variance = pairs.iter().map(|(s, _)| (s - mean_size).powi(2)).sum::<f64>();
+
+    if variance == 0.0 { return 0.0; }
+    return covariance / variance;
+}
+
+/// Parsimony coefficient of [`ParsimonyPressure`].
+///
+/// # Variants
+/// * `Constant(f64)` - fixed penalty per node, making fitness worse in the direction of the
+///   wrapped evaluator
+/// * `Covariant` - coefficient recomputed for every evaluated population by
+///   [`covariant_coefficient`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParsimonyCoefficient {
+    Constant(f64),
+    Covariant
+}
+
+/// Evaluator wrapper adding parametric parsimony pressure to fitness.
+///
+/// Fitness of a tree with `n` nodes is `f + c * n` when minimizing and `f - c * n` when
+/// maximizing for a constant coefficient `c`. Covariant coefficient computes fitness as
+/// `f - c * n` with `c` from [`covariant_coefficient`] of the evaluated population. Per-case
+/// errors are passed through unchanged.
+///
+/// # Fields
+/// * `evaluator: E` - wrapped evaluator computing raw fitness
+/// * `coefficient: ParsimonyCoefficient` - how penalty per node is determined
+///
+/// # Examples
+/// ```
+/// use mycoforge::common::traits::Evaluator;
+/// use mycoforge::dataset::core::Dataset;
+/// use mycoforge::operators::builder::OperatorsBuilder;
+/// use mycoforge::tree::core::tree::TreeGenotype;
+/// use mycoforge::tree::fitness::evaluate::MSE;
+/// use mycoforge::tree::fitness::parsimony::ParsimonyPressure;
+///
+/// let operators = OperatorsBuilder::default()
+///     .add_registered("+", "add", 1.0).expect("Failed to add function!")
+///     .add_variable("x", 1.0).expect("Failed to add variable!")
+///     .build().expect("Failed to build operators!");
+/// let tree = TreeGenotype::from_infix("x + x", &operators).expect("Failed to parse tree!");
+/// let dataset = Dataset::new(vec!["x".to_string()], "y".to_string(), vec![vec![1.0]], vec![3.0]);
+///
+/// let evaluator = ParsimonyPressure::new(MSE::new(), 0.5).expect("Failed to create evaluator!");
+///
+/// assert_eq!(evaluator.evaluate(&tree, &dataset, &operators.create_map()), 1.0 + 0.5 * 3.0);
+/// assert!(ParsimonyPressure::new(MSE::new(), -1.0).is_err());
+/// ```
+pub struct ParsimonyPressure<E> {
+    evaluator: E,
+    coefficient: ParsimonyCoefficient
+}
+
+impl<E> ParsimonyPressure<E> {
+    /// Creates new ParsimonyPressure with constant coefficient.
+    ///
+    /// # Arguments
+    /// * `evaluator: E` - evaluator computing raw fitness
+    /// * `coefficient: f64` - penalty per node, non-negative and finite
+    ///
+    /// # Returns
+    /// * `Result<Self, EvaluatorError>` - new evaluator or error if coefficient is invalid
+    pub fn new(evaluator: E, coefficient: f64) -> Result<Self, EvaluatorError> {
+        if !coefficient.is_finite() || coefficient < 0.0 {
+            return Err(EvaluatorError::InvalidParsimonyCoefficient(coefficient));
+        }
+        return Ok(Self { evaluator, coefficient: ParsimonyCoefficient::Constant(coefficient) });
+    }
+
+    /// Creates new ParsimonyPressure with covariant coefficient.
+    pub fn covariant(evaluator: E) -> Self {
+        return Self { evaluator, coefficient: ParsimonyCoefficient::Covariant };
+    }
+
+    pub fn evaluator(&self) -> &E { return &self.evaluator; }
+    pub fn coefficient(&self) -> ParsimonyCoefficient { return self.coefficient; }
+}
+
+impl<E: Evaluator<TreeGenotype>> ParsimonyPressure<E> {
+    /// Penalty per node added to fitness of the given population.
+    fn slope(&self, sizes: &[f64], fitness: &[f64]) -> f64 {
+        return match (self.coefficient, self.evaluator.direction()) {
+            (ParsimonyCoefficient::Constant(c), Direction::Minimize) => c,
+            (ParsimonyCoefficient::Constant(c), Direction::Maximize) => -c,
+            (ParsimonyCoefficient::Covariant, _) => -covariant_coefficient(sizes, fitness),
+        };
+    }
+
+    fn penalize(&self, trees: &[TreeGenotype], fitness: Vec<f64>) -> Vec<f64> {
+        let sizes = trees.iter().map(size).collect::<Vec<f64>>();
+        let slope = self.slope(&sizes, &fitness);
+        return fitness.iter().zip(&sizes).map(|(f, s)| f + slope * s).collect();
+    }
+}
+
+impl<E: Evaluator<TreeGenotype>> Evaluator<TreeGenotype> for ParsimonyPressure<E> {
+    type D = E::D;
+
+    fn evaluate(&self,
+        tree: &TreeGenotype, data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> f64 {
+        return self.penalize(std::slice::from_ref(tree), vec![self.evaluator.evaluate(tree, data, map)])[0];
+    }
+
+    fn evaluate_population(&self,
+        trees: &[TreeGenotype], data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> Vec<f64> {
+        return self.penalize(trees, self.evaluator.evaluate_population(trees, data, map));
+    }
+
+    fn evaluate_cases(&self,
+        tree: &TreeGenotype, data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> (f64, Vec<f64>) {
+        let (fitness, cases) = self.evaluator.evaluate_cases(tree, data, map);
+        return (self.penalize(std::slice::from_ref(tree), vec![fitness])[0], cases);
+    }
+
+    fn evaluate_population_cases(&self,
+        trees: &[TreeGenotype], data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> Vec<(f64, Vec<f64>)> {
+        let (fitness, cases): (Vec<f64>, Vec<Vec<f64>>) = self.evaluator.evaluate_population_cases(trees, data, map).into_iter().unzip();
+        return self.penalize(trees, fitness).into_iter().zip(cases).collect();
+    }
+
+    fn direction(&self) -> Direction { return self.evaluator.direction(); }
+
+    fn population_dependent(&self) -> bool {
+        return self.coefficient == ParsimonyCoefficient::Covariant || self.evaluator.population_dependent();
+    }
+}
+
+/// Evaluator wrapper implementing Tarpeian bloat control.
+///
+/// Every tree larger than the average size of the evaluated population is, with probability
+/// `rate`, given the worst fitness ([`Direction::worst`]) without being evaluated, which also saves
+/// evaluations spent on bloated trees. Its per-case errors are all infinite.
+///
+/// # Fields
+/// * `evaluator: E` - wrapped evaluator computing fitness of remaining trees
+/// * `rate: f64` - probability of discarding a tree of above-average size
+/// * `rng: Mutex<ChaCha8Rng>` - random number generator deciding which trees are discarded
+///
+/// # Examples
+/// ```
+/// use mycoforge::common::traits::Evaluator;
+/// use mycoforge::dataset::core::Dataset;
+/// use mycoforge::operators::builder::OperatorsBuilder;
+/// use mycoforge::tree::core::tree::TreeGenotype;
+/// use mycoforge::tree::fitness::evaluate::MSE;
+/// use mycoforge::tree::fitness::parsimony::Tarpeian;
+///
+/// let operators = OperatorsBuilder::default()
+///     .add_registered("+", "add", 1.0).expect("Failed to add function!")
+///     .add_variable("x", 1.0).expect("Failed to add variable!")
+///     .build().expect("Failed to build operators!");
+/// let trees = ["x", "x + x", "x + x + x"].iter()
+///     .map(|infix| TreeGenotype::from_infix(infix, &operators).expect("Failed to parse tree!"))
+///     .collect::<Vec<TreeGenotype>>();
+/// let dataset = Dataset::new(vec!["x".to_string()], "y".to_string(), vec![vec![1.0]], vec![1.0]);
+///
+/// let evaluator = Tarpeian::new(MSE::new(), 1.0, 42).expect("Failed to create evaluator!");
+///
+/// // Only the largest tree is above the average size of 3 nodes
+/// assert_eq!(evaluator.evaluate_population(&trees, &dataset, &operators.create_map()), vec![0.0, 1.0, f64::INFINITY]);
+/// assert!(Tarpeian::new(MSE::new(), 1.5, 42).is_err());
+/// ```
+pub struct Tarpeian<E> {
+    evaluator: E,
+    rate: f64,
+    rng: Mutex<ChaCha8Rng>
+}
+
+impl<E> Tarpeian<E> {
+    /// Creates new Tarpeian evaluator.
+    ///
+    /// # Arguments
+    /// * `evaluator: E` - evaluator computing fitness of trees that are not discarded
+    /// * `rate: f64` - probability of discarding a tree of above-average size, in [0.0, 1.0]
+    /// * `seed: u64` - seed of the random number generator deciding which trees are discarded
+    ///
+    /// # Returns
+    /// * `Result<Self, EvaluatorError>` - new evaluator or error if rate is invalid
+    pub fn new(evaluator: E, rate: f64, seed: u64) -> Result<Self, EvaluatorError> {
+        if !(0.0..=1.0).contains(&rate) { return Err(EvaluatorError::InvalidTarpeianRate(rate)); }
+        return Ok(Self { evaluator, rate, rng: Mutex::new(ChaCha8Rng::seed_from_u64(seed)) });
+    }
+
+    pub fn evaluator(&self) -> &E { return &self.evaluator; }
+    pub fn rate(&self) -> f64 { return self.rate; }
+
+    /// Decides which trees are discarded, the smallest tree is never above average.
+    fn discarded(&self, trees: &[TreeGenotype]) -> Vec<bool> {
+        let average = trees.iter().map(size).sum::<f64>() / (trees.len().max(1) as f64);
+        let mut rng = self.rng.lock().expect("Tarpeian random number generator lock should not be poisoned!");
+        return trees.iter().map(|tree| size(tree) > average && rng.random_bool(self.rate)).collect();
+    }
+
+    /// Evaluates remaining trees with `evaluate` and fills results of discarded ones with `worst`.
+    fn evaluate_remaining<T: Clone>(
+        &self, trees: &[TreeGenotype],
+        evaluate: impl FnOnce(&[TreeGenotype]) -> Vec<T>, worst: impl Fn(&[T]) -> T
+    ) -> Vec<T> {
+        let discarded = self.discarded(trees);
+        let remaining = trees.iter().zip(&discarded).filter(|(_, &d)| !d).map(|(tree, _)| tree.clone()).collect::<Vec<TreeGenotype>>();
+        let evaluated = evaluate(&remaining);
+        let worst = worst(&evaluated);
+
+        let mut evaluated = evaluated.into_iter();
+        return discarded.iter().map(|&d| if d { worst.clone() } else { evaluated.next().expect("Missing fitness of remaining tree!") }).collect();
+    }
+}
+
+impl<E: Evaluator<TreeGenotype>> Evaluator<TreeGenotype> for Tarpeian<E> {
+    type D = E::D;
+
+    fn evaluate(&self,
+        tree: &TreeGenotype, data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> f64 {
+        return self.evaluator.evaluate(tree, data, map);
+    }
+
+    fn evaluate_population(&self,
+        trees: &[TreeGenotype], data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> Vec<f64> {
+        let worst = self.direction().worst();
+        return self.evaluate_remaining(trees, |remaining| self.evaluator.evaluate_population(remaining, data, map), |_| worst);
+    }
+
+    fn evaluate_cases(&self,
+        tree: &TreeGenotype, data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> (f64, Vec<f64>) {
+        return self.evaluator.evaluate_cases(tree, data, map);
+    }
+
+    fn evaluate_population_cases(&self,
+        trees: &[TreeGenotype], data: &Self::D,
+        map: &HashMap<String, (usize, VectorFunction)>
+    ) -> Vec<(f64, Vec<f64>)> {
+        let worst = self.direction().worst();
+        return self.evaluate_remaining(
+            trees,
+            |remaining| self.evaluator.evaluate_population_cases(remaining, data, map),
+            |evaluated| (worst, vec![f64::INFINITY; evaluated.first().map_or(0, |(_, cases)| cases.len())])
+        );
+    }
+
+    fn direction(&self) -> Direction { return self.evaluator.direction(); }
+
+    fn population_dependent(&self) -> bool { return true; }
+}
@@ -4,6 +4,7 @@
 //! - [`core`] - Core tree structures and individuals
 //! - [`operators`] - Tree-specific evolutionary operators
 //! - [`fitness`] - Fitness evaluation functions for trees
+//! - [`model`] - Trained symbolic models for prediction and persistence
 
 pub mod core;
 
@@ -11,6 +12,8 @@ pub mod operators;
 
 pub mod fitness;
 
+pub mod model;
+
 pub mod components {
     pub use super::core::tree::TreeGenotype;
     pub use super::core::individual::TreeIndividual;
@@ -18,7 +21,10 @@ pub mod components {
         init::*,
         mutation::*,
         crossover::*,
-        select::*
+        select::*,
+        simplify::*
     };
     pub use super::fitness::evaluate::*;
+    pub use super::fitness::parallel::*;
+    pub use super::fitness::compile::*;
 }
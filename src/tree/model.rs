@@ -0,0 +1,483 @@
+//! Persistent symbolic models.
+//!
+//! This module provides [`SymbolicModel`], an artifact bundling an evolved
+//! [`TreeGenotype`][`crate::tree::core::tree::TreeGenotype`] with everything needed to use it
+//! later: the operator set, feature and target names, fitness, training metadata and, for runs
+//! evaluated with [`LinearScaling`][`crate::tree::fitness::evaluate::LinearScaling`], the fitted
+//! intercept and slope. With the `serde` feature, models are saved and loaded as JSON or compact
+//! binary (CBOR). Operator sets must be built from [`registry`][`crate::operators::registry`]
+//! functions to be saved. [`OneVsRestModel`] combines one model per class into a multiclass
+//! classifier.
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Serialize, Deserialize};
+
+use crate::common::traits::{Data, Individual};
+use crate::common::types::VectorFunction;
+use crate::dataset::core::Dataset;
+use crate::operators::set::Operators;
+use crate::optimizers::ga::RunResult;
+use crate::optimizers::stats::GenerationStats;
+use crate::tree::core::errors::TreeError;
+#[cfg(feature = "serde")]
+use crate::tree::core::node::Node;
+use crate::tree::core::tree::TreeGenotype;
+use crate::tree::fitness::compile::{CompileError, Program, Workspace};
+use crate::tree::fitness::classify::{BinaryClassifier, OneVsRest};
+use crate::tree::fitness::evaluate::{LinearScaling, Loss};
+
+/// Errors that can occur while using, saving or loading models.
+///
+/// # Variants
+/// * `IoError(std::io::Error)` - reading or writing model file failed
+/// * `SerializationError(String)` - model could not be encoded or decoded
+/// * `CompileError(CompileError)` - tree uses function missing from the operator set
+/// * `InvalidTree(TreeError)` - loaded tree does not match its operator set or feature names
+/// * `MissingFeature(String)` - dataset lacks feature the model was trained on
+/// * `DimensionMismatch { expected, found }` - row has different number of features than the model
+#[derive(Debug)]
+pub enum ModelError {
+    IoError(std::io::Error),
+    SerializationError(String),
+    CompileError(CompileError),
+    InvalidTree(TreeError),
+    MissingFeature(String),
+    DimensionMismatch { expected: usize, found: usize }
+}
+
+impl std::error::Error for ModelError {}
+
+impl Display for ModelError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ModelError::IoError(error) => write!(f, "IO error: {}", error),
+            ModelError::SerializationError(message) => write!(f, "Serialization error: {}", message),
+            ModelError::CompileError(error) => write!(f, "Failed to compile model: {}", error),
+            ModelError::InvalidTree(error) => write!(f, "Invalid model tree: {}", error),
+            ModelError::MissingFeature(name) => write!(f, "Missing feature: {}", name),
+            ModelError::DimensionMismatch { expected, found } =>
+                write!(f, "Dimensions do not match: expected {}, found {}", expected, found),
+        }
+    }
+}
+
+/// Storage format of saved models.
+///
+/// # Variants
+/// * `Json` - human readable JSON
+/// * `Binary` - compact CBOR encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelFormat {
+    Json,
+    Binary
+}
+
+/// Summary of the run that produced a model.
+///
+/// # Fields
+/// * `generations: usize` - number of generations performed after initialization
+/// * `evaluations: usize` - total number of fitness evaluations
+/// * `elapsed: Duration` - wall-clock duration of the run
+/// * `history: Vec<GenerationStats>` - per-generation [`statistics`][`GenerationStats`]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrainingMetadata {
+    generations: usize,
+    evaluations: usize,
+    elapsed: Duration,
+    history: Vec<GenerationStats>
+}
+
+impl TrainingMetadata {
+    pub fn new(generations: usize, evaluations: usize, elapsed: Duration, history: Vec<GenerationStats>) -> Self {
+        return Self { generations, evaluations, elapsed, history };
+    }
+
+    /// Collects metadata from result of an optimizer run.
+    pub fn from_run<I>(result: &RunResult<I>) -> Self {
+        return Self::new(result.generations(), result.evaluations(), result.elapsed(), result.history().to_vec());
+    }
+
+    pub fn generations(&self) -> usize { return self.generations; }
+    pub fn evaluations(&self) -> usize { return self.evaluations; }
+    pub fn elapsed(&self) -> Duration { return self.elapsed; }
+    pub fn history(&self) -> &[GenerationStats] { return &self.history; }
+}
+
+/// Trained symbolic model ready for prediction and persistence.
+///
+/// Variables of the tree index features in the order of `feature_names`. Predictions on datasets
+/// match features by name, so column order of new data does not need to follow training data.
+///
+/// # Fields
+/// * `tree: TreeGenotype` - evolved [`tree`][`crate::tree::core::tree::TreeGenotype`]
+/// * `operators: Operators` - [`operator set`][`crate::operators::set::Operators`] the tree was
+///   evolved with
+/// * `feature_names: Vec<String>` - names of features the model expects
+/// * `target_name: String` - name of predicted target
+/// * `fitness: f64` - fitness of the tree on training data
+/// * `metadata: TrainingMetadata` - [`summary`][`TrainingMetadata`] of the training run
+/// * `intercept: f64` - added to tree output, 0.0 unless the model is linearly scaled
+/// * `slope: f64` - multiplies tree output, 1.0 unless the model is linearly scaled
+/// * `program: OnceLock<Program>` - tree [`compiled`][`crate::tree::fitness::compile::Program`]
+///   on first prediction and reused afterwards, not serialized
+///
+/// # Examples
+/// ```
+/// use mycoforge::dataset::core::Dataset;
+/// use mycoforge::operators::builder::OperatorsBuilder;
+/// use mycoforge::tree::core::tree::TreeGenotype;
+/// use mycoforge::tree::model::{SymbolicModel, TrainingMetadata};
+///
+/// let operators = OperatorsBuilder::default()
+///     .add_registered("+", "add", 0.5).expect("Failed to add function!")
+///     .add_registered("*", "mul", 0.5).expect("Failed to add function!")
+///     .add_variable("a", 0.5).expect("Failed to add variable!")
+///     .add_variable("b", 0.5).expect("Failed to add variable!")
+///     .build().expect("Failed to build operators!");
+/// let tree = TreeGenotype::from_infix("a * b + a", &operators).expect("Failed to parse tree!");
+/// let names = vec!["a".to_string(), "b".to_string()];
+/// let model = SymbolicModel::new(tree, operators, names, "y".to_string(), 0.0, TrainingMetadata::default());
+///
+/// assert_eq!(model.predict_row(&[2.0, 3.0]).expect("Failed to predict!"), 8.0);
+///
+/// // Columns are matched by name
+/// let dataset = Dataset::new(vec!["b".to_string(), "a".to_string()], "y".to_string(),
+///     vec![vec![3.0, 0.0], vec![2.0, 1.0]], vec![8.0, 1.0]);
+/// assert_eq!(model.predict(&dataset).expect("Failed to predict!"), vec![8.0, 1.0]);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SymbolicModel {
+    tree: TreeGenotype,
+    operators: Operators,
+    feature_names: Vec<String>,
+    target_name: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::float_serde"))]
+    fitness: f64,
+    metadata: TrainingMetadata,
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::float_serde", default))]
+    intercept: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::float_serde", default = "unit_slope"))]
+    slope: f64,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    program: OnceLock<Program>
+}
+
+#[cfg(feature = "serde")]
+fn unit_slope() -> f64 { return 1.0; }
+
+impl SymbolicModel {
+    pub fn new(
+        tree: TreeGenotype, operators: Operators,
+        feature_names: Vec<String>, target_name: String,
+        fitness: f64, metadata: TrainingMetadata
+    ) -> Self {
+        return Self {
+            tree, operators, feature_names, target_name, fitness, metadata,
+            intercept: 0.0, slope: 1.0, program: OnceLock::new()
+        };
+    }
+
+    /// Sets linear scaling applied to tree output, predictions become `intercept + slope * tree`.
+    pub fn with_scaling(mut self, intercept: f64, slope: f64) -> Self {
+        self.intercept = intercept;
+        self.slope = slope;
+        return self;
+    }
+
+    /// Creates model from the best individual of an optimizer run on given dataset.
+    ///
+    /// # Arguments
+    /// * `result: &RunResult<I>` - [`result`][`crate::optimizers::ga::RunResult`] of the run
+    /// * `operators: Operators` - operator set used during the run
+    /// * `dataset: &Dataset` - training dataset, provides feature and target names
+    pub fn from_run<I: Individual<TreeGenotype>>(result: &RunResult<I>, operators: Operators, dataset: &Dataset) -> Self {
+        let (feature_names, target_name) = dataset.names();
+        return Self::new(
+            result.best().genotype().clone(), operators,
+            feature_names.clone(), target_name.clone(),
+            result.best().phenotype(), TrainingMetadata::from_run(result)
+        );
+    }
+
+    /// Creates model from the best individual of a run evaluated with linear scaling, fitting its
+    /// intercept and slope on given dataset.
+    ///
+    /// # Arguments
+    /// * `result: &RunResult<I>` - [`result`][`crate::optimizers::ga::RunResult`] of the run
+    /// * `operators: Operators` - operator set used during the run
+    /// * `dataset: &Dataset` - training dataset, provides feature and target names
+    /// * `evaluator: &LinearScaling<L>` - [`evaluator`][`crate::tree::fitness::evaluate::LinearScaling`]
+    ///   used during the run
+    pub fn from_scaled_run<I: Individual<TreeGenotype>, L: Loss>(
+        result: &RunResult<I>, operators: Operators, dataset: &Dataset, evaluator: &LinearScaling<L>
+    ) -> Self {
+        let (intercept, slope) = evaluator.coefficients(result.best().genotype(), dataset, &operators.create_map());
+        return Self::from_run(result, operators, dataset).with_scaling(intercept, slope);
+    }
+
+    pub fn tree(&self) -> &TreeGenotype { return &self.tree; }
+    pub fn operators(&self) -> &Operators { return &self.operators; }
+    pub fn feature_names(&self) -> &Vec<String> { return &self.feature_names; }
+    pub fn target_name(&self) -> &String { return &self.target_name; }
+    pub fn fitness(&self) -> f64 { return self.fitness; }
+    pub fn metadata(&self) -> &TrainingMetadata { return &self.metadata; }
+    pub fn intercept(&self) -> f64 { return self.intercept; }
+    pub fn slope(&self) -> f64 { return self.slope; }
+
+    /// Returns mapping of function names to implementations for evaluating the tree.
+    pub fn map(&self) -> HashMap<String, (usize, VectorFunction)> { return self.operators.create_map(); }
+
+    /// Predicts target for every row of the dataset.
+    ///
+    /// # Arguments
+    /// * `dataset: &Dataset` - dataset containing all features of the model, extra features are
+    ///   ignored
+    ///
+    /// # Returns
+    /// * `Result<Vec<f64>, ModelError>` - predictions or [`error`][`ModelError`] if a feature is missing
+    pub fn predict(&self, dataset: &Dataset) -> std::result::Result<Vec<f64>, ModelError> {
+        let (names, _) = dataset.names();
+        let (features, _) = dataset.data();
+        let columns = self.feature_names.iter()
+            .map(|name| names.iter().position(|found| found == name)
+                .map(|index| features[index].clone())
+                .ok_or_else(|| ModelError::MissingFeature(name.clone())))
+            .collect::<std::result::Result<Vec<Vec<f64>>, ModelError>>()?;
+        return self.evaluate(&columns, dataset.targets().len());
+    }
+
+    /// Predicts target for a single row.
+    ///
+    /// # Arguments
+    /// * `row: &[f64]` - feature values in the order of [`feature_names`][`SymbolicModel::feature_names`]
+    ///
+    /// # Returns
+    /// * `Result<f64, ModelError>` - prediction or [`error`][`ModelError`] if row has wrong length
+    pub fn predict_row(&self, row: &[f64]) -> std::result::Result<f64, ModelError> {
+        if row.len() != self.feature_names.len() {
+            return Err(ModelError::DimensionMismatch { expected: self.feature_names.len(), found: row.len() });
+        }
+        let columns = row.iter().map(|&value| vec![value]).collect::<Vec<Vec<f64>>>();
+        return Ok(self.evaluate(&columns, 1)?[0]);
+    }
+
+    /// Predicts probability of the positive class for every row of the dataset, treating tree
+    /// output as the raw score of a binary classifier.
+    ///
+    /// # Arguments
+    /// * `dataset: &Dataset` - dataset containing all features of the model
+    /// * `classifier: &BinaryClassifier` - [`mapping`][`crate::tree::fitness::classify::BinaryClassifier`]
+    ///   of scores the model was trained with
+    pub fn predict_probabilities(&self, dataset: &Dataset, classifier: &BinaryClassifier) -> std::result::Result<Vec<f64>, ModelError> {
+        return Ok(classifier.probabilities(&self.predict(dataset)?));
+    }
+
+    /// Predicts binary label, 1.0 or 0.0, for every row of the dataset, treating tree output as
+    /// the raw score of a binary classifier.
+    ///
+    /// # Arguments
+    /// * `dataset: &Dataset` - dataset containing all features of the model
+    /// * `classifier: &BinaryClassifier` - [`mapping`][`crate::tree::fitness::classify::BinaryClassifier`]
+    ///   of scores the model was trained with
+    pub fn predict_labels(&self, dataset: &Dataset, classifier: &BinaryClassifier) -> std::result::Result<Vec<f64>, ModelError> {
+        return Ok(classifier.labels(&self.predict(dataset)?));
+    }
+
+    /// Returns compiled tree, compiling it on first use.
+    fn program(&self) -> std::result::Result<&Program, ModelError> {
+        if let Some(program) = self.program.get() { return Ok(program); }
+        let program = Program::compile(&self.tree, &self.operators).map_err(ModelError::CompileError)?;
+        return Ok(self.program.get_or_init(|| program));
+    }
+
+    /// Evaluates model on feature columns with given number of rows, models without features
+    /// have no column to take it from.
+    fn evaluate(&self, columns: &[Vec<f64>], rows: usize) -> std::result::Result<Vec<f64>, ModelError> {
+        let predictions = self.program()?.execute_rows(columns, rows, &mut Workspace::new()).to_vec();
+        if self.intercept == 0.0 && self.slope == 1.0 { return Ok(predictions); }
+        return Ok(predictions.into_iter().map(|p| self.intercept + self.slope * p).collect());
+    }
+}
+
+#[cfg(feature = "serde")]
+fn encode<T: Serialize>(value: &T, format: ModelFormat) -> std::result::Result<Vec<u8>, ModelError> {
+    let serialization_error = |error: &dyn Display| ModelError::SerializationError(error.to_string());
+    return match format {
+        ModelFormat::Json => serde_json::to_vec_pretty(value).map_err(|e| serialization_error(&e)),
+        ModelFormat::Binary => {
+            let mut bytes = Vec::new();
+            ciborium::into_writer(value, &mut bytes).map_err(|e| serialization_error(&e))?;
+            Ok(bytes)
+        },
+    };
+}
+
+#[cfg(feature = "serde")]
+fn decode<T: DeserializeOwned>(bytes: &[u8], format: ModelFormat) -> std::result::Result<T, ModelError> {
+    let serialization_error = |error: &dyn Display| ModelError::SerializationError(error.to_string());
+    return match format {
+        ModelFormat::Json => serde_json::from_slice(bytes).map_err(|e| serialization_error(&e)),
+        ModelFormat::Binary => ciborium::from_reader(bytes).map_err(|e| serialization_error(&e)),
+    };
+}
+
+#[cfg(feature = "serde")]
+impl SymbolicModel {
+    /// Encodes model in given format.
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>, ModelError>` - encoded model or [`error`][`ModelError`] if operator set
+    ///   cannot be serialized
+    pub fn to_bytes(&self, format: ModelFormat) -> std::result::Result<Vec<u8>, ModelError> {
+        return encode(self, format);
+    }
+
+    /// Decodes model from bytes in given format.
+    ///
+    /// # Returns
+    /// * `Result<Self, ModelError>` - decoded model or [`error`][`ModelError`] if bytes are not a
+    ///   valid model, e.g. the tree does not match its operator set or uses a variable without
+    ///   feature name
+    pub fn from_bytes(bytes: &[u8], format: ModelFormat) -> std::result::Result<Self, ModelError> {
+        let model: Self = decode(bytes, format)?;
+        model.validate()?;
+        return Ok(model);
+    }
+
+    /// Checks that decoded tree is consistent with the operator set and feature names, so
+    /// predictions on loaded models cannot panic.
+    fn validate(&self) -> std::result::Result<(), ModelError> {
+        self.tree.validate(&self.operators).map_err(ModelError::InvalidTree)?;
+        for (index, node) in self.tree.arena().iter().enumerate() {
+            if let Node::Variable(column) = node {
+                if *column >= self.feature_names.len() {
+                    return Err(ModelError::InvalidTree(TreeError::UnknownSymbol { index, symbol: node.name() }));
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    /// Saves model to file.
+    ///
+    /// # Arguments
+    /// * `path: &str` - path of created file
+    /// * `format: ModelFormat` - [`format`][`ModelFormat`] of the file
+    pub fn save(&self, path: &str, format: ModelFormat) -> std::result::Result<(), ModelError> {
+        return std::fs::write(path, self.to_bytes(format)?).map_err(ModelError::IoError);
+    }
+
+    /// Loads model saved with [`save`][`SymbolicModel::save`].
+    pub fn load(path: &str, format: ModelFormat) -> std::result::Result<Self, ModelError> {
+        let bytes = std::fs::read(path).map_err(ModelError::IoError)?;
+        return Self::from_bytes(&bytes, format);
+    }
+}
+
+/// Multiclass classifier built from one [`SymbolicModel`] per class by
+/// [`one-vs-rest`][`crate::tree::fitness::classify::OneVsRest`] decomposition.
+///
+/// # Fields
+/// * `models: Vec<SymbolicModel>` - binary models in the order of classes of `scheme`
+/// * `scheme: OneVsRest` - [`scheme`][`crate::tree::fitness::classify::OneVsRest`] mapping
+///   scores of the models to class labels
+///
+/// # Examples
+/// ```
+/// use mycoforge::dataset::core::Dataset;
+/// use mycoforge::operators::builder::OperatorsBuilder;
+/// use mycoforge::tree::core::tree::TreeGenotype;
+/// use mycoforge::tree::fitness::classify::OneVsRest;
+/// use mycoforge::tree::model::{OneVsRestModel, SymbolicModel, TrainingMetadata};
+///
+/// let model = |infix: &str| {
+///     let operators = OperatorsBuilder::default()
+///         .add_registered("-", "sub", 1.0).expect("Failed to add function!")
+///         .add_variable("x", 0.5).expect("Failed to add variable!")
+///         .add_constant(1.0, 0.5).expect("Failed to add constant!")
+///         .build().expect("Failed to build operators!");
+///     let tree = TreeGenotype::from_infix(infix, &operators).expect("Failed to parse tree!");
+///     SymbolicModel::new(tree, operators, vec!["x".to_string()], "y".to_string(), 0.0, TrainingMetadata::default())
+/// };
+/// let scheme = OneVsRest::new(vec![10.0, 20.0]).expect("Failed to create scheme!");
+/// let classifier = OneVsRestModel::new(vec![model("1 - x"), model("x - 1")], scheme).expect("Failed to create model!");
+///
+/// let dataset = Dataset::new(vec!["x".to_string()], "y".to_string(), vec![vec![0.0, 3.0]], vec![10.0, 20.0]);
+/// assert_eq!(classifier.predict_labels(&dataset).expect("Failed to predict!"), vec![10.0, 20.0]);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OneVsRestModel {
+    models: Vec<SymbolicModel>,
+    scheme: OneVsRest
+}
+
+impl OneVsRestModel {
+    /// Creates new multiclass model.
+    ///
+    /// # Arguments
+    /// * `models: Vec<SymbolicModel>` - one binary model per class, in the order of classes of
+    ///   `scheme`
+    /// * `scheme: OneVsRest` - decomposition the models were trained with
+    ///
+    /// # Returns
+    /// * `Result<Self, ModelError>` - new model or [`error`][`ModelError`] if number of models
+    ///   differs from number of classes
+    pub fn new(models: Vec<SymbolicModel>, scheme: OneVsRest) -> std::result::Result<Self, ModelError> {
+        if models.len() != scheme.classes().len() {
+            return Err(ModelError::DimensionMismatch { expected: scheme.classes().len(), found: models.len() });
+        }
+        return Ok(Self { models, scheme });
+    }
+
+    pub fn models(&self) -> &[SymbolicModel] { return &self.models; }
+    pub fn scheme(&self) -> &OneVsRest { return &self.scheme; }
+
+    /// Computes raw scores of every class model, one vector per class.
+    pub fn predict_scores(&self, dataset: &Dataset) -> std::result::Result<Vec<Vec<f64>>, ModelError> {
+        return self.models.iter().map(|model| model.predict(dataset)).collect();
+    }
+
+    /// Predicts class label for every row of the dataset, the class whose model scores highest.
+    ///
+    /// # Arguments
+    /// * `dataset: &Dataset` - dataset containing all features of the models
+    ///
+    /// # Returns
+    /// * `Result<Vec<f64>, ModelError>` - class labels or [`error`][`ModelError`] if a feature is
+    ///   missing
+    pub fn predict_labels(&self, dataset: &Dataset) -> std::result::Result<Vec<f64>, ModelError> {
+        return Ok(self.scheme.labels(&self.predict_scores(dataset)?));
+    }
+}
+
+#[cfg(feature = "serde")]
+impl OneVsRestModel {
+    /// Encodes model in given format.
+    pub fn to_bytes(&self, format: ModelFormat) -> std::result::Result<Vec<u8>, ModelError> {
+        return encode(self, format);
+    }
+
+    /// Decodes model from bytes in given format, validating every class model as
+    /// [`SymbolicModel::from_bytes`] does.
+    pub fn from_bytes(bytes: &[u8], format: ModelFormat) -> std::result::Result<Self, ModelError> {
+        let model: Self = decode(bytes, format)?;
+        for class_model in &model.models { class_model.validate()?; }
+        return Self::new(model.models, model.scheme);
+    }
+
+    /// Saves model to file.
+    pub fn save(&self, path: &str, format: ModelFormat) -> std::result::Result<(), ModelError> {
+        return std::fs::write(path, self.to_bytes(format)?).map_err(ModelError::IoError);
+    }
+
+    /// Loads model saved with [`save`][`OneVsRestModel::save`].
+    pub fn load(path: &str, format: ModelFormat) -> std::result::Result<Self, ModelError> {
+        let bytes = std::fs::read(path).map_err(ModelError::IoError)?;
+        return Self::from_bytes(&bytes, format);
+    }
+}
@@ -7,6 +7,7 @@
 use rand::Rng;
 
 use crate::common::traits::Crossoverer;
+use crate::tree::core::node::Node;
 use crate::tree::core::tree::TreeGenotype;
 use crate::operators::sampler::OperatorSampler;
 use crate::tree::operators::errors::CrossoverError;
@@ -73,9 +74,9 @@ impl SubtreeCrossover {
     /// * `crossover_points: (usize, usize)` - indices where subtree swap occurs
     ///
     /// # Returns
-    /// * `Vec<Vec<String>>` - arenas of two offspring after subtree swap
+    /// * `Vec<Vec<Node>>` - arenas of two offspring after subtree swap
     fn swap(parents: (&TreeGenotype, &TreeGenotype), crossover_points: (usize, usize)) 
-        -> Vec<Vec<String>> {
+        -> Vec<Vec<Node>> {
         let (parent1, parent2) = parents;
         let (xo_point1, xo_point2) = crossover_points;
         
@@ -111,11 +112,11 @@ impl Crossoverer<TreeGenotype> for SubtreeCrossover {
             crossover_points.0, crossover_points.1, 
             parent1.arena().len(), parent2.arena().len(), trees[0].len(), trees[1].len()
         );
-        let mut mutants = Vec::new();
-        for tree in trees {
-            let mut child = TreeGenotype::with_arena(tree.clone());
-            *child.children_mut() = child.construct_children(sampler);
-            mutants.push(child);
+        let mutants = trees.into_iter().map(TreeGenotype::with_arena).collect::<Vec<TreeGenotype>>();
+        for child in &mutants {
+            debug_assert!(child.validate_with_sampler(sampler).is_ok(),
+                "Crossover produced invalid tree: {}", child.validate_with_sampler(sampler).unwrap_err()
+            );
         }
         debug!("Completed crossover: original sizes ({}, {}) -> children sizes ({}, {})",
             parent1.arena().len(), parent2.arena().len(), mutants[0].arena().len(), mutants[1].arena().len()
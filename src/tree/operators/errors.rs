@@ -3,6 +3,26 @@
 use std::fmt;
 use std::error::Error;
 
+/// Errors that can occur during initialization operations.
+///
+/// # Variants
+/// * `InvalidDepthRange((usize, usize))` - minimum depth exceeds maximum depth
+#[derive(Debug)]
+pub enum InitializationError {
+    InvalidDepthRange((usize, usize))
+}
+
+impl Error for InitializationError {}
+
+impl fmt::Display for InitializationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InitializationError::InvalidDepthRange((min_depth, max_depth))
+                => write!(f, "Minimum depth {} exceeds maximum depth {}!", min_depth, max_depth),
+        }
+    }
+}
+
 /// Errors that can occur during mutation operations.
 ///
 /// # Variants
@@ -52,10 +72,16 @@ impl fmt::Display for CrossoverError {
 /// # Variants
 /// * `InvalidTournamentSize((usize, usize))` - tournament size exceeds population size
 /// * `InvalidFitnessComparison((f64, f64))` - failed to compare fitness values
+/// * `MissingCaseErrors` - individuals lack per-case errors or have different numbers of them
+/// * `InvalidPressure(f64)` - selection pressure outside of the allowed range
+/// * `InvalidProportion(f64)` - truncation proportion outside of (0.0, 1.0] range
 #[derive(Debug)]
 pub enum SelectionError {
     InvalidTournamentSize((usize, usize)),
-    InvalidFitnessComparison((f64, f64))
+    InvalidFitnessComparison((f64, f64)),
+    MissingCaseErrors,
+    InvalidPressure(f64),
+    InvalidProportion(f64)
 }
 
 impl Error for SelectionError {}
@@ -66,7 +92,13 @@ impl fmt::Display for SelectionError {
             SelectionError::InvalidTournamentSize((tournament_size, population_size))
                 => write!(f, "Tournament size {} exceeds population size {}!", tournament_size, population_size),
             SelectionError::InvalidFitnessComparison((a, b))
-                => write!(f, "Invalid fitness comparison: {} ? {}", a, b)
+                => write!(f, "Invalid fitness comparison: {} ? {}", a, b),
+            SelectionError::MissingCaseErrors
+                => write!(f, "Case-based selection requires the same non-zero number of case errors for all individuals!"),
+            SelectionError::InvalidPressure(pressure)
+                => write!(f, "Invalid selection pressure: {}", pressure),
+            SelectionError::InvalidProportion(proportion)
+                => write!(f, "Invalid truncation proportion: {}", proportion)
         }
     }
 }
@@ -1,18 +1,22 @@
 //! Tree initialization methods for Genetic Programming.
 //!
 //! Provides standard implementations: Grow and Full methods.
-//! Designed to return `TreeGenotype` structure. Does not include `Ramped Half and Half` method,
-//! because it's more of method of constructing population, not a single individual.
+//! Designed to return `TreeGenotype` structure. `Ramped Half and Half` constructs whole population
+//! rather than a single individual, so it implements
+//! [`PopulationInitializer`][`crate::common::traits::PopulationInitializer`] instead.
 //! Serves as a template for creating custom initialization methods.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use log::{info, error, warn};
 use rand::Rng;
 use rand::prelude::IndexedRandom;
 
-use crate::common::traits::Initializer;
+use crate::common::traits::{Initializer, PopulationInitializer};
 use crate::tree::core::tree::TreeGenotype;
 use crate::operators::sampler::{OperatorSampler, Sampler};
 
+use super::errors::InitializationError;
+
 /// Grow initialization method
 ///
 /// Generates trees with depths between minimum and maximum bounds.
@@ -63,32 +67,32 @@ impl Initializer<TreeGenotype> for Grow {
         );
 
         let mut root: usize = 0;
-        let (node_id, node_arity) = 
+        let node = 
             if self.max_depth == 0 {
-                term_set.sample(rng)
+                term_set.sample_node(rng)
             } else {
-                func_set.sample(rng)
+                func_set.sample_node(rng)
             };
 
-        tree.arena_mut().push(node_id);
-        for _ in 0..node_arity {
+        tree.arena_mut().push(node);
+        for _ in 0..node.arity() {
             stack.push((root, 1));
         }
         
         while let Some((parent, depth)) = stack.pop() {
             root += 1;
-            let (node_id, node_arity) = 
+            let node = 
                 if depth == self.max_depth {
-                    term_set.sample(rng)
+                    term_set.sample_node(rng)
                 } else if depth < self.min_depth {
-                    func_set.sample(rng)
+                    func_set.sample_node(rng)
                 } else {
-                    [&term_set, &func_set].choose(rng).unwrap().sample(rng)
+                    [&term_set, &func_set].choose(rng).unwrap().sample_node(rng)
                 };
 
-            tree.arena_mut().push(node_id);
+            tree.arena_mut().push(node);
             tree.children_mut().entry(parent).or_default().push(root);
-            for _ in 0..node_arity {
+            for _ in 0..node.arity() {
                 stack.push((root, depth+1));
             }
         }
@@ -131,3 +135,99 @@ impl Initializer<TreeGenotype> for Full {
         return scheme.initialize(rng, sampler);
     }
 }
+
+/// Ramped half-and-half population initialization method.
+///
+/// Splits population evenly between depths from `min_depth` to `max_depth` (inclusive). For each
+/// depth, half of the trees is generated with [`Full`] method and the other half with [`Grow`]
+/// method limited to that depth. Optionally rejects trees already present in the population.
+///
+/// # Fields:
+/// * `min_depth: usize` - minimum depth of a tree
+/// * `max_depth: usize` - maximum depth of a tree
+/// * `reject_duplicates: bool` - when true, regenerates trees already present in the population
+///
+/// # Examples
+/// ```
+/// use mycoforge::common::traits::PopulationInitializer;
+/// use mycoforge::operators::sampler::OperatorSampler;
+/// use mycoforge::tree::operators::init::RampedHalfAndHalf;
+/// use rand::{rngs::StdRng, SeedableRng};
+///
+/// let operators = ["+", "*", "x", "y"].iter().map(|&w| w.to_string()).collect();
+/// let sampler = OperatorSampler::new(operators, vec![2, 2, 0, 0], vec![0.25; 4]);
+/// let initializer = RampedHalfAndHalf::new(1, 3, true)
+///     .expect("Failed to create RampedHalfAndHalf!");
+///
+/// let mut rng = StdRng::seed_from_u64(42);
+/// let population = initializer.initialize_population(&mut rng, &sampler, 30);
+///
+/// assert_eq!(population.len(), 30);
+/// ```
+pub struct RampedHalfAndHalf {
+    min_depth: usize,
+    max_depth: usize,
+    reject_duplicates: bool
+}
+
+impl RampedHalfAndHalf {
+    /// Number of attempts to generate a new tree before a duplicate is accepted.
+    pub const MAX_ATTEMPTS: usize = 100;
+
+    /// Creates new RampedHalfAndHalf initializer.
+    ///
+    /// # Arguments
+    /// * `min_depth: usize` - minimum depth of a tree
+    /// * `max_depth: usize` - maximum depth of a tree
+    /// * `reject_duplicates: bool` - whether to regenerate trees already present in the population
+    ///
+    /// # Returns
+    /// * `Result<Self, InitializationError>` - new initializer or
+    ///   [`Error`][`crate::tree::operators::errors::InitializationError`]
+    pub fn new(min_depth: usize, max_depth: usize, reject_duplicates: bool) -> Result<Self, InitializationError> {
+        if min_depth > max_depth {
+            error!("Attempted to create RampedHalfAndHalf with invalid depth range ({}, {})", min_depth, max_depth);
+            return Err(InitializationError::InvalidDepthRange((min_depth, max_depth)));
+        }
+        info!("Created RampedHalfAndHalf initializer with depth range ({}, {})", min_depth, max_depth);
+        return Ok(Self { min_depth, max_depth, reject_duplicates });
+    }
+
+    pub fn min_depth(&self) -> usize { return self.min_depth; }
+    pub fn max_depth(&self) -> usize { return self.max_depth; }
+    pub fn reject_duplicates(&self) -> bool { return self.reject_duplicates; }
+}
+
+impl PopulationInitializer<TreeGenotype> for RampedHalfAndHalf {
+    fn initialize_population<R: Rng>(&self, rng: &mut R, sampler: &OperatorSampler, population_size: usize) -> Vec<TreeGenotype> {
+        let depths = self.max_depth - self.min_depth + 1;
+        let mut population: Vec<TreeGenotype> = Vec::with_capacity(population_size);
+        let mut seen: HashSet<TreeGenotype> = HashSet::new();
+
+        for i in 0..population_size {
+            let depth = self.min_depth + (i / 2) % depths;
+            let generate = |rng: &mut R| -> TreeGenotype {
+                return if i % 2 == 0 {
+                    Full::new(depth).initialize(rng, sampler)
+                } else {
+                    Grow::new(self.min_depth, depth).initialize(rng, sampler)
+                };
+            };
+
+            let mut tree = generate(rng);
+            if self.reject_duplicates {
+                let mut attempts = 1;
+                while seen.contains(&tree) && attempts < Self::MAX_ATTEMPTS {
+                    tree = generate(rng);
+                    attempts += 1;
+                }
+                if seen.contains(&tree) {
+                    warn!("Accepted duplicate tree at depth {} after {} attempts", depth, attempts);
+                }
+                seen.insert(tree.clone());
+            }
+            population.push(tree);
+        }
+        return population;
+    }
+}
@@ -6,6 +6,7 @@
 //! - [`mutation`] - Tree mutation operators
 //! - [`crossover`] - Tree crossover operators
 //! - [`select`] - Selection operators
+//! - [`simplify`] - Algebraic simplification of trees
 
 pub mod init;
 
@@ -13,3 +14,4 @@ pub mod errors;
 pub mod mutation;
 pub mod crossover;
 pub mod select;
+pub mod simplify;
@@ -6,6 +6,7 @@
 use rand::Rng;
 
 use crate::common::traits::{Initializer, Mutator};
+use crate::tree::core::node::Node;
 use crate::tree::core::tree::TreeGenotype;
 use crate::operators::sampler::{OperatorSampler, Sampler};
 
@@ -22,9 +23,9 @@ use log::{info, error, debug};
 /// * `mutation_point: usize` - index where substitution occurs
 ///
 /// # Returns
-/// * `Vec<String>` - new tree arena after substitution
+/// * `Vec<Node>` - new tree arena after substitution
 fn substitute(individual: &TreeGenotype, subtree: &TreeGenotype, mutation_point: usize) 
-    -> Vec<String> {
+    -> Vec<Node> {
     let mutation_end: usize = individual.subtree(mutation_point);
 
     let mut new_arena = individual.arena()[0..mutation_point].to_vec();
@@ -110,9 +111,10 @@ impl Mutator<TreeGenotype> for SubtreeMutation {
         let subtree = init_scheme.initialize(rng, sampler);
         debug!("Generated subtree of size {} at point {}", subtree.arena().len(), mutation_point);
         
-        let arena = substitute(individual, &subtree, mutation_point);
-        let mut tree = TreeGenotype::with_arena(arena);
-        *tree.children_mut() = tree.construct_children(sampler);
+        let tree = TreeGenotype::with_arena(substitute(individual, &subtree, mutation_point));
+        debug_assert!(tree.validate_with_sampler(sampler).is_ok(),
+            "Mutation produced invalid tree: {}", tree.validate_with_sampler(sampler).unwrap_err()
+        );
         
         debug!("Completed mutation: original size {} -> mutant size {}", individual.arena().len(), tree.arena().len());
         return tree.clone();
@@ -218,9 +220,10 @@ impl Mutator<TreeGenotype> for SizeFairMutation {
         let init_scheme = Grow::new(depth_limits.0, depth_limits.1);
         let subtree = init_scheme.initialize(rng, sampler);
 
-        let arena = substitute(individual, &subtree, mutation_point);
-        let mut tree = TreeGenotype::with_arena(arena);
-        *tree.children_mut() = tree.construct_children(sampler);
+        let tree = TreeGenotype::with_arena(substitute(individual, &subtree, mutation_point));
+        debug_assert!(tree.validate_with_sampler(sampler).is_ok(),
+            "Mutation produced invalid tree: {}", tree.validate_with_sampler(sampler).unwrap_err()
+        );
 
         debug!("Completed mutation: original size {} -> mutant size {}", individual.arena().len(), tree.arena().len());
         return tree.clone();
@@ -288,20 +291,19 @@ impl Mutator<TreeGenotype> for PointMutation {
         }
         
         let mutation_point: usize = rng.random_range(0..individual.arena().len());
-        let index = sampler.operators().iter()
-            .position(|s| *s == individual.arena()[mutation_point])
-            .expect("Failed to find operator in given sampler!");
-        let arity = sampler.arities()[index];
+        let arity = individual.arena()[mutation_point].arity();
         let limited_sampler = sampler.sampler_with_arity(arity, arity);
-        let new_node = limited_sampler.sample(rng);
-        assert_eq!(new_node.1, arity,
-            "Generated new node with different arity! Expected {}, found {}", arity, new_node.1
+        let new_node = limited_sampler.sample_node(rng);
+        assert_eq!(new_node.arity(), arity,
+            "Generated new node with different arity! Expected {}, found {}", arity, new_node.arity()
         );
-        debug!("Generated new node {} with arity {}", new_node.0, new_node.1);
+        debug!("Generated new node {} with arity {}", new_node, arity);
         let mut arena = individual.arena().clone();
-        arena[mutation_point] = new_node.0;
-        let mut tree = TreeGenotype::with_arena(arena);
-        *tree.children_mut() = tree.construct_children(sampler);
+        arena[mutation_point] = new_node;
+        let tree = TreeGenotype::new(arena, individual.children().clone());
+        debug_assert!(tree.validate_with_sampler(sampler).is_ok(),
+            "Mutation produced invalid tree: {}", tree.validate_with_sampler(sampler).unwrap_err()
+        );
         
         debug!("Completed mutation: original size {} -> mutant size {}", individual.arena().len(), tree.arena().len());
         return tree.clone();
@@ -399,9 +401,7 @@ impl Mutator<TreeGenotype> for ConstantMutation {
         let mut arena = individual.arena().clone();
 
         let constant_positions = arena.iter().enumerate()
-            .filter(|(_, node)| {
-                node.parse::<f64>().is_ok()
-            })
+            .filter(|(_, node)| matches!(node, Node::Constant(_)))
             .map(|(i, _)| i).collect::<Vec<usize>>();
 
         if constant_positions.is_empty() {
@@ -411,17 +411,20 @@ impl Mutator<TreeGenotype> for ConstantMutation {
 
         let mutation_point = constant_positions[rng.random_range(0..constant_positions.len())];
 
-        let current_value = arena[mutation_point].parse::<f64>()
-            .unwrap_or_else(|_| panic!("Failed to parse constant node: {}", arena[mutation_point]));
+        let Node::Constant(current_value) = arena[mutation_point] else {
+            unreachable!("Constant positions should point at constant nodes!");
+        };
         let delta = 1.0 + (rng.random::<f64>() * 2.0 - 1.0) * self.mutation_rate;
         let new_value = if let Some((min, max)) = self.range_limits {
             (current_value * delta).clamp(min, max)
         } else { current_value * delta };
 
-        arena[mutation_point] = format!("{}", new_value);
+        arena[mutation_point] = Node::Constant(new_value);
 
-        let mut tree = TreeGenotype::with_arena(arena);
-        *tree.children_mut() = tree.construct_children(sampler);
+        let tree = TreeGenotype::new(arena, individual.children().clone());
+        debug_assert!(tree.validate_with_sampler(sampler).is_ok(),
+            "Mutation produced invalid tree: {}", tree.validate_with_sampler(sampler).unwrap_err()
+        );
         
         debug!("Completed mutation: constant {} -> {}", current_value, new_value);
         return tree;
@@ -2,13 +2,29 @@
 //!
 //! This module provides selection operators for GP algorithms designed for selecting individuals
 //! based on their fitness values. Also serves as a template for custom selection operators.
+//!
+//! Selectors honour the optimization [`Direction`] passed by optimizers, so tournament, fitness
+//! proportionate ([`RouletteSelection`], [`StochasticUniversalSampling`]), rank-based
+//! ([`LinearRankSelection`], [`ExponentialRankSelection`]) and [`TruncationSelection`] favour
+//! lower fitness when minimizing errors and higher fitness when maximizing scores.
+//!
+//! Lexicase selection ([`LexicaseSelection`], [`EpsilonLexicaseSelection`]) selects on errors of
+//! individual data rows (cases) instead of aggregated fitness; optimizers then evaluate
+//! individuals with [`Evaluator::evaluate_population_cases`][`crate::common::traits::Evaluator::evaluate_population_cases`].
+//! Case errors are always minimized, regardless of the direction of fitness.
+//!
+//! [`LexicographicParsimonyTournament`] and [`DoubleTournament`] control bloat by preferring
+//! smaller trees, fitness-based alternatives are in
+//! [`parsimony`][`crate::tree::fitness::parsimony`].
 
 use core::panic;
+use std::cmp::Ordering;
 
 use log::error;
 use rand::Rng;
-use rand::prelude::IndexedRandom;
+use rand::prelude::{IndexedRandom, SliceRandom};
 
+use crate::common::objectives::Direction;
 use crate::common::traits::{Individual, Selector};
 use crate::tree::core::{tree::TreeGenotype, individual::TreeIndividual};
 use crate::tree::operators::errors::SelectionError;
@@ -45,19 +61,649 @@ impl TournamentSelection {
 
 impl Selector<TreeGenotype> for TournamentSelection {
     type I = TreeIndividual<TreeGenotype>;
-    fn select<R: Rng>(&self, rng: &mut R, population: &[TreeIndividual<TreeGenotype>]) -> TreeGenotype {
-        if self.tournament_size > population.len() {
-            error!("{}", SelectionError::InvalidTournamentSize((self.tournament_size, population.len())));
-            panic!("{}", SelectionError::InvalidTournamentSize((self.tournament_size, population.len())));
-        }
-
-        return population.choose_multiple(rng, self.tournament_size)
-            .min_by(|a, b| 
-                a.phenotype().partial_cmp(&b.phenotype()
-            ).unwrap_or_else(|| 
-                panic!("{}", SelectionError::InvalidFitnessComparison((a.phenotype(), b.phenotype())))
-            )).expect("Tournament selection failed!")
+    fn select<R: Rng>(&self, rng: &mut R, population: &[TreeIndividual<TreeGenotype>], direction: Direction) -> TreeGenotype {
+        return tournament(rng, population, self.tournament_size, |a, b| direction.compare(a.phenotype(), b.phenotype()))
             .genotype().clone();
      }
 }
 
+/// Returns the best of `size` randomly sampled individuals, where [`Ordering::Less`] from
+/// `compare` means the first individual is better.
+///
+/// # Panics
+/// * If `size` exceeds population size
+fn tournament<'a, R: Rng>(
+    rng: &mut R, population: &'a [TreeIndividual<TreeGenotype>], size: usize,
+    compare: impl Fn(&TreeIndividual<TreeGenotype>, &TreeIndividual<TreeGenotype>) -> Ordering
+) -> &'a TreeIndividual<TreeGenotype> {
+    if size > population.len() {
+        error!("{}", SelectionError::InvalidTournamentSize((size, population.len())));
+        panic!("{}", SelectionError::InvalidTournamentSize((size, population.len())));
+    }
+
+    return population.choose_multiple(rng, size)
+        .min_by(|a, b| compare(a, b))
+        .expect("Tournament selection failed!");
+}
+
+/// Size of individual used by parsimony-based selection, number of nodes of its tree.
+fn tree_size(individual: &TreeIndividual<TreeGenotype>) -> usize { return individual.genotype().arena().len(); }
+
+/// Lexicographic parsimony pressure tournament operator.
+///
+/// Selects the individual with the best fitness from random subset like [`TournamentSelection`],
+/// but breaks ties in fitness by preferring the smaller tree. Effective when many individuals share
+/// the same fitness, e.g. with discrete fitness such as classification error.
+///
+/// # Fields
+/// * `tournament_size: usize` - Number of individuals randomly sampled for tournament
+///
+/// # Examples
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha8Rng;
+/// use mycoforge::common::objectives::Direction;
+/// use mycoforge::common::traits::{Individual, Selector};
+/// use mycoforge::operators::builder::OperatorsBuilder;
+/// use mycoforge::tree::core::{individual::TreeIndividual, tree::TreeGenotype};
+/// use mycoforge::tree::operators::select::LexicographicParsimonyTournament;
+///
+/// let operators = OperatorsBuilder::default()
+///     .add_registered("+", "add", 1.0).expect("Failed to add function!")
+///     .add_variable("x", 1.0).expect("Failed to add variable!")
+///     .build().expect("Failed to build operators!");
+/// let large = TreeGenotype::from_infix("x + x", &operators).expect("Failed to parse tree!");
+/// let small = TreeGenotype::from_infix("x", &operators).expect("Failed to parse tree!");
+/// let population = TreeIndividual::from_vecs(&[large, small.clone()], &[1.0, 1.0]);
+///
+/// let selection = LexicographicParsimonyTournament::new(2);
+/// let selected = selection.select(&mut ChaCha8Rng::seed_from_u64(42), &population, Direction::Minimize);
+///
+/// assert_eq!(selected, small);
+/// ```
+pub struct LexicographicParsimonyTournament {
+    tournament_size: usize,
+}
+
+impl LexicographicParsimonyTournament {
+    /// Creates new LexicographicParsimonyTournament operator.
+    ///
+    /// # Arguments
+    /// * `tournament_size: usize` - number of individuals in tournament
+    pub fn new(tournament_size: usize) -> Self { return Self { tournament_size }; }
+
+    pub fn tournament_size(&self) -> usize { return self.tournament_size; }
+}
+
+impl Selector<TreeGenotype> for LexicographicParsimonyTournament {
+    type I = TreeIndividual<TreeGenotype>;
+    fn select<R: Rng>(&self, rng: &mut R, population: &[TreeIndividual<TreeGenotype>], direction: Direction) -> TreeGenotype {
+        let compare = |a: &TreeIndividual<TreeGenotype>, b: &TreeIndividual<TreeGenotype>| {
+            return direction.compare(a.phenotype(), b.phenotype()).then(tree_size(a).cmp(&tree_size(b)));
+        };
+        return tournament(rng, population, self.tournament_size, compare).genotype().clone();
+    }
+}
+
+/// Double tournament operator with fitness tournaments inside a size tournament.
+///
+/// Two fitness tournaments (see [`TournamentSelection`]) choose two qualifiers, of which the
+/// smaller tree is selected with probability `parsimony_pressure / 2` and the larger one otherwise.
+/// Qualifiers of equal size are selected by fitness. Pressure 1.0 disables parsimony, pressure 2.0
+/// always prefers the smaller qualifier; values around 1.4 control bloat while keeping accuracy.
+///
+/// # Fields
+/// * `fitness_size: usize` - Number of individuals randomly sampled for each fitness tournament
+/// * `parsimony_pressure: f64` - pressure towards smaller trees, in [1.0, 2.0]
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::operators::select::DoubleTournament;
+///
+/// let selection = DoubleTournament::new(7, 1.4).expect("Failed to create selection!");
+///
+/// assert_eq!(selection.fitness_size(), 7);
+/// assert_eq!(selection.parsimony_pressure(), 1.4);
+/// assert!(DoubleTournament::new(7, 2.5).is_err());
+/// ```
+pub struct DoubleTournament {
+    fitness_size: usize,
+    parsimony_pressure: f64
+}
+
+impl DoubleTournament {
+    /// Creates new DoubleTournament operator.
+    ///
+    /// # Arguments
+    /// * `fitness_size: usize` - number of individuals in each fitness tournament
+    /// * `parsimony_pressure: f64` - pressure towards smaller trees, in [1.0, 2.0]
+    ///
+    /// # Returns
+    /// * `Result<Self, SelectionError>` - new operator or error if pressure is out of range
+    pub fn new(fitness_size: usize, parsimony_pressure: f64) -> Result<Self, SelectionError> {
+        if !(1.0..=2.0).contains(&parsimony_pressure) {
+            return Err(SelectionError::InvalidPressure(parsimony_pressure));
+        }
+        return Ok(Self { fitness_size, parsimony_pressure });
+    }
+
+    pub fn fitness_size(&self) -> usize { return self.fitness_size; }
+    pub fn parsimony_pressure(&self) -> f64 { return self.parsimony_pressure; }
+}
+
+impl Selector<TreeGenotype> for DoubleTournament {
+    type I = TreeIndividual<TreeGenotype>;
+    fn select<R: Rng>(&self, rng: &mut R, population: &[TreeIndividual<TreeGenotype>], direction: Direction) -> TreeGenotype {
+        let compare = |a: &TreeIndividual<TreeGenotype>, b: &TreeIndividual<TreeGenotype>| direction.compare(a.phenotype(), b.phenotype());
+        let first = tournament(rng, population, self.fitness_size, compare);
+        let second = tournament(rng, population, self.fitness_size, compare);
+
+        let (smaller, larger) = match tree_size(first).cmp(&tree_size(second)) {
+            Ordering::Less => (first, second),
+            Ordering::Greater => (second, first),
+            Ordering::Equal => return [first, second].into_iter().min_by(|a, b| compare(a, b))
+                .expect("Double tournament failed!").genotype().clone(),
+        };
+        let winner = if rng.random_bool(self.parsimony_pressure / 2.0) { smaller } else { larger };
+        return winner.genotype().clone();
+    }
+}
+
+
+/// Indices of population ordered from the best to the worst fitness, NaN last.
+fn ranking(population: &[TreeIndividual<TreeGenotype>], direction: Direction) -> Vec<usize> {
+    let mut order = (0..population.len()).collect::<Vec<usize>>();
+    order.sort_by(|&a, &b| direction.compare(population[a].phenotype(), population[b].phenotype()));
+    return order;
+}
+
+/// Selection weights `1 / (1 + d)`, where `d` is the distance from the best fitness, and 0.0 for
+/// NaN or infinitely bad fitness.
+///
+/// Best individual gets weight 1.0 and weights do not change if all fitness values are shifted by
+/// a constant, so negative values are handled as well. Infinitely good fitness is the best one,
+/// so if present only such individuals get non-zero weight.
+fn proportionate_weights(population: &[TreeIndividual<TreeGenotype>], direction: Direction) -> Vec<f64> {
+    let keys = population.iter().map(|i| direction.key(i.phenotype())).collect::<Vec<f64>>();
+    let best = keys.iter().copied().fold(f64::INFINITY, f64::min);
+    if best == f64::NEG_INFINITY {
+        return keys.iter().map(|&k| if k == f64::NEG_INFINITY { 1.0 } else { 0.0 }).collect();
+    }
+    return keys.iter().map(|&k| if k.is_finite() { 1.0 / (1.0 + k - best) } else { 0.0 }).collect();
+}
+
+/// Index of the slot of cumulative weights `pointer` falls into.
+fn spin(cumulative: &[f64], pointer: f64) -> usize {
+    return cumulative.partition_point(|&total| total <= pointer).min(cumulative.len() - 1);
+}
+
+/// Draws index with probability proportional to its weight, uniformly if all weights are zero.
+///
+/// # Panics
+/// * If `weights` is empty
+fn sample_weighted<R: Rng>(rng: &mut R, weights: &[f64]) -> usize {
+    assert!(!weights.is_empty(), "Selection from empty population failed!");
+    let cumulative = weights.iter().scan(0.0, |total, &w| { *total += w; return Some(*total); }).collect::<Vec<f64>>();
+    let total = cumulative[cumulative.len() - 1];
+    if total <= 0.0 { return rng.random_range(0..weights.len()); }
+    return spin(&cumulative, rng.random_range(0.0..total));
+}
+
+/// Roulette-wheel (fitness proportionate) selection operator.
+///
+/// Each individual is selected with probability proportional to `1 / (1 + d)`, where `d` is how
+/// much worse its fitness is than the best one in population: the best individual has weight 1.0
+/// and an individual worse by 1.0 half of it, both when minimizing and maximizing. Weights depend
+/// on the scale of fitness, for scale-independent pressure see [`LinearRankSelection`].
+/// Individuals with NaN or infinitely bad fitness are never selected, unless the whole population
+/// is invalid and selection is uniform. Infinitely good fitness counts as the best, individuals
+/// having it are the only ones selected.
+///
+/// # Examples
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha8Rng;
+/// use mycoforge::common::objectives::Direction;
+/// use mycoforge::common::traits::{Individual, Selector};
+/// use mycoforge::tree::core::{individual::TreeIndividual, tree::TreeGenotype};
+/// use mycoforge::tree::operators::select::RouletteSelection;
+///
+/// let population = TreeIndividual::from_vecs(&vec![TreeGenotype::default(); 3], &[0.0, 1.0, f64::NAN]);
+/// let mut rng = ChaCha8Rng::seed_from_u64(42);
+///
+/// let _ = RouletteSelection::new().select(&mut rng, &population, Direction::Minimize);
+/// ```
+pub struct RouletteSelection {}
+
+impl RouletteSelection {
+    pub fn new() -> Self { return Self {}; }
+}
+
+impl Default for RouletteSelection {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl Selector<TreeGenotype> for RouletteSelection {
+    type I = TreeIndividual<TreeGenotype>;
+
+    fn select<R: Rng>(&self, rng: &mut R, population: &[TreeIndividual<TreeGenotype>], direction: Direction) -> TreeGenotype {
+        let winner = sample_weighted(rng, &proportionate_weights(population, direction));
+        return population[winner].genotype().clone();
+    }
+}
+
+/// Stochastic universal sampling (SUS) operator.
+///
+/// Uses the same weights as [`RouletteSelection`], but [`select_many`][`Selector::select_many`]
+/// spins the wheel once with `count` evenly spaced pointers (Baker, 1987). Every individual is then
+/// selected within one of its expected number of times, removing the sampling noise of repeated
+/// roulette spins. Selected genotypes are shuffled, so consecutive ones can be paired as parents.
+/// Optimizers request parents in pairs, a single [`select`][`Selector::select`] equals a roulette
+/// spin.
+///
+/// # Examples
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha8Rng;
+/// use mycoforge::common::objectives::Direction;
+/// use mycoforge::common::traits::{Individual, Selector};
+/// use mycoforge::tree::core::{individual::TreeIndividual, tree::TreeGenotype};
+/// use mycoforge::tree::operators::select::StochasticUniversalSampling;
+///
+/// let population = TreeIndividual::from_vecs(&vec![TreeGenotype::default(); 3], &[0.0, 1.0, 2.0]);
+/// let mut rng = ChaCha8Rng::seed_from_u64(42);
+///
+/// assert_eq!(StochasticUniversalSampling::new().select_many(&mut rng, &population, 4, Direction::Minimize).len(), 4);
+/// ```
+pub struct StochasticUniversalSampling {}
+
+impl StochasticUniversalSampling {
+    pub fn new() -> Self { return Self {}; }
+}
+
+impl Default for StochasticUniversalSampling {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl Selector<TreeGenotype> for StochasticUniversalSampling {
+    type I = TreeIndividual<TreeGenotype>;
+
+    fn select<R: Rng>(&self, rng: &mut R, population: &[TreeIndividual<TreeGenotype>], direction: Direction) -> TreeGenotype {
+        let winner = sample_weighted(rng, &proportionate_weights(population, direction));
+        return population[winner].genotype().clone();
+    }
+
+    fn select_many<R: Rng>(
+        &self, rng: &mut R, population: &[TreeIndividual<TreeGenotype>], count: usize, direction: Direction
+    ) -> Vec<TreeGenotype> {
+        if count == 0 { return Vec::new(); }
+        assert!(!population.is_empty(), "Selection from empty population failed!");
+        let mut weights = proportionate_weights(population, direction);
+        if weights.iter().all(|&w| w <= 0.0) { weights = vec![1.0; population.len()]; }
+        let cumulative = weights.iter().scan(0.0, |total, &w| { *total += w; return Some(*total); }).collect::<Vec<f64>>();
+
+        let spacing = cumulative[cumulative.len() - 1] / count as f64;
+        let start = rng.random_range(0.0..spacing);
+        let mut selected = (0..count)
+            .map(|k| population[spin(&cumulative, start + k as f64 * spacing)].genotype().clone())
+            .collect::<Vec<TreeGenotype>>();
+        selected.shuffle(rng);
+        return selected;
+    }
+}
+
+/// Linear rank selection operator.
+///
+/// Population is sorted from the best to the worst fitness and the individual at rank `i`
+/// (0 is best) out of `n` is selected with probability `(s - (2s - 2) * i / (n - 1)) / n`, where
+/// pressure `s` is the expected number of copies of the best individual (Baker, 1985). Pressure
+/// 1.0 is uniform selection, 2.0 gives the worst individual zero probability. Only the order of
+/// fitness values matters, not their scale; NaN ranks last.
+///
+/// # Fields
+/// * `pressure: f64` - selection pressure in [1.0, 2.0] range, 1.5 by default
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::operators::select::LinearRankSelection;
+///
+/// let selection = LinearRankSelection::new(1.8).expect("Failed to create selection!");
+///
+/// assert_eq!(selection.pressure(), 1.8);
+/// assert!(LinearRankSelection::new(2.5).is_err());
+/// ```
+pub struct LinearRankSelection {
+    pressure: f64
+}
+
+impl LinearRankSelection {
+    /// Creates new LinearRankSelection operator.
+    ///
+    /// # Arguments
+    /// * `pressure: f64` - expected number of copies of the best individual
+    ///
+    /// # Returns
+    /// * `Result<Self, SelectionError>` - new operator or error if pressure is outside
+    ///   [1.0, 2.0] range
+    pub fn new(pressure: f64) -> Result<Self, SelectionError> {
+        if !(1.0..=2.0).contains(&pressure) { return Err(SelectionError::InvalidPressure(pressure)); }
+        return Ok(Self { pressure });
+    }
+
+    pub fn pressure(&self) -> f64 { return self.pressure; }
+}
+
+impl Default for LinearRankSelection {
+    fn default() -> Self { return Self { pressure: 1.5 }; }
+}
+
+impl Selector<TreeGenotype> for LinearRankSelection {
+    type I = TreeIndividual<TreeGenotype>;
+
+    fn select<R: Rng>(&self, rng: &mut R, population: &[TreeIndividual<TreeGenotype>], direction: Direction) -> TreeGenotype {
+        let ranking = ranking(population, direction);
+        let last = (ranking.len().max(2) - 1) as f64;
+        let weights = (0..ranking.len())
+            .map(|rank| self.pressure - (2.0 * self.pressure - 2.0) * rank as f64 / last)
+            .collect::<Vec<f64>>();
+        return population[ranking[sample_weighted(rng, &weights)]].genotype().clone();
+    }
+}
+
+/// Exponential rank selection operator.
+///
+/// Population is sorted from the best to the worst fitness and the individual at rank `i`
+/// (0 is best) is selected with probability proportional to `base^i`. The smaller the base, the
+/// stronger the pressure: 1.0 would be uniform selection, 0.5 halves the probability with every
+/// rank. Unlike [`LinearRankSelection`] it can concentrate selection on the top few individuals of
+/// large populations. NaN ranks last.
+///
+/// # Fields
+/// * `base: f64` - ratio of probabilities of consecutive ranks in (0.0, 1.0) range, 0.9 by default
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::operators::select::ExponentialRankSelection;
+///
+/// let selection = ExponentialRankSelection::new(0.8).expect("Failed to create selection!");
+///
+/// assert_eq!(selection.base(), 0.8);
+/// assert!(ExponentialRankSelection::new(1.0).is_err());
+/// ```
+pub struct ExponentialRankSelection {
+    base: f64
+}
+
+impl ExponentialRankSelection {
+    /// Creates new ExponentialRankSelection operator.
+    ///
+    /// # Arguments
+    /// * `base: f64` - ratio of probabilities of consecutive ranks
+    ///
+    /// # Returns
+    /// * `Result<Self, SelectionError>` - new operator or error if base is outside (0.0, 1.0) range
+    pub fn new(base: f64) -> Result<Self, SelectionError> {
+        if !(base > 0.0 && base < 1.0) { return Err(SelectionError::InvalidPressure(base)); }
+        return Ok(Self { base });
+    }
+
+    pub fn base(&self) -> f64 { return self.base; }
+}
+
+impl Default for ExponentialRankSelection {
+    fn default() -> Self { return Self { base: 0.9 }; }
+}
+
+impl Selector<TreeGenotype> for ExponentialRankSelection {
+    type I = TreeIndividual<TreeGenotype>;
+
+    fn select<R: Rng>(&self, rng: &mut R, population: &[TreeIndividual<TreeGenotype>], direction: Direction) -> TreeGenotype {
+        let ranking = ranking(population, direction);
+        let weights = (0..ranking.len()).map(|rank| self.base.powi(rank as i32)).collect::<Vec<f64>>();
+        return population[ranking[sample_weighted(rng, &weights)]].genotype().clone();
+    }
+}
+
+/// Truncation selection operator that selects uniformly among the best individuals.
+///
+/// Only the `proportion` of population with the best fitness (at least one individual) can be
+/// selected, all of them with equal probability. NaN ranks last.
+///
+/// # Fields
+/// * `proportion: f64` - selected fraction of population in (0.0, 1.0] range, 0.5 by default
+///
+/// # Examples
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha8Rng;
+/// use mycoforge::common::objectives::Direction;
+/// use mycoforge::common::traits::{Individual, Selector};
+/// use mycoforge::tree::core::{individual::TreeIndividual, tree::TreeGenotype};
+/// use mycoforge::tree::operators::select::TruncationSelection;
+///
+/// let population = TreeIndividual::from_vecs(&vec![TreeGenotype::default(); 4], &[3.0, 1.0, 2.0, 0.0]);
+/// let selection = TruncationSelection::new(0.5).expect("Failed to create selection!");
+/// let mut rng = ChaCha8Rng::seed_from_u64(42);
+///
+/// let _ = selection.select(&mut rng, &population, Direction::Minimize);
+/// assert!(TruncationSelection::new(0.0).is_err());
+/// ```
+pub struct TruncationSelection {
+    proportion: f64
+}
+
+impl TruncationSelection {
+    /// Creates new TruncationSelection operator.
+    ///
+    /// # Arguments
+    /// * `proportion: f64` - fraction of best individuals eligible for selection
+    ///
+    /// # Returns
+    /// * `Result<Self, SelectionError>` - new operator or error if proportion is outside
+    ///   (0.0, 1.0] range
+    pub fn new(proportion: f64) -> Result<Self, SelectionError> {
+        if !(proportion > 0.0 && proportion <= 1.0) { return Err(SelectionError::InvalidProportion(proportion)); }
+        return Ok(Self { proportion });
+    }
+
+    pub fn proportion(&self) -> f64 { return self.proportion; }
+}
+
+impl Default for TruncationSelection {
+    fn default() -> Self { return Self { proportion: 0.5 }; }
+}
+
+impl Selector<TreeGenotype> for TruncationSelection {
+    type I = TreeIndividual<TreeGenotype>;
+
+    fn select<R: Rng>(&self, rng: &mut R, population: &[TreeIndividual<TreeGenotype>], direction: Direction) -> TreeGenotype {
+        let ranking = ranking(population, direction);
+        let eligible = ((self.proportion * ranking.len() as f64).ceil() as usize).max(1);
+        let winner = ranking[..eligible].choose(rng).expect("Truncation selection failed!");
+        return population[*winner].genotype().clone();
+    }
+}
+
+/// Error used to compare cases, NaN counts as the worst possible error.
+fn case_error(individual: &TreeIndividual<TreeGenotype>, case: usize) -> f64 {
+    let error = individual.cases()[case];
+    return if error.is_nan() { f64::INFINITY } else { error };
+}
+
+/// Median of values, average of the two middle values for even length.
+fn median(values: &mut [f64]) -> f64 {
+    let len = values.len();
+    let (lower, upper, _) = values.select_nth_unstable_by(len / 2, f64::total_cmp);
+    if len % 2 == 1 { return *upper; }
+    return (lower.iter().copied().fold(f64::NEG_INFINITY, f64::max) + *upper) / 2.0;
+}
+
+/// Computes median absolute deviation (MAD) of finite errors.
+///
+/// MAD is the median of absolute differences between errors and their median, a spread estimate
+/// robust to outliers. Non-finite errors are ignored.
+///
+/// # Arguments
+/// * `errors: &[f64]` - errors of the population on a single case
+///
+/// # Returns
+/// * `f64` - median absolute deviation, 0.0 if there are no finite errors
+///
+/// # Examples
+/// ```
+/// use mycoforge::tree::operators::select::median_absolute_deviation;
+///
+/// assert_eq!(median_absolute_deviation(&[1.0, 2.0, 3.0, 4.0, 100.0]), 1.0);
+/// assert_eq!(median_absolute_deviation(&[f64::INFINITY, f64::NAN]), 0.0);
+/// ```
+pub fn median_absolute_deviation(errors: &[f64]) -> f64 {
+    let mut values = errors.iter().copied().filter(|error| error.is_finite()).collect::<Vec<f64>>();
+    if values.is_empty() { return 0.0; }
+    let center = median(&mut values);
+    let mut deviations = values.iter().map(|value| (value - center).abs()).collect::<Vec<f64>>();
+    return median(&mut deviations);
+}
+
+/// Runs lexicase filtering and returns genotype of randomly chosen survivor.
+///
+/// Cases are visited in random order, each keeps only candidates whose error is within
+/// `epsilon(case)` of the best candidate error on that case.
+fn lexicase<R: Rng>(
+    rng: &mut R, population: &[TreeIndividual<TreeGenotype>], mut epsilon: impl FnMut(usize) -> f64
+) -> TreeGenotype {
+    let cases = population.first().expect("Lexicase selection failed!").cases().len();
+    if cases == 0 || population.iter().any(|individual| individual.cases().len() != cases) {
+        error!("{}", SelectionError::MissingCaseErrors);
+        panic!("{}", SelectionError::MissingCaseErrors);
+    }
+
+    let mut order = (0..cases).collect::<Vec<usize>>();
+    order.shuffle(rng);
+    let mut candidates = (0..population.len()).collect::<Vec<usize>>();
+    for case in order {
+        if candidates.len() == 1 { break; }
+        let best = candidates.iter().map(|&i| case_error(&population[i], case)).fold(f64::INFINITY, f64::min);
+        let threshold = best + epsilon(case);
+        candidates.retain(|&i| case_error(&population[i], case) <= threshold);
+    }
+
+    let winner = *candidates.choose(rng).expect("Lexicase selection failed!");
+    return population[winner].genotype().clone();
+}
+
+/// Lexicase selection operator that filters population by errors on individual cases.
+///
+/// Cases (data rows) are shuffled and the population is filtered one case at a time, keeping only
+/// individuals with the lowest error on it, until a single individual remains or cases run out;
+/// ties are broken at random. Individuals that solve different parts of the problem are thus
+/// selected, which preserves diversity (Spector, 2012). Suited to discrete errors, e.g.
+/// classification, for continuous errors see [`EpsilonLexicaseSelection`].
+///
+/// # Panics
+/// * If individuals lack per-case errors, see
+///   [`TreeIndividual::with_cases`][`crate::tree::core::individual::TreeIndividual::with_cases`]
+///
+/// # Examples
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha8Rng;
+/// use mycoforge::common::objectives::Direction;
+/// use mycoforge::common::traits::Selector;
+/// use mycoforge::tree::core::{individual::TreeIndividual, tree::TreeGenotype};
+/// use mycoforge::tree::operators::select::LexicaseSelection;
+///
+/// let population = vec![
+///     TreeIndividual::new(TreeGenotype::default(), 1.0).with_cases(vec![0.0, 2.0]),
+///     TreeIndividual::new(TreeGenotype::default(), 1.0).with_cases(vec![2.0, 0.0]),
+/// ];
+/// let selection = LexicaseSelection::new();
+/// let mut rng = ChaCha8Rng::seed_from_u64(42);
+///
+/// assert!(selection.uses_cases());
+/// let _ = selection.select(&mut rng, &population, Direction::Minimize);
+/// ```
+pub struct LexicaseSelection {}
+
+impl LexicaseSelection {
+    pub fn new() -> Self { return Self {}; }
+}
+
+impl Default for LexicaseSelection {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl Selector<TreeGenotype> for LexicaseSelection {
+    type I = TreeIndividual<TreeGenotype>;
+
+    fn select<R: Rng>(&self, rng: &mut R, population: &[TreeIndividual<TreeGenotype>], _direction: Direction) -> TreeGenotype {
+        return lexicase(rng, population, |_| 0.0);
+    }
+
+    fn uses_cases(&self) -> bool { return true; }
+}
+
+/// Epsilon-lexicase selection operator for continuous errors.
+///
+/// Works as [`LexicaseSelection`], but on every case keeps all individuals whose error is within
+/// epsilon of the best one. Epsilon of a case is the
+/// [`median absolute deviation`][`median_absolute_deviation`] of errors of the whole population
+/// on that case (La Cava et al., 2016), so it adapts to the scale of errors without parameters.
+/// Epsilons are computed only for cases actually visited, at most once per
+/// [`select_many`][`Selector::select_many`] call, so selecting many parents from the same
+/// population at once is much cheaper than repeated [`select`][`Selector::select`].
+///
+/// # Panics
+/// * If individuals lack per-case errors, see
+///   [`TreeIndividual::with_cases`][`crate::tree::core::individual::TreeIndividual::with_cases`]
+///
+/// # Examples
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha8Rng;
+/// use mycoforge::common::objectives::Direction;
+/// use mycoforge::common::traits::Selector;
+/// use mycoforge::tree::core::{individual::TreeIndividual, tree::TreeGenotype};
+/// use mycoforge::tree::operators::select::EpsilonLexicaseSelection;
+///
+/// let population = vec![
+///     TreeIndividual::new(TreeGenotype::default(), 1.0).with_cases(vec![0.1, 2.0]),
+///     TreeIndividual::new(TreeGenotype::default(), 1.0).with_cases(vec![0.2, 0.0]),
+/// ];
+/// let selection = EpsilonLexicaseSelection::new();
+/// let mut rng = ChaCha8Rng::seed_from_u64(42);
+///
+/// assert!(selection.uses_cases());
+/// let _ = selection.select(&mut rng, &population, Direction::Minimize);
+/// ```
+pub struct EpsilonLexicaseSelection {}
+
+impl EpsilonLexicaseSelection {
+    pub fn new() -> Self { return Self {}; }
+}
+
+impl Default for EpsilonLexicaseSelection {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl Selector<TreeGenotype> for EpsilonLexicaseSelection {
+    type I = TreeIndividual<TreeGenotype>;
+
+    fn select<R: Rng>(&self, rng: &mut R, population: &[TreeIndividual<TreeGenotype>], direction: Direction) -> TreeGenotype {
+        return self.select_many(rng, population, 1, direction).remove(0);
+    }
+
+    fn select_many<R: Rng>(
+        &self, rng: &mut R, population: &[TreeIndividual<TreeGenotype>], count: usize, _direction: Direction
+    ) -> Vec<TreeGenotype> {
+        let cases = population.first().map_or(0, |individual| individual.cases().len());
+        let mut epsilons: Vec<Option<f64>> = vec![None; cases];
+        return (0..count)
+            .map(|_| lexicase(rng, population, |case| *epsilons[case].get_or_insert_with(|| {
+                let errors = population.iter().map(|individual| individual.cases()[case]).collect::<Vec<f64>>();
+                return median_absolute_deviation(&errors);
+            })))
+            .collect();
+    }
+
+    fn uses_cases(&self) -> bool { return true; }
+}
@@ -0,0 +1,211 @@
+//! Algebraic simplification of trees.
+//!
+//! This module provides [`Simplifier`] for turning evolved
+//! [`TreeGenotype`][`crate::tree::core::tree::TreeGenotype`]s into compact equivalent models and
+//! [`SimplifyingMutation`] for simplifying offspring during evolution.
+//!
+//! Constants are folded by calling the functions of the operator map, so protected semantics of
+//! [`symbolic`][`crate::operators::functions::symbolic`] operators (e.g. `div` returning 1.0 for
+//! divisors close to zero) are reproduced exactly. Algebraic rules are applied to functions named
+//! `+`, `-`, `*` and `/`, which are assumed to follow the semantics of `add`, `sub`, `mul` and
+//! `div`, and assume finite operand values.
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use crate::common::traits::Mutator;
+use crate::common::types::VectorFunction;
+use crate::operators::functions::symbolic::Float;
+use crate::operators::sampler::OperatorSampler;
+use crate::tree::core::node::Node;
+use crate::tree::core::tree::TreeGenotype;
+
+/// Recursive form of a tree used during simplification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Expr {
+    node: Node,
+    args: Vec<Expr>
+}
+
+impl Expr {
+    fn from_tree(tree: &TreeGenotype, index: usize) -> Self {
+        let args = tree.children().get(&index)
+            .map(|children| children.iter().map(|&child| Self::from_tree(tree, child)).collect())
+            .unwrap_or_default();
+        return Self { node: tree.arena()[index], args };
+    }
+
+    fn constant(value: f64) -> Self { return Self { node: Node::Constant(value), args: Vec::new() }; }
+
+    fn value(&self) -> Option<f64> {
+        return match self.node {
+            Node::Constant(value) => Some(value),
+            _ => None,
+        };
+    }
+
+    fn flatten(self, arena: &mut Vec<Node>) {
+        arena.push(self.node);
+        self.args.into_iter().for_each(|arg| arg.flatten(arena));
+    }
+
+    /// Key used for canonical ordering of commutative operands, constants are placed last.
+    fn key(&self) -> (bool, String) {
+        let mut arena = Vec::new();
+        self.clone().flatten(&mut arena);
+        return (self.value().is_some(), TreeGenotype::with_arena(arena).to_sexpr());
+    }
+}
+
+/// Simplifies trees by constant folding, identity removal, collapsing nested constants and
+/// canonical ordering of commutative operators.
+///
+/// Applied rules:
+/// * functions with only constant arguments are replaced by their value
+/// * operands of nested `+` and `*` are collected, their constants folded into one constant and
+///   the rest ordered canonically, e.g. `(2 + x1) + (x0 + 3)` becomes `x0 + x1 + 5`
+/// * `x + 0`, `x - 0`, `x * 1` and `x / 1` become `x`
+/// * `x * 0` and `x - x` become `0`
+/// * `x / x` and `x / c` for `|c|` below epsilon become `1`, as protected division does; `0 / x`
+///   is kept since `x` may be close to zero
+///
+/// # Fields
+/// * `map: HashMap<String, (usize, VectorFunction)>` - mapping of function names to their
+///   implementations, see [`create_map`][`crate::operators::set::Operators::create_map`]
+///
+/// # Examples
+/// ```
+/// use mycoforge::operators::builder::OperatorsBuilder;
+/// use mycoforge::operators::functions::symbolic::{add, mul, div};
+/// use mycoforge::tree::core::tree::TreeGenotype;
+/// use mycoforge::tree::operators::simplify::Simplifier;
+///
+/// let operators = OperatorsBuilder::default()
+///     .add_function("+", add, 2, 1.0 / 3.0).expect("Failed to add function!")
+///     .add_function("*", mul, 2, 1.0 / 3.0).expect("Failed to add function!")
+///     .add_function("/", div, 2, 1.0 / 3.0).expect("Failed to add function!")
+///     .add_variable("x", 1.0).expect("Failed to add variable!")
+///     .build().expect("Failed to build operators!");
+/// let simplifier = Simplifier::new(operators.create_map());
+///
+/// let tree = TreeGenotype::from_infix("(x * 1 + 2) + x / 0 * 3", &operators).expect("Failed to parse tree!");
+///
+/// assert_eq!(simplifier.simplify(&tree).to_infix(), "x0 + 5");
+/// ```
+pub struct Simplifier {
+    map: HashMap<String, (usize, VectorFunction)>
+}
+
+impl Simplifier {
+    pub fn new(map: HashMap<String, (usize, VectorFunction)>) -> Self { return Self { map }; }
+
+    pub fn map(&self) -> &HashMap<String, (usize, VectorFunction)> { return &self.map; }
+
+    /// Returns simplified copy of the tree.
+    ///
+    /// # Arguments
+    /// * `tree: &TreeGenotype` - [`TreeGenotype`][`crate::tree::core::tree::TreeGenotype`] to simplify
+    ///
+    /// # Returns
+    /// * `TreeGenotype` - equivalent tree with no more nodes than the original
+    pub fn simplify(&self, tree: &TreeGenotype) -> TreeGenotype {
+        if tree.arena().is_empty() { return tree.clone(); }
+
+        let mut arena = Vec::with_capacity(tree.arena().len());
+        self.simplify_expr(Expr::from_tree(tree, 0)).flatten(&mut arena);
+        return TreeGenotype::with_arena(arena);
+    }
+
+    fn simplify_expr(&self, expr: Expr) -> Expr {
+        let Expr { node, args } = expr;
+        let args = args.into_iter().map(|arg| self.simplify_expr(arg)).collect::<Vec<Expr>>();
+
+        if !args.is_empty() && args.iter().all(|arg| arg.value().is_some()) {
+            let values = args.iter().filter_map(Expr::value).collect::<Vec<f64>>();
+            if let Some(value) = self.apply(&node, &values) { return Expr::constant(value); }
+        }
+        if node.arity() != 2 { return Expr { node, args }; }
+
+        let mut args = args.into_iter();
+        let (left, right) = (args.next().expect("Binary node should have two arguments!"),
+            args.next().expect("Binary node should have two arguments!"));
+        return match node.name().as_str() {
+            "+" => self.simplify_commutative(node, left, right, 0.0),
+            "*" => self.simplify_commutative(node, left, right, 1.0),
+            "-" if left == right => Expr::constant(0.0),
+            "-" if right.value() == Some(0.0) => left,
+            "/" if left == right => Expr::constant(1.0),
+            "/" if right.value().is_some_and(|value| value.abs() < <f64 as Float>::epsilon()) => Expr::constant(1.0),
+            "/" if right.value() == Some(1.0) => left,
+            _ => Expr { node, args: vec![left, right] },
+        };
+    }
+
+    /// Collects operands of nested applications of `node`, folds their constants and rebuilds
+    /// canonically ordered left-deep chain with the constant last.
+    fn simplify_commutative(&self, node: Node, left: Expr, right: Expr, identity: f64) -> Expr {
+        let mut operands = Vec::new();
+        let mut pending = vec![right, left];
+        while let Some(operand) = pending.pop() {
+            if operand.node == node {
+                pending.extend(operand.args.into_iter().rev());
+            } else {
+                operands.push(operand);
+            }
+        }
+
+        let (constants, mut operands): (Vec<Expr>, Vec<Expr>) = operands.into_iter()
+            .partition(|operand| operand.value().is_some());
+        let values = constants.iter().filter_map(Expr::value).collect::<Vec<f64>>();
+        let mut constant = None;
+        if let Some((&first, rest)) = values.split_first() {
+            match rest.iter().try_fold(first, |folded, &value| self.apply(&node, &[folded, value])) {
+                Some(value) if value == identity => {},
+                // Multiplication by zero
+                Some(value) if identity == 1.0 && value == 0.0 => return Expr::constant(0.0),
+                Some(value) => constant = Some(Expr::constant(value)),
+                None => operands.extend(constants),
+            }
+        }
+
+        operands.sort_by_cached_key(Expr::key);
+        operands.extend(constant);
+        let mut operands = operands.into_iter();
+        let first = operands.next().unwrap_or_else(|| Expr::constant(identity));
+        return operands.fold(first, |chain, operand| Expr { node, args: vec![chain, operand] });
+    }
+
+    /// Calls function of the node on single row, `None` if node is not a function of the map.
+    fn apply(&self, node: &Node, values: &[f64]) -> Option<f64> {
+        if !node.is_function() { return None; }
+        let (arity, function) = self.map.get(&node.name())?;
+        if *arity != values.len() { return None; }
+
+        let columns = values.iter().map(std::slice::from_ref).collect::<Vec<&[f64]>>();
+        return function(&columns).first().copied();
+    }
+}
+
+/// Wraps mutation operator, simplifying every mutant it produces.
+///
+/// # Fields
+/// * `mutator: M` - wrapped [`mutation operator`][`crate::common::traits::Mutator`]
+/// * `simplifier: Simplifier` - [`Simplifier`] applied to mutants
+pub struct SimplifyingMutation<M: Mutator<TreeGenotype>> {
+    mutator: M,
+    simplifier: Simplifier
+}
+
+impl<M: Mutator<TreeGenotype>> SimplifyingMutation<M> {
+    pub fn new(mutator: M, simplifier: Simplifier) -> Self { return Self { mutator, simplifier }; }
+
+    pub fn mutator(&self) -> &M { return &self.mutator; }
+    pub fn simplifier(&self) -> &Simplifier { return &self.simplifier; }
+}
+
+impl<M: Mutator<TreeGenotype>> Mutator<TreeGenotype> for SimplifyingMutation<M> {
+    fn variate<R: Rng>(&self, rng: &mut R, individual: &TreeGenotype, sampler: &OperatorSampler) -> TreeGenotype {
+        let mutant = self.mutator.variate(rng, individual, sampler);
+        return self.simplifier.simplify(&mutant);
+    }
+}
@@ -0,0 +1,167 @@
+use std::path::PathBuf;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rstest::{fixture, rstest};
+
+use mycoforge::common::traits::Individual;
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::set::Operators;
+use mycoforge::optimizers::checkpoint::{Checkpoint, CheckpointError, Checkpointer};
+use mycoforge::optimizers::ga::{EA, EABuilder, EAComponents, RunResult};
+use mycoforge::optimizers::replacement::Replacement;
+use mycoforge::optimizers::termination::{MaxGenerations, TerminationCriterion};
+
+use mycoforge::tree::{
+    core::{individual::TreeIndividual, tree::TreeGenotype},
+    fitness::evaluate::MSE,
+    operators::{crossover::SubtreeCrossover, init::Grow, mutation::SubtreeMutation, select::TournamentSelection}
+};
+
+type TreeCheckpoint = Checkpoint<TreeIndividual<TreeGenotype>, ChaCha8Rng>;
+
+struct Components;
+impl EAComponents<TreeGenotype> for Components {
+    type I = TreeIndividual<TreeGenotype>;
+    type Init = Grow;
+    type Mut = SubtreeMutation;
+    type Cross = SubtreeCrossover;
+    type Eval = MSE;
+    type Sel = TournamentSelection;
+}
+
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_registered("+", "add", 0.25).expect("Failed to add function!")
+        .add_registered("-", "sub", 0.25).expect("Failed to add function!")
+        .add_registered("*", "mul", 0.25).expect("Failed to add function!")
+        .add_registered("/", "protected_div", 0.25).expect("Failed to add function!")
+        .add_variable("x", 0.8).expect("Failed to add variable!")
+        .add_constant(1.0, 0.2).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+}
+
+fn build_ea(replacement: Replacement, cache_capacity: Option<usize>) -> EA<Components, TreeGenotype> {
+    let operators = sample_operators();
+    let builder = EABuilder::<Components, TreeGenotype>::new()
+        .set_initializer(Grow::new(1, 3))
+        .set_mutator(SubtreeMutation::new(0.2, (1, 2)).expect("Failed to create mutation scheme!"))
+        .set_crossoverer(SubtreeCrossover::new(0.9).expect("Failed to create crossover scheme!"))
+        .set_evaluator(MSE::new())
+        .set_selector(TournamentSelection::new(3))
+        .set_sampler(operators.sampler())
+        .set_map(operators.create_map())
+        .set_replacement(replacement);
+    let builder = match cache_capacity {
+        Some(capacity) => builder.set_cache_capacity(capacity),
+        None => builder
+    };
+    return builder.build().expect("Failed to build EA!");
+}
+
+fn max_generations(generations: usize) -> Vec<Box<dyn TerminationCriterion>> {
+    return vec![Box::new(MaxGenerations::new(generations))];
+}
+
+/// Unique checkpoint path per test, tests run in parallel.
+fn checkpoint_path(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("mycoforge_checkpoint_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).expect("Failed to create directory!");
+    return directory.join(format!("{}.ckpt", name));
+}
+
+#[fixture]
+fn sample_dataset() -> Dataset {
+    let xs: Vec<f64> = (0..=20).map(|i| i as f64 / 10.0 - 1.0).collect();
+    let ys = xs.iter().map(|&v| v.powi(3) - v / 2.0).collect::<Vec<f64>>();
+
+    return Dataset::new(vec!["x".to_string()], "y".to_string(), vec![xs], ys);
+}
+
+fn assert_same_trajectory(found: &RunResult<TreeIndividual<TreeGenotype>>, expected: &RunResult<TreeIndividual<TreeGenotype>>) {
+    assert_eq!(found.history(), expected.history());
+    assert_eq!(found.evaluations(), expected.evaluations());
+    assert_eq!(found.best().genotype(), expected.best().genotype());
+    assert_eq!(found.best().phenotype().to_bits(), expected.best().phenotype().to_bits());
+    assert_eq!(found.population().len(), expected.population().len());
+    for (found, expected) in found.population().iter().zip(expected.population()) {
+        assert_eq!(found.genotype(), expected.genotype());
+        assert_eq!(found.phenotype().to_bits(), expected.phenotype().to_bits());
+    }
+}
+
+#[rstest]
+#[case("generational", Replacement::Generational, None, 4, 2)]
+#[case("elitism", Replacement::Elitism(2), None, 5, 2)]
+#[case("plus", Replacement::MuPlusLambda, Some(1000), 3, 3)]
+#[case("worst", Replacement::ReplaceWorst, None, 1, 5)]
+fn test_resume_reproduces_run(
+    #[case] name: &str, #[case] replacement: Replacement, #[case] cache_capacity: Option<usize>,
+    #[case] interrupted_at: usize, #[case] interval: usize, sample_dataset: Dataset
+) {
+    let ea = build_ea(replacement, cache_capacity);
+    let expected = ea.run(&mut ChaCha8Rng::seed_from_u64(7), 30, &sample_dataset, &max_generations(12));
+
+    let path = checkpoint_path(name);
+    let checkpointer = Checkpointer::new(&path, interval).expect("Failed to create checkpointer!");
+    // Interrupted run, the last checkpoint is written when it stops
+    ea.run_checkpointed(&mut ChaCha8Rng::seed_from_u64(7), 30, &sample_dataset, &max_generations(interrupted_at), &checkpointer);
+    let checkpoint = TreeCheckpoint::load(&path).expect("Failed to load checkpoint!");
+    assert_eq!(checkpoint.generation(), interrupted_at);
+
+    // Fresh EA, as if resumed in another process
+    let resumed = build_ea(replacement, cache_capacity).resume(checkpoint, &sample_dataset, &max_generations(12));
+
+    assert_same_trajectory(&resumed, &expected);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[rstest]
+fn test_checkpoint_contents(sample_dataset: Dataset) {
+    let ea = build_ea(Replacement::Generational, None);
+    let path = checkpoint_path("contents");
+    let checkpointer = Checkpointer::new(&path, 3).expect("Failed to create checkpointer!");
+    let mut rng = ChaCha8Rng::seed_from_u64(11);
+
+    let result = ea.run_checkpointed(&mut rng, 20, &sample_dataset, &max_generations(4), &checkpointer);
+    let checkpoint = TreeCheckpoint::load(&path).expect("Failed to load checkpoint!");
+
+    assert_eq!(checkpoint.generation(), 4);
+    assert_eq!(checkpoint.evaluations(), result.evaluations());
+    assert_eq!(checkpoint.history(), result.history());
+    assert_eq!(checkpoint.best().genotype(), result.best().genotype());
+    assert_eq!(checkpoint.population().len(), 20);
+    assert_eq!(checkpoint.rng(), &rng);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[rstest]
+fn test_resume_checkpointed_extends_run(sample_dataset: Dataset) {
+    let ea = build_ea(Replacement::Elitism(1), None);
+    let path = checkpoint_path("extend");
+    let checkpointer = Checkpointer::new(&path, 4).expect("Failed to create checkpointer!");
+    let expected = ea.run(&mut ChaCha8Rng::seed_from_u64(3), 20, &sample_dataset, &max_generations(10));
+
+    ea.run_checkpointed(&mut ChaCha8Rng::seed_from_u64(3), 20, &sample_dataset, &max_generations(5), &checkpointer);
+    let checkpoint = TreeCheckpoint::load(&path).expect("Failed to load checkpoint!");
+    ea.resume_checkpointed(checkpoint, &sample_dataset, &max_generations(7), &checkpointer);
+    let checkpoint = TreeCheckpoint::load(&path).expect("Failed to load checkpoint!");
+    assert_eq!(checkpoint.generation(), 7);
+    assert_eq!(checkpoint.history().len(), 8);
+
+    let resumed = ea.resume(checkpoint, &sample_dataset, &max_generations(10));
+    assert_same_trajectory(&resumed, &expected);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[rstest]
+fn test_checkpoint_errors() {
+    assert!(matches!(Checkpointer::new("run.ckpt", 0), Err(CheckpointError::InvalidInterval)));
+    assert!(matches!(TreeCheckpoint::load(&checkpoint_path("missing")), Err(CheckpointError::IoError(_))));
+
+    let path = checkpoint_path("corrupted");
+    std::fs::write(&path, b"not a checkpoint").expect("Failed to write file!");
+    assert!(matches!(TreeCheckpoint::load(&path), Err(CheckpointError::SerializationError(_))));
+    let _ = std::fs::remove_file(&path);
+}
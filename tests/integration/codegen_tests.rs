@@ -0,0 +1,241 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use rstest::*;
+
+use mycoforge::common::traits::{Data, Evaluator, Initializer};
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::functions::symbolic::add;
+use mycoforge::operators::set::Operators;
+use mycoforge::tree::core::codegen::{CodegenError, Language};
+use mycoforge::tree::core::tree::TreeGenotype;
+use mycoforge::tree::fitness::compile::Program;
+use mycoforge::tree::fitness::evaluate::MSE;
+use mycoforge::tree::operators::init::Grow;
+
+fn square(args: &[&[f64]]) -> Vec<f64> { return args[0].iter().map(|a| a * a).collect(); }
+
+#[fixture]
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_registered("+", "add", 1.0 / 7.0).expect("Failed to add function!")
+        .add_registered("-", "sub", 1.0 / 7.0).expect("Failed to add function!")
+        .add_registered("*", "mul", 1.0 / 7.0).expect("Failed to add function!")
+        .add_registered("/", "protected_div", 1.0 / 7.0).expect("Failed to add function!")
+        .add_registered("sin", "sin", 1.0 / 7.0).expect("Failed to add function!")
+        .add_registered("cos", "cos", 1.0 / 7.0).expect("Failed to add function!")
+        .add_registered("ln", "protected_ln", 1.0 / 7.0).expect("Failed to add function!")
+        .add_variable("x", 0.35).expect("Failed to add variable!")
+        .add_variable("y", 0.35).expect("Failed to add variable!")
+        .add_constant(0.5, 0.15).expect("Failed to add constant!")
+        .add_constant(-1.5, 0.15).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+}
+
+/// Rows hitting every protected branch: divisors below epsilon, non-positive logarithms and
+/// overflowing products.
+#[fixture]
+fn sample_dataset() -> Dataset {
+    let xs = vec![-2.0, -0.5, 0.0, 1e-7, 0.25, 1.0, 3.0, 1e200, -1e300];
+    let ys = vec![1.0, 0.0, -1.5, 2.0, 0.5, -3.0, 1e-7, 1e200, 7.0];
+    let targets = xs.iter().zip(ys.iter()).map(|(x, y)| x * y + 1.0).collect();
+    return Dataset::new(vec!["x".to_string(), "y".to_string()], "z".to_string(), vec![xs, ys], targets);
+}
+
+#[fixture]
+fn sample_trees(sample_operators: Operators) -> Vec<TreeGenotype> {
+    let mut rng = StdRng::seed_from_u64(42);
+    let sampler = sample_operators.sampler();
+    let mut trees = vec![
+        TreeGenotype::from_infix("ln(x) / (y - y) * (x * y)", &sample_operators).expect("Failed to parse tree!"),
+        TreeGenotype::from_infix("-1.5", &sample_operators).expect("Failed to parse tree!"),
+        TreeGenotype::from_infix("y", &sample_operators).expect("Failed to parse tree!"),
+    ];
+    trees.extend((0..30).map(|_| Grow::new(1, 6).initialize(&mut rng, &sampler)));
+    return trees;
+}
+
+fn working_directory(language: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("mycoforge_codegen_{}_{}", std::process::id(), language));
+    fs::create_dir_all(&directory).expect("Failed to create working directory!");
+    return directory;
+}
+
+fn is_available(program: &str, args: &[&str]) -> bool {
+    return Command::new(program).args(args).output().is_ok_and(|output| output.status.success());
+}
+
+fn run(command: &mut Command) -> String {
+    let output = command.output().expect("Failed to run command!");
+    assert!(output.status.success(), "Command failed: {}", String::from_utf8_lossy(&output.stderr));
+    return String::from_utf8(output.stdout).expect("Output is not UTF-8!");
+}
+
+/// Parses one line of predictions per tree.
+fn parse_predictions(output: &str) -> Vec<Vec<f64>> {
+    return output.lines()
+        .map(|line| line.split_whitespace().map(|value| value.parse::<f64>().expect("Failed to parse prediction!")).collect())
+        .collect();
+}
+
+fn rows(dataset: &Dataset) -> Vec<Vec<f64>> {
+    let (features, _) = dataset.data();
+    return (0..features[0].len()).map(|row| features.iter().map(|column| column[row]).collect()).collect();
+}
+
+/// Checks that predictions of generated code match compiled program row by row and reproduce
+/// fitness of [`MSE`] evaluator.
+fn assert_matches(trees: &[TreeGenotype], predictions: &[Vec<f64>], operators: &Operators, dataset: &Dataset, tolerance: f64) {
+    let map = operators.create_map();
+    let (features, target) = dataset.data();
+    assert_eq!(predictions.len(), trees.len());
+
+    for (tree, found) in trees.iter().zip(predictions.iter()) {
+        let expected = Program::compile(tree, operators).expect("Failed to compile tree!").evaluate(features);
+        assert_eq!(found.len(), expected.len());
+        for (row, (&found, &expected)) in found.iter().zip(expected.iter()).enumerate() {
+            let matches = if expected.is_nan() || found.is_nan() {
+                expected.is_nan() && found.is_nan()
+            } else if tolerance == 0.0 || !expected.is_finite() {
+                found == expected
+            } else {
+                (found - expected).abs() <= tolerance * expected.abs().max(1.0)
+            };
+            assert!(matches, "Prediction of {} differs on row {}! Expected {:?}, found {:?}", tree.to_infix(), row, expected, found);
+        }
+
+        if tolerance == 0.0 {
+            let fitness = found.iter().zip(target.iter()).map(|(t, y)| (t - y).powi(2)).sum::<f64>() / target.len() as f64;
+            let expected = MSE::new().evaluate(tree, dataset, &map);
+            assert!(fitness.to_bits() == expected.to_bits() || (fitness.is_nan() && expected.is_nan()),
+                "Fitness of {} differs! Expected {:?}, found {:?}", tree.to_infix(), expected, fitness
+            );
+        }
+    }
+}
+
+#[rstest]
+fn test_rust_codegen(sample_operators: Operators, sample_dataset: Dataset, sample_trees: Vec<TreeGenotype>) {
+    if !is_available("rustc", &["--version"]) {
+        eprintln!("Skipping Rust code generation test, rustc is not available");
+        return;
+    }
+    let directory = working_directory("rust");
+
+    let mut source = String::new();
+    for (index, tree) in sample_trees.iter().enumerate() {
+        source.push_str(&tree.to_source(Language::Rust, &format!("model_{}", index), &sample_operators).expect("Failed to generate code!"));
+    }
+    let rows = rows(&sample_dataset).iter().map(|row| format!("&{:?}", row)).collect::<Vec<String>>().join(", ");
+    let models = (0..sample_trees.len()).map(|index| format!("model_{}", index)).collect::<Vec<String>>().join(", ");
+    source.push_str(&format!(
+        "\nfn main() {{\n    let rows: [&[f64]; {}] = [{}];\n    let models: [fn(&[f64]) -> f64; {}] = [{}];\n    \
+            for model in models {{\n        let line = rows.iter().map(|row| format!(\"{{:?}}\", model(row))).collect::<Vec<_>>().join(\" \");\n        \
+            println!(\"{{}}\", line);\n    }}\n}}\n",
+        sample_dataset.data().0[0].len(), rows, sample_trees.len(), models
+    ));
+    fs::write(directory.join("model.rs"), source).expect("Failed to write source!");
+
+    run(Command::new("rustc").args(["-O", "--edition", "2021", "-o"]).arg(directory.join("model")).arg(directory.join("model.rs")));
+    let predictions = parse_predictions(&run(&mut Command::new(directory.join("model"))));
+
+    assert_matches(&sample_trees, &predictions, &sample_operators, &sample_dataset, 0.0);
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[rstest]
+fn test_c_codegen(sample_operators: Operators, sample_dataset: Dataset, sample_trees: Vec<TreeGenotype>) {
+    if !is_available("cc", &["--version"]) {
+        eprintln!("Skipping C code generation test, cc is not available");
+        return;
+    }
+    let directory = working_directory("c");
+
+    let mut source = String::from("#include <stdio.h>\n");
+    for (index, tree) in sample_trees.iter().enumerate() {
+        source.push_str(&tree.to_source(Language::C, &format!("model_{}", index), &sample_operators).expect("Failed to generate code!"));
+    }
+    let rows = rows(&sample_dataset).iter()
+        .map(|row| format!("{{{}}}", row.iter().map(|value| format!("{:?}", value)).collect::<Vec<String>>().join(", ")))
+        .collect::<Vec<String>>().join(", ");
+    let models = (0..sample_trees.len()).map(|index| format!("model_{}", index)).collect::<Vec<String>>().join(", ");
+    source.push_str(&format!(
+        "\nint main(void) {{\n    const double rows[][2] = {{{}}};\n    double (*models[])(const double *) = {{{}}};\n    \
+            for (int m = 0; m < {}; m++) {{\n        for (int r = 0; r < {}; r++) printf(\"%.17g \", models[m](rows[r]));\n        \
+            printf(\"\\n\");\n    }}\n    return 0;\n}}\n",
+        rows, models, sample_trees.len(), sample_dataset.data().0[0].len()
+    ));
+    fs::write(directory.join("model.c"), source).expect("Failed to write source!");
+
+    // Contraction into fused multiply-add would change rounding
+    run(Command::new("cc").args(["-O2", "-std=c99", "-ffp-contract=off", "-o"]).arg(directory.join("model")).arg(directory.join("model.c")).arg("-lm"));
+    let predictions = parse_predictions(&run(&mut Command::new(directory.join("model"))));
+
+    assert_matches(&sample_trees, &predictions, &sample_operators, &sample_dataset, 0.0);
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[rstest]
+fn test_python_codegen(sample_operators: Operators, sample_dataset: Dataset, sample_trees: Vec<TreeGenotype>) {
+    if !is_available("python3", &["-c", "import numpy"]) {
+        eprintln!("Skipping Python code generation test, python3 with numpy is not available");
+        return;
+    }
+    let directory = working_directory("python");
+
+    let mut source = String::new();
+    for (index, tree) in sample_trees.iter().enumerate() {
+        source.push_str(&tree.to_source(Language::Python, &format!("model_{}", index), &sample_operators).expect("Failed to generate code!"));
+        source.push_str("\n\n");
+    }
+    let rows = rows(&sample_dataset).iter()
+        .map(|row| format!("[{}]", row.iter().map(|value| format!("{:?}", value)).collect::<Vec<String>>().join(", ")))
+        .collect::<Vec<String>>().join(", ");
+    let models = (0..sample_trees.len()).map(|index| format!("model_{}", index)).collect::<Vec<String>>().join(", ");
+    source.push_str(&format!(
+        "X = np.array([{}])\nfor model in [{}]:\n    print(\" \".join(repr(float(value)) for value in model(X)))\n",
+        rows, models
+    ));
+    let path = directory.join("model.py");
+    fs::write(&path, source).expect("Failed to write source!");
+
+    let predictions = parse_predictions(&run(Command::new("python3").arg(Path::new(&path))));
+
+    // NumPy may use its own vectorized transcendental functions
+    assert_matches(&sample_trees, &predictions, &sample_operators, &sample_dataset, 1e-12);
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[rstest]
+fn test_codegen_errors(sample_operators: Operators) {
+    let operators = OperatorsBuilder::default()
+        .add_function("square", square, 1, 1.0).expect("Failed to add function!")
+        .add_variable("x", 1.0).expect("Failed to add variable!")
+        .build().expect("Failed to build operators!");
+    let tree = TreeGenotype::from_infix("square(x)", &operators).expect("Failed to parse tree!");
+
+    assert_eq!(tree.to_source(Language::C, "model", &operators),
+        Err(CodegenError::UnsupportedFunction("square".to_string())));
+    assert_eq!(tree.to_source(Language::C, "model", &sample_operators),
+        Err(CodegenError::UnknownFunction("square".to_string())));
+    assert_eq!(TreeGenotype::default().to_source(Language::Rust, "model", &sample_operators),
+        Err(CodegenError::EmptyTree));
+}
+
+#[rstest]
+fn test_codegen_requires_registry_key() {
+    // Built-in function added as a plain pointer is indistinguishable from a custom one
+    let operators = OperatorsBuilder::default()
+        .add_function("+", add, 2, 1.0).expect("Failed to add function!")
+        .add_variable("x", 1.0).expect("Failed to add variable!")
+        .build().expect("Failed to build operators!");
+    let tree = TreeGenotype::from_infix("x + x", &operators).expect("Failed to parse tree!");
+
+    assert_eq!(tree.to_source(Language::Python, "model", &operators),
+        Err(CodegenError::UnsupportedFunction("+".to_string())));
+}
@@ -1 +1,6 @@
 mod ea_tests;
+mod run_tests;
+mod steady_state_tests;
+mod codegen_tests;
+mod checkpoint_tests;
+mod nsga2_tests;
@@ -0,0 +1,148 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rstest::{fixture, rstest};
+
+use mycoforge::common::objectives::{dominates, Direction, Objectives};
+use mycoforge::common::traits::Individual;
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::set::Operators;
+use mycoforge::optimizers::nsga2::{CrowdedTournament, NSGA2};
+use mycoforge::optimizers::termination::{MaxGenerations, TerminationCriterion};
+use mycoforge::tree::core::tree::TreeGenotype;
+use mycoforge::tree::fitness::complexity::Size;
+use mycoforge::tree::fitness::evaluate::MSE;
+use mycoforge::tree::operators::{crossover::SubtreeCrossover, init::Grow, mutation::SubtreeMutation};
+
+type TreeNSGA2 = NSGA2<TreeGenotype, Dataset, Grow, SubtreeMutation, SubtreeCrossover>;
+
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_registered("+", "add", 0.25).expect("Failed to add function!")
+        .add_registered("-", "sub", 0.25).expect("Failed to add function!")
+        .add_registered("*", "mul", 0.25).expect("Failed to add function!")
+        .add_registered("/", "protected_div", 0.25).expect("Failed to add function!")
+        .add_variable("x", 0.8).expect("Failed to add variable!")
+        .add_constant(1.0, 0.2).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+}
+
+fn build_nsga2(size_direction: Direction) -> TreeNSGA2 {
+    let operators = sample_operators();
+    let objectives = Objectives::new()
+        .add(MSE::new())
+        .add_with_direction(Size::new(), size_direction);
+    return NSGA2::new(
+        Grow::new(1, 4),
+        SubtreeMutation::new(0.2, (1, 2)).expect("Failed to create mutation scheme!"),
+        SubtreeCrossover::new(0.9).expect("Failed to create crossover scheme!"),
+        objectives, operators.sampler(), operators.create_map()
+    );
+}
+
+fn max_generations(generations: usize) -> Vec<Box<dyn TerminationCriterion>> {
+    return vec![Box::new(MaxGenerations::new(generations))];
+}
+
+#[fixture]
+fn sample_dataset() -> Dataset {
+    let xs: Vec<f64> = (0..=20).map(|i| i as f64 / 10.0 - 1.0).collect();
+    let ys = xs.iter().map(|&v| v.powi(3) - v / 2.0).collect::<Vec<f64>>();
+
+    return Dataset::new(vec!["x".to_string()], "y".to_string(), vec![xs], ys);
+}
+
+#[rstest]
+fn test_run(sample_dataset: Dataset) {
+    let nsga2 = build_nsga2(Direction::Minimize);
+    let result = nsga2.run(&mut ChaCha8Rng::seed_from_u64(5), 40, &sample_dataset, &max_generations(10));
+
+    assert_eq!(result.population().len(), 40);
+    assert_eq!(result.generations(), 10);
+    assert_eq!(result.evaluations(), 40 * 11);
+    assert_eq!(result.directions(), &[Direction::Minimize, Direction::Minimize]);
+
+    // Objective values match a fresh evaluation
+    for individual in result.population() {
+        let expected = nsga2.objectives().evaluate(individual.genotype(), &sample_dataset, nsga2.map());
+        assert_eq!(individual.objectives()[1], expected[1]);
+        assert!(individual.objectives()[0].to_bits() == expected[0].to_bits() || individual.objectives()[0].is_nan());
+    }
+
+    // Front is mutually non-dominated, sorted by error and dominates the rest of the population
+    let front = result.pareto_front();
+    assert!(!front.is_empty());
+    let directions = result.directions();
+    for a in &front {
+        assert_eq!(a.rank(), 0);
+        assert!(front.iter().all(|b| !dominates(b.objectives(), a.objectives(), directions)));
+    }
+    for pair in front.windows(2) { assert!(pair[0].objectives()[0] <= pair[1].objectives()[0]); }
+    for individual in result.population().iter().filter(|i| i.rank() > 0) {
+        assert!(result.population().iter().any(|other| dominates(other.objectives(), individual.objectives(), directions)));
+    }
+
+    // Smallest trees survive as the other end of the trade-off
+    let smallest = front.iter().map(|i| i.objectives()[1]).fold(f64::INFINITY, f64::min);
+    assert_eq!(smallest, 1.0);
+}
+
+#[rstest]
+fn test_elitism(sample_dataset: Dataset) {
+    let nsga2 = build_nsga2(Direction::Minimize);
+    let short = nsga2.run(&mut ChaCha8Rng::seed_from_u64(9), 30, &sample_dataset, &max_generations(3));
+    let history = short.history();
+
+    // Best error never gets worse, survivors include the best of parents and offspring
+    for pair in history.windows(2) {
+        assert!(pair[1].best_fitness() <= pair[0].best_fitness(), "{:?}", history);
+    }
+}
+
+#[rstest]
+fn test_run_is_reproducible(sample_dataset: Dataset) {
+    let nsga2 = build_nsga2(Direction::Minimize).set_selector(CrowdedTournament::new(3));
+    assert_eq!(nsga2.selector().tournament_size(), 3);
+
+    let first = nsga2.run(&mut ChaCha8Rng::seed_from_u64(1), 20, &sample_dataset, &max_generations(4));
+    let second = nsga2.run(&mut ChaCha8Rng::seed_from_u64(1), 20, &sample_dataset, &max_generations(4));
+
+    assert_eq!(first.history(), second.history());
+    for (a, b) in first.population().iter().zip(second.population()) {
+        assert_eq!(a.genotype(), b.genotype());
+    }
+}
+
+#[rstest]
+fn test_maximized_objective(sample_dataset: Dataset) {
+    let nsga2 = build_nsga2(Direction::Maximize);
+    let result = nsga2.run(&mut ChaCha8Rng::seed_from_u64(3), 20, &sample_dataset, &max_generations(5));
+
+    // Largest tree is on the front when size is maximized
+    let largest = result.population().iter().map(|i| i.objectives()[1]).fold(0.0, f64::max);
+    assert!(result.pareto_front().iter().any(|i| i.objectives()[1] == largest));
+}
+
+#[rstest]
+fn test_maximized_first_objective_history(sample_dataset: Dataset) {
+    let operators = sample_operators();
+    let objectives = Objectives::new()
+        .add_with_direction(Size::new(), Direction::Maximize)
+        .add(MSE::new());
+    assert_eq!(objectives.directions(), vec![Direction::Maximize, Direction::Minimize]);
+    let nsga2: TreeNSGA2 = NSGA2::new(
+        Grow::new(1, 4),
+        SubtreeMutation::new(0.2, (1, 2)).expect("Failed to create mutation scheme!"),
+        SubtreeCrossover::new(0.9).expect("Failed to create crossover scheme!"),
+        objectives, operators.sampler(), operators.create_map()
+    );
+    let result = nsga2.run(&mut ChaCha8Rng::seed_from_u64(5), 20, &sample_dataset, &max_generations(4));
+    let history = result.history();
+
+    // History keeps raw sizes, best is the largest tree and never shrinks
+    let largest = result.population().iter().map(|i| i.objectives()[0]).fold(0.0, f64::max);
+    assert_eq!(history.last().expect("History is empty!").best_fitness(), largest);
+    for pair in history.windows(2) {
+        assert!(pair[1].best_fitness() >= pair[0].best_fitness(), "{:?}", history);
+    }
+}
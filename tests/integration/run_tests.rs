@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rand::{rngs::StdRng, SeedableRng};
+use rstest::{fixture, rstest};
+
+use mycoforge::common::objectives::Direction;
+use mycoforge::common::traits::{Evaluator, Individual, Optimizer};
+use mycoforge::common::types::VectorFunction;
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::functions::symbolic::{add, sub, mul};
+use mycoforge::operators::sampler::OperatorSampler;
+
+use mycoforge::optimizers::ga::{EA, EABuilder, EAComponents};
+use mycoforge::optimizers::replacement::Replacement;
+use mycoforge::optimizers::termination::{
+    FitnessThreshold, MaxEvaluations, MaxGenerations, Stagnation, TerminationCriterion, TimeLimit
+};
+
+use mycoforge::tree::{
+    core::{individual::TreeIndividual, tree::TreeGenotype},
+    fitness::evaluate::MSE,
+    operators::{crossover::SubtreeCrossover, init::{Grow, RampedHalfAndHalf}, mutation::SubtreeMutation, select::TournamentSelection}
+};
+
+fn x(args: &[&[f64]]) -> Vec<f64> { return args[0].to_vec(); }
+
+struct Components;
+impl EAComponents<TreeGenotype> for Components {
+    type I = TreeIndividual<TreeGenotype>;
+    type Init = Grow;
+    type Mut = SubtreeMutation;
+    type Cross = SubtreeCrossover;
+    type Eval = MSE;
+    type Sel = TournamentSelection;
+}
+
+struct RampedComponents;
+impl EAComponents<TreeGenotype> for RampedComponents {
+    type I = TreeIndividual<TreeGenotype>;
+    type Init = RampedHalfAndHalf;
+    type Mut = SubtreeMutation;
+    type Cross = SubtreeCrossover;
+    type Eval = MSE;
+    type Sel = TournamentSelection;
+}
+
+/// Negated MSE, a score to maximize.
+struct NegatedMSE;
+
+impl Evaluator<TreeGenotype> for NegatedMSE {
+    type D = Dataset;
+
+    fn evaluate(&self, tree: &TreeGenotype, data: &Dataset, map: &HashMap<String, (usize, VectorFunction)>) -> f64 {
+        return -MSE::new().evaluate(tree, data, map);
+    }
+
+    fn direction(&self) -> Direction { return Direction::Maximize; }
+}
+
+struct MaximizingComponents;
+impl EAComponents<TreeGenotype> for MaximizingComponents {
+    type I = TreeIndividual<TreeGenotype>;
+    type Init = Grow;
+    type Mut = SubtreeMutation;
+    type Cross = SubtreeCrossover;
+    type Eval = NegatedMSE;
+    type Sel = TournamentSelection;
+}
+
+fn sample_map() -> HashMap<String, (usize, VectorFunction)> {
+    let mut map: HashMap<String, (usize, VectorFunction)> = HashMap::new();
+    map.insert("+".to_string(), (2, add));
+    map.insert("-".to_string(), (2, sub));
+    map.insert("*".to_string(), (2, mul));
+    map.insert("x".to_string(), (0, x));
+    return map;
+}
+
+fn sample_sampler() -> OperatorSampler {
+    let operators: Vec<String> = ["+", "-", "*", "x"].iter().map(|&w| w.to_string()).collect();
+    return OperatorSampler::new(operators, vec![2, 2, 2, 0], vec![0.25; 4]);
+}
+
+fn build_ea(
+    replacement: Replacement, offspring_size: Option<usize>, cache_capacity: Option<usize>
+) -> EA<Components, TreeGenotype> {
+    let builder = EABuilder::<Components, TreeGenotype>::new()
+        .set_initializer(Grow::new(1, 3))
+        .set_mutator(SubtreeMutation::new(0.1, (1, 2)).expect("Failed to create mutation scheme!"))
+        .set_crossoverer(SubtreeCrossover::new(0.9).expect("Failed to create crossover scheme!"))
+        .set_evaluator(MSE::new())
+        .set_selector(TournamentSelection::new(5))
+        .set_sampler(sample_sampler())
+        .set_map(sample_map())
+        .set_replacement(replacement);
+    let builder = match offspring_size {
+        Some(size) => builder.set_offspring_size(size),
+        None => builder
+    };
+    let builder = match cache_capacity {
+        Some(capacity) => builder.set_cache_capacity(capacity),
+        None => builder
+    };
+    return builder.build().expect("Failed to build EA!");
+}
+
+#[fixture]
+fn sample_ea() -> EA<Components, TreeGenotype> { return build_ea(Replacement::Generational, None, None); }
+
+#[fixture]
+fn sample_dataset() -> Dataset {
+    let xs: Vec<f64> = (0..=10).map(|i| i as f64 / 10.0).collect();
+    let ys = xs.iter().map(|&v| v.powi(2) + v).collect::<Vec<f64>>();
+
+    return Dataset::new(vec!["x".to_string()], "y".to_string(), vec![xs], ys);
+}
+
+#[rstest]
+fn test_run_max_generations(sample_ea: EA<Components, TreeGenotype>, sample_dataset: Dataset) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![Box::new(MaxGenerations::new(5))];
+
+    let result = sample_ea.run(&mut rng, 50, &sample_dataset, &criteria);
+
+    assert_eq!(result.generations(), 5,
+        "Wrong number of generations! Expected {}, found {}", 5, result.generations()
+    );
+    assert_eq!(result.history().len(), 6);
+    assert_eq!(result.population().len(), 50);
+    assert_eq!(result.evaluations(), 6 * 50);
+
+    let best_in_history = result.history().iter().map(|s| s.best_fitness()).fold(f64::INFINITY, f64::min);
+    assert_eq!(result.best().phenotype(), best_in_history,
+        "Best individual does not match history! Expected {}, found {}", best_in_history, result.best().phenotype()
+    );
+}
+
+#[rstest]
+fn test_run_max_evaluations(sample_ea: EA<Components, TreeGenotype>, sample_dataset: Dataset) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![
+        Box::new(MaxEvaluations::new(120)),
+        Box::new(MaxGenerations::new(100)),
+    ];
+
+    let result = sample_ea.run(&mut rng, 50, &sample_dataset, &criteria);
+
+    assert_eq!(result.evaluations(), 150);
+    assert_eq!(result.generations(), 2);
+}
+
+#[rstest]
+fn test_run_fitness_threshold(sample_ea: EA<Components, TreeGenotype>, sample_dataset: Dataset) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![
+        Box::new(FitnessThreshold::new(f64::INFINITY)),
+    ];
+
+    let result = sample_ea.run(&mut rng, 20, &sample_dataset, &criteria);
+
+    assert_eq!(result.generations(), 0,
+        "Run should stop right after initialization! Found {} generations", result.generations()
+    );
+}
+
+#[rstest]
+fn test_run_stagnation_and_time_limit(sample_ea: EA<Components, TreeGenotype>, sample_dataset: Dataset) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![
+        Box::new(Stagnation::new(3)),
+        Box::new(TimeLimit::new(Duration::from_secs(10))),
+    ];
+
+    let result = sample_ea.run(&mut rng, 20, &sample_dataset, &criteria);
+
+    assert!(result.generations() >= 3);
+    assert!(result.elapsed() < Duration::from_secs(20));
+}
+
+#[rstest]
+#[should_panic]
+fn test_run_without_criteria(sample_ea: EA<Components, TreeGenotype>, sample_dataset: Dataset) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let _ = sample_ea.run(&mut rng, 20, &sample_dataset, &[]);
+}
+
+#[rstest]
+#[case(Replacement::Elitism(1), None)]
+#[case(Replacement::MuPlusLambda, None)]
+#[case(Replacement::MuCommaLambda, Some(40))]
+#[case(Replacement::ReplaceWorst, Some(5))]
+fn test_run_with_replacement(
+    #[case] replacement: Replacement, #[case] offspring_size: Option<usize>, sample_dataset: Dataset
+) {
+    let ea = build_ea(replacement, offspring_size, None);
+    let mut rng = StdRng::seed_from_u64(42);
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![Box::new(MaxGenerations::new(5))];
+
+    let result = ea.run(&mut rng, 20, &sample_dataset, &criteria);
+
+    assert_eq!(result.population().len(), 20,
+        "{:?}: population size has changed! Expected {}, found {}", replacement, 20, result.population().len()
+    );
+    if replacement != Replacement::MuCommaLambda {
+        let bests = result.history().iter().map(|s| s.best_fitness()).collect::<Vec<f64>>();
+        assert!(bests.windows(2).all(|w| w[1] <= w[0]),
+            "{:?}: best fitness should never degrade! Found {:?}", replacement, bests
+        );
+    }
+}
+
+#[rstest]
+fn test_run_with_cache(sample_dataset: Dataset) {
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![Box::new(MaxGenerations::new(5))];
+    let plain = build_ea(Replacement::Elitism(2), None, None)
+        .run(&mut StdRng::seed_from_u64(42), 30, &sample_dataset, &criteria);
+
+    let ea = build_ea(Replacement::Elitism(2), None, Some(1000));
+    let cached = ea.run(&mut StdRng::seed_from_u64(42), 30, &sample_dataset, &criteria);
+
+    assert_eq!(plain.history(), cached.history(), "Cache should not change the trajectory of the run");
+
+    let cache = ea.cache().expect("Cache should be enabled").lock().unwrap();
+    assert!(cache.hits() > 0, "Repeated genotypes should be served from the cache");
+    assert!(cache.len() <= 1000);
+}
+
+#[rstest]
+fn test_run_with_ramped_half_and_half(sample_dataset: Dataset) {
+    let ea = EABuilder::<RampedComponents, TreeGenotype>::new()
+        .set_initializer(RampedHalfAndHalf::new(2, 4, true).expect("Failed to create RampedHalfAndHalf!"))
+        .set_mutator(SubtreeMutation::new(0.1, (1, 2)).expect("Failed to create mutation scheme!"))
+        .set_crossoverer(SubtreeCrossover::new(0.9).expect("Failed to create crossover scheme!"))
+        .set_evaluator(MSE::new())
+        .set_selector(TournamentSelection::new(5))
+        .set_sampler(sample_sampler())
+        .set_map(sample_map())
+        .build().expect("Failed to build EA!");
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let population = ea.init_population(&mut rng, 40);
+    let unique = population.iter().map(|tree| tree.to_string()).collect::<std::collections::HashSet<String>>();
+    assert_eq!(unique.len(), 40, "Initial population should not contain duplicates!");
+
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![Box::new(MaxGenerations::new(3))];
+    let result = ea.run(&mut rng, 40, &sample_dataset, &criteria);
+    assert_eq!(result.generations(), 3);
+}
+
+#[rstest]
+fn test_run_maximization(sample_dataset: Dataset) {
+    let ea = EABuilder::<MaximizingComponents, TreeGenotype>::new()
+        .set_initializer(Grow::new(1, 3))
+        .set_mutator(SubtreeMutation::new(0.1, (1, 2)).expect("Failed to create mutation scheme!"))
+        .set_crossoverer(SubtreeCrossover::new(0.9).expect("Failed to create crossover scheme!"))
+        .set_evaluator(NegatedMSE)
+        .set_selector(TournamentSelection::new(5))
+        .set_sampler(sample_sampler())
+        .set_map(sample_map())
+        .set_replacement(Replacement::Elitism(1))
+        .build().expect("Failed to build EA!");
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![Box::new(MaxGenerations::new(8))];
+
+    let maximized = ea.run(&mut StdRng::seed_from_u64(42), 30, &sample_dataset, &criteria);
+    let minimized = build_ea(Replacement::Elitism(1), None, None)
+        .run(&mut StdRng::seed_from_u64(42), 30, &sample_dataset, &criteria);
+
+    // Negated fitness with reversed direction follows exactly the same trajectory
+    assert_eq!(maximized.best().genotype(), minimized.best().genotype());
+    assert_eq!(maximized.best().phenotype(), -minimized.best().phenotype());
+    for (found, expected) in maximized.history().iter().zip(minimized.history()) {
+        assert_eq!(found.best_fitness(), -expected.best_fitness());
+        assert_eq!(found.worst_fitness(), -expected.worst_fitness());
+    }
+
+    let bests = maximized.history().iter().map(|s| s.best_fitness()).collect::<Vec<f64>>();
+    assert!(bests.windows(2).all(|w| w[1] >= w[0]), "Best score should never degrade! Found {:?}", bests);
+    assert_eq!(maximized.best().phenotype(), bests.iter().copied().fold(f64::NEG_INFINITY, f64::max));
+}
+
+#[rstest]
+fn test_run_maximization_termination(sample_dataset: Dataset) {
+    let ea = EABuilder::<MaximizingComponents, TreeGenotype>::new()
+        .set_initializer(Grow::new(1, 3))
+        .set_mutator(SubtreeMutation::new(0.1, (1, 2)).expect("Failed to create mutation scheme!"))
+        .set_crossoverer(SubtreeCrossover::new(0.9).expect("Failed to create crossover scheme!"))
+        .set_evaluator(NegatedMSE)
+        .set_selector(TournamentSelection::new(5))
+        .set_sampler(sample_sampler())
+        .set_map(sample_map())
+        .build().expect("Failed to build EA!");
+
+    // Any score reaches threshold below all possible scores, none reaches one above them
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![
+        Box::new(FitnessThreshold::new(f64::MIN)),
+        Box::new(MaxGenerations::new(3)),
+    ];
+    assert_eq!(ea.run(&mut StdRng::seed_from_u64(42), 20, &sample_dataset, &criteria).generations(), 0);
+
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![
+        Box::new(FitnessThreshold::new(1.0)),
+        Box::new(MaxGenerations::new(3)),
+    ];
+    assert_eq!(ea.run(&mut StdRng::seed_from_u64(42), 20, &sample_dataset, &criteria).generations(), 3);
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, SeedableRng};
+use rstest::{fixture, rstest};
+
+use mycoforge::common::objectives::Direction;
+use mycoforge::common::traits::{Individual, Optimizer};
+use mycoforge::common::types::VectorFunction;
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::functions::symbolic::{add, sub, mul};
+use mycoforge::operators::sampler::OperatorSampler;
+
+use mycoforge::optimizers::ga::{EABuilder, EAComponents};
+use mycoforge::optimizers::steady_state::{SteadyStateEA, SteadyStateReplacement};
+use mycoforge::optimizers::termination::{MaxGenerations, TerminationCriterion};
+
+use mycoforge::tree::{
+    core::{individual::TreeIndividual, tree::TreeGenotype},
+    fitness::evaluate::MSE,
+    operators::{crossover::SubtreeCrossover, init::Grow, mutation::SubtreeMutation, select::TournamentSelection}
+};
+
+fn x(args: &[&[f64]]) -> Vec<f64> { return args[0].to_vec(); }
+
+struct Components;
+impl EAComponents<TreeGenotype> for Components {
+    type I = TreeIndividual<TreeGenotype>;
+    type Init = Grow;
+    type Mut = SubtreeMutation;
+    type Cross = SubtreeCrossover;
+    type Eval = MSE;
+    type Sel = TournamentSelection;
+}
+
+fn build_ea(replacement: SteadyStateReplacement, offspring_per_step: usize) -> SteadyStateEA<Components, TreeGenotype> {
+    let operators: Vec<String> = ["+", "-", "*", "x"].iter().map(|&w| w.to_string()).collect();
+    let sampler = OperatorSampler::new(operators, vec![2, 2, 2, 0], vec![0.25; 4]);
+
+    let mut map: HashMap<String, (usize, VectorFunction)> = HashMap::new();
+    map.insert("+".to_string(), (2, add));
+    map.insert("-".to_string(), (2, sub));
+    map.insert("*".to_string(), (2, mul));
+    map.insert("x".to_string(), (0, x));
+
+    return EABuilder::<Components, TreeGenotype>::new()
+        .set_initializer(Grow::new(1, 3))
+        .set_mutator(SubtreeMutation::new(0.1, (1, 2)).expect("Failed to create mutation scheme!"))
+        .set_crossoverer(SubtreeCrossover::new(0.9).expect("Failed to create crossover scheme!"))
+        .set_evaluator(MSE::new())
+        .set_selector(TournamentSelection::new(3))
+        .set_sampler(sampler)
+        .set_map(map)
+        .build_steady_state(replacement, offspring_per_step)
+        .expect("Failed to build steady-state EA!");
+}
+
+#[fixture]
+fn sample_dataset() -> Dataset {
+    let xs: Vec<f64> = (0..=10).map(|i| i as f64 / 10.0).collect();
+    let ys = xs.iter().map(|&v| v.powi(2) + v).collect::<Vec<f64>>();
+
+    return Dataset::new(vec!["x".to_string()], "y".to_string(), vec![xs], ys);
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn test_optimize_creates_children(#[case] offspring_per_step: usize) {
+    let ea = build_ea(SteadyStateReplacement::Worst, offspring_per_step);
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let genotypes = ea.init_population(&mut rng, 10);
+    let population = TreeIndividual::from_vecs(&genotypes, &[1.0; 10]);
+    let children = ea.optimize(&mut rng, &population);
+
+    assert_eq!(children.len(), offspring_per_step);
+}
+
+#[rstest]
+fn test_step_replaces_worst(sample_dataset: Dataset) {
+    let ea = build_ea(SteadyStateReplacement::Worst, 1);
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let genotypes = ea.init_population(&mut rng, 10);
+    let fitnesses = (0..10).map(|i| if i == 3 { f64::MAX } else { 0.0 }).collect::<Vec<f64>>();
+    let mut population = TreeIndividual::from_vecs(&genotypes, &fitnesses);
+
+    let evaluations = ea.step(&mut rng, &mut population, &sample_dataset);
+
+    assert_eq!(evaluations, 1);
+    assert!(population[3].phenotype() < f64::MAX,
+        "Worst individual should have been replaced! Found fitness {}", population[3].phenotype()
+    );
+}
+
+#[rstest]
+#[case(SteadyStateReplacement::Worst, Direction::Minimize, 1)]
+#[case(SteadyStateReplacement::InverseTournament(3), Direction::Minimize, 1)]
+#[case(SteadyStateReplacement::Worst, Direction::Maximize, 0)]
+#[case(SteadyStateReplacement::InverseTournament(3), Direction::Maximize, 0)]
+fn test_loser(#[case] replacement: SteadyStateReplacement, #[case] direction: Direction, #[case] expected: usize) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let population = TreeIndividual::from_vecs(&vec![TreeGenotype::default(); 3], &[1.0, 3.0, 2.0]);
+
+    assert_eq!(replacement.loser(&mut rng, &population, direction), expected);
+}
+
+#[rstest]
+#[case(SteadyStateReplacement::Worst, 2)]
+#[case(SteadyStateReplacement::InverseTournament(4), 1)]
+fn test_steady_state_run(
+    #[case] replacement: SteadyStateReplacement, #[case] offspring_per_step: usize, sample_dataset: Dataset
+) {
+    let ea = build_ea(replacement, offspring_per_step);
+    let mut rng = StdRng::seed_from_u64(42);
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![Box::new(MaxGenerations::new(10))];
+
+    let result = ea.run(&mut rng, 30, &sample_dataset, &criteria);
+
+    assert_eq!(result.generations(), 10);
+    assert_eq!(result.population().len(), 30);
+    assert_eq!(result.evaluations(), 30 * 11);
+
+    let initial = result.history()[0].best_fitness();
+    assert!(result.best().phenotype() <= initial,
+        "Best fitness degraded! Initial {}, found {}", initial, result.best().phenotype()
+    );
+}
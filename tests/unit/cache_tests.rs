@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, SeedableRng};
+use rstest::{fixture, rstest};
+
+use mycoforge::common::cache::FitnessCache;
+use mycoforge::common::traits::{Data, Evaluator, Initializer};
+use mycoforge::common::types::VectorFunction;
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::functions::symbolic::{add, sub, mul};
+use mycoforge::operators::sampler::OperatorSampler;
+
+use mycoforge::tree::core::tree::TreeGenotype;
+use mycoforge::tree::fitness::evaluate::MSE;
+use mycoforge::tree::operators::init::Grow;
+
+fn x(args: &[&[f64]]) -> Vec<f64> { return args[0].to_vec(); }
+
+#[fixture]
+fn sample_map() -> HashMap<String, (usize, VectorFunction)> {
+    let mut map: HashMap<String, (usize, VectorFunction)> = HashMap::new();
+    map.insert("+".to_string(), (2, add));
+    map.insert("-".to_string(), (2, sub));
+    map.insert("*".to_string(), (2, mul));
+    map.insert("x".to_string(), (0, x));
+    return map;
+}
+
+#[fixture]
+fn sample_population() -> Vec<TreeGenotype> {
+    let operators: Vec<String> = ["+", "-", "*", "x"].iter().map(|&w| w.to_string()).collect();
+    let sampler = OperatorSampler::new(operators, vec![2, 2, 2, 0], vec![0.25; 4]);
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let init_scheme = Grow::new(1, 4);
+    return (0..20).map(|_| init_scheme.initialize(&mut rng, &sampler)).collect();
+}
+
+fn dataset(offset: f64) -> Dataset {
+    let xs: Vec<f64> = (0..=10).map(|i| i as f64 / 10.0).collect();
+    let ys = xs.iter().map(|&v| v.powi(2) + offset).collect::<Vec<f64>>();
+
+    return Dataset::new(vec!["x".to_string()], "y".to_string(), vec![xs], ys);
+}
+
+#[rstest]
+fn test_cache_lru_eviction() {
+    let mut cache: FitnessCache<u32> = FitnessCache::new(2);
+    cache.insert(0, 1, 1.0);
+    cache.insert(0, 2, 2.0);
+    assert_eq!(cache.get(0, &1), Some(1.0));
+
+    cache.insert(0, 3, 3.0);
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.evictions(), 1);
+    assert_eq!(cache.get(0, &2), None, "Least recently used entry should be evicted");
+    assert_eq!(cache.get(0, &1), Some(1.0));
+    assert_eq!(cache.get(0, &3), Some(3.0));
+    assert_eq!((cache.hits(), cache.misses()), (3, 1));
+    assert_eq!(cache.hit_rate(), 0.75);
+}
+
+#[rstest]
+fn test_cache_zero_capacity() {
+    let mut cache: FitnessCache<u32> = FitnessCache::new(0);
+    cache.insert(0, 1, 1.0);
+
+    assert!(cache.is_empty());
+    assert_eq!(cache.get(0, &1), None);
+}
+
+#[rstest]
+fn test_cache_clear() {
+    let mut cache: FitnessCache<u32> = FitnessCache::new(4);
+    cache.insert(0, 1, 1.0);
+    let _ = cache.get(0, &1);
+    cache.clear();
+
+    assert!(cache.is_empty());
+    assert_eq!((cache.hits(), cache.misses(), cache.evictions()), (0, 0, 0));
+}
+
+#[rstest]
+fn test_dataset_fingerprint() {
+    assert_eq!(dataset(0.0).fingerprint(), dataset(0.0).fingerprint());
+    assert_ne!(dataset(0.0).fingerprint(), dataset(1.0).fingerprint());
+}
+
+#[rstest]
+fn test_memoized_evaluate(
+    sample_population: Vec<TreeGenotype>, sample_map: HashMap<String, (usize, VectorFunction)>
+) {
+    let evaluator = MSE::new();
+    let (train, test) = (dataset(0.0), dataset(1.0));
+    let mut cache = FitnessCache::new(100);
+
+    let tree = &sample_population[0];
+    let first = evaluator.memoized_evaluate(tree, &train, &sample_map, &mut cache);
+    let second = evaluator.memoized_evaluate(tree, &train, &sample_map, &mut cache);
+    let other = evaluator.memoized_evaluate(tree, &test, &sample_map, &mut cache);
+
+    assert_eq!(first, evaluator.evaluate(tree, &train, &sample_map));
+    assert_eq!(first, second);
+    assert_eq!(other, evaluator.evaluate(tree, &test, &sample_map), "Datasets should not share entries");
+    assert_eq!((cache.len(), cache.hits(), cache.misses()), (2, 1, 2));
+}
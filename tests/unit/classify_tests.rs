@@ -0,0 +1,211 @@
+use std::time::Duration;
+
+use rstest::*;
+
+use mycoforge::common::traits::Evaluator;
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::set::Operators;
+use mycoforge::tree::core::tree::TreeGenotype;
+use mycoforge::tree::fitness::classify::{Accuracy, AUC, BalancedAccuracy, BinaryClassifier, F1, LogLoss, OneVsRest};
+use mycoforge::tree::fitness::evaluate::{EvaluatorError, Loss};
+use mycoforge::tree::model::{ModelError, ModelFormat, OneVsRestModel, SymbolicModel, TrainingMetadata};
+
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_registered("+", "add", 1.0 / 3.0).expect("Failed to add function!")
+        .add_registered("-", "sub", 1.0 / 3.0).expect("Failed to add function!")
+        .add_registered("*", "mul", 1.0 / 3.0).expect("Failed to add function!")
+        .add_variable("x", 0.4).expect("Failed to add variable!")
+        .add_variable("y", 0.4).expect("Failed to add variable!")
+        .add_constant(1.0, 0.2).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+}
+
+fn sample_model(infix: &str) -> SymbolicModel {
+    let operators = sample_operators();
+    let tree = TreeGenotype::from_infix(infix, &operators).expect("Failed to parse tree!");
+    return SymbolicModel::new(tree, operators, vec!["x".to_string(), "y".to_string()], "class".to_string(),
+        0.0, TrainingMetadata::new(0, 0, Duration::ZERO, Vec::new()));
+}
+
+/// Scores [-2, -0.5, 0.5, 3, 1, -1] against labels [0, 0, 0, 1, 1, 1]: one false positive at
+/// 0.5 and one false negative at -1.
+#[fixture]
+fn sample_scores() -> (Vec<f64>, Vec<f64>) {
+    return (vec![-2.0, -0.5, 0.5, 3.0, 1.0, -1.0], vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+}
+
+/// Three classes in the plane: 0 left, 1 right, 2 above.
+#[fixture]
+fn multiclass_dataset() -> Dataset {
+    let xs = vec![-2.0, -3.0, 2.0, 3.0, 0.0, 0.5];
+    let ys = vec![0.0, 0.5, 0.0, -0.5, 3.0, 2.5];
+    let classes = vec![0.0, 0.0, 1.0, 1.0, 2.0, 2.0];
+    return Dataset::new(vec!["x".to_string(), "y".to_string()], "class".to_string(), vec![xs, ys], classes);
+}
+
+#[rstest]
+fn test_binary_classifier() {
+    let classifier = BinaryClassifier::default();
+    assert_eq!(classifier.threshold(), 0.5);
+    assert_eq!(classifier.probabilities(&[-1000.0, 0.0, 1000.0]), vec![0.0, 0.5, 1.0]);
+    assert!((classifier.probability(2.0) - 1.0 / (1.0 + (-2.0f64).exp())).abs() < 1e-15);
+    assert!((classifier.probability(-2.0) + classifier.probability(2.0) - 1.0).abs() < 1e-15);
+    assert_eq!(classifier.labels(&[-0.1, 0.0, 0.1, f64::NAN]), vec![0.0, 1.0, 1.0, 0.0]);
+
+    let strict = BinaryClassifier::new(0.8).expect("Failed to create classifier!");
+    assert_eq!(strict.labels(&[1.0, 1.5]), vec![0.0, 1.0]);
+}
+
+#[rstest]
+#[case(0.0)]
+#[case(1.0)]
+#[case(-0.5)]
+#[case(f64::NAN)]
+fn test_binary_classifier_invalid_threshold(#[case] threshold: f64) {
+    assert!(matches!(BinaryClassifier::new(threshold), Err(EvaluatorError::InvalidThreshold(_))));
+}
+
+#[rstest]
+fn test_threshold_metrics(sample_scores: (Vec<f64>, Vec<f64>)) {
+    let (scores, targets) = sample_scores;
+
+    // TP 2, FP 1, TN 2, FN 1
+    assert!((Accuracy::default().loss(&scores, &targets) - 2.0 / 6.0).abs() < 1e-15);
+    assert!((BalancedAccuracy::default().loss(&scores, &targets) - 1.0 / 3.0).abs() < 1e-15);
+    assert!((F1::default().loss(&scores, &targets) - 1.0 / 3.0).abs() < 1e-15);
+
+    // Threshold above sigmoid(1) turns the positive at 1 into a false negative and removes the false positive
+    let classifier = BinaryClassifier::new(0.75).expect("Failed to create classifier!");
+    assert_eq!(Accuracy::new(classifier).classifier(), &classifier);
+    assert!((Accuracy::new(classifier).loss(&scores, &targets) - 2.0 / 6.0).abs() < 1e-15);
+    assert!((F1::new(classifier).loss(&scores, &targets) - (1.0 - 2.0 / 4.0)).abs() < 1e-15);
+    assert!((BalancedAccuracy::new(classifier).loss(&scores, &targets) - (1.0 - (1.0 / 3.0 + 1.0) / 2.0)).abs() < 1e-15);
+}
+
+#[rstest]
+fn test_perfect_classification() {
+    let scores = [-3.0, -1.0, 2.0, 5.0];
+    let targets = [0.0, 0.0, 1.0, 1.0];
+
+    assert_eq!(Accuracy::default().loss(&scores, &targets), 0.0);
+    assert_eq!(BalancedAccuracy::default().loss(&scores, &targets), 0.0);
+    assert_eq!(F1::default().loss(&scores, &targets), 0.0);
+    assert_eq!(AUC::new().loss(&scores, &targets), 0.0);
+    assert!(LogLoss::new().loss(&scores, &targets) < 0.2);
+    assert_eq!(AUC::new().loss(&[5.0, 2.0, -1.0, -3.0], &targets), 1.0);
+}
+
+#[rstest]
+fn test_imbalanced_classes() {
+    let targets = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0];
+    let majority = [-1.0; 10];
+
+    assert!((Accuracy::default().loss(&majority, &targets) - 0.1).abs() < 1e-15);
+    assert_eq!(BalancedAccuracy::default().loss(&majority, &targets), 0.5);
+    assert_eq!(F1::default().loss(&majority, &targets), 1.0);
+
+    // Without positives, only specificity counts and F1 is perfect if nothing is predicted positive
+    assert_eq!(BalancedAccuracy::default().loss(&[-1.0, 1.0], &[0.0, 0.0]), 0.5);
+    assert_eq!(F1::default().loss(&[-1.0, -1.0], &[0.0, 0.0]), 0.0);
+}
+
+#[rstest]
+fn test_log_loss(sample_scores: (Vec<f64>, Vec<f64>)) {
+    let (scores, targets) = sample_scores;
+    let classifier = BinaryClassifier::default();
+
+    let expected = scores.iter().zip(targets.iter())
+        .map(|(&s, &y)| {
+            let p = classifier.probability(s);
+            return -(y * p.ln() + (1.0 - y) * (1.0 - p).ln());
+        }).sum::<f64>() / scores.len() as f64;
+    let result = LogLoss::default().loss(&scores, &targets);
+    assert!((result - expected).abs() < 1e-12, "{} != {}", result, expected);
+
+    // Confident mistakes cost about the score, not infinity
+    assert!((LogLoss::new().loss(&[800.0], &[0.0]) - 800.0).abs() < 1e-9);
+    assert!((LogLoss::new().loss(&[-800.0], &[1.0]) - 800.0).abs() < 1e-9);
+    assert!(LogLoss::new().loss(&[800.0], &[1.0]) < 1e-300);
+    assert!(LogLoss::new().loss(&[f64::NAN], &[1.0]).is_nan());
+}
+
+#[rstest]
+fn test_auc(sample_scores: (Vec<f64>, Vec<f64>)) {
+    let (scores, targets) = sample_scores;
+
+    // 9 positive-negative pairs, ordered correctly: 3 for score 3, 3 for score 1, 1 for score -1
+    assert!((AUC::new().loss(&scores, &targets) - (1.0 - 7.0 / 9.0)).abs() < 1e-15);
+    // AUC does not depend on threshold or monotonic transformations of scores
+    let transformed = scores.iter().map(|s| s.exp() * 10.0).collect::<Vec<f64>>();
+    assert!((AUC::new().loss(&transformed, &targets) - AUC::new().loss(&scores, &targets)).abs() < 1e-15);
+
+    // Ties count as half
+    assert_eq!(AUC::new().loss(&[1.0, 1.0, 1.0, 1.0], &[0.0, 1.0, 0.0, 1.0]), 0.5);
+    assert_eq!(AUC::new().loss(&[0.0, 1.0, 1.0], &[0.0, 0.0, 1.0]), 0.25);
+
+    assert_eq!(AUC::new().loss(&[1.0, 2.0], &[1.0, 1.0]), 0.5);
+    assert!(AUC::new().loss(&[1.0, f64::NAN], &[0.0, 1.0]).is_nan());
+}
+
+#[rstest]
+fn test_binary_evaluation(multiclass_dataset: Dataset) {
+    let operators = sample_operators();
+    let map = operators.create_map();
+    let scheme = OneVsRest::from_targets(multiclass_dataset.targets()).expect("Failed to create scheme!");
+    let right = scheme.binary_dataset(&multiclass_dataset, 1);
+
+    assert_eq!(right.targets(), &vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+    assert_eq!(right.features(), multiclass_dataset.features());
+
+    let tree = TreeGenotype::from_infix("x - 1", &operators).expect("Failed to parse tree!");
+    assert_eq!(Accuracy::default().evaluate(&tree, &right, &map), 0.0);
+    assert_eq!(AUC::new().evaluate(&tree, &right, &map), 0.0);
+    let tree = TreeGenotype::from_infix("1 - x", &operators).expect("Failed to parse tree!");
+    assert_eq!(Accuracy::default().evaluate(&tree, &right, &map), 1.0);
+}
+
+#[rstest]
+fn test_one_vs_rest() {
+    let scheme = OneVsRest::new(vec![3.0, 1.0, 3.0, 2.0]).expect("Failed to create scheme!");
+    assert_eq!(scheme.classes(), &[1.0, 2.0, 3.0]);
+
+    let scores = vec![vec![1.0, f64::NAN, 0.0, f64::NAN], vec![1.0, 0.0, 2.0, f64::NAN], vec![0.0, -1.0, 2.0, f64::NAN]];
+    assert_eq!(scheme.labels(&scores), vec![1.0, 2.0, 2.0, 1.0]);
+
+    assert!(matches!(OneVsRest::new(vec![1.0, 1.0]), Err(EvaluatorError::TooFewClasses(1))));
+    assert!(matches!(OneVsRest::new(Vec::new()), Err(EvaluatorError::TooFewClasses(0))));
+    assert!(matches!(OneVsRest::new(vec![0.0, f64::NAN]), Err(EvaluatorError::InvalidClass(_))));
+}
+
+#[rstest]
+fn test_model_predict_labels(multiclass_dataset: Dataset) {
+    let model = sample_model("x - 1");
+    let classifier = BinaryClassifier::default();
+
+    assert_eq!(model.predict_labels(&multiclass_dataset, &classifier).expect("Failed to predict!"), vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+    let probabilities = model.predict_probabilities(&multiclass_dataset, &classifier).expect("Failed to predict!");
+    assert_eq!(probabilities[4], 1.0 / (1.0 + std::f64::consts::E));
+}
+
+#[rstest]
+fn test_one_vs_rest_model(multiclass_dataset: Dataset) {
+    let scheme = OneVsRest::from_targets(multiclass_dataset.targets()).expect("Failed to create scheme!");
+    let models = vec![sample_model("0 - x - y"), sample_model("x - y"), sample_model("y - 1")];
+    let classifier = OneVsRestModel::new(models, scheme.clone()).expect("Failed to create model!");
+
+    assert_eq!(classifier.predict_scores(&multiclass_dataset).expect("Failed to predict!").len(), 3);
+    assert_eq!(classifier.predict_labels(&multiclass_dataset).expect("Failed to predict!"), multiclass_dataset.targets().clone());
+
+    for format in [ModelFormat::Json, ModelFormat::Binary] {
+        let loaded = OneVsRestModel::from_bytes(&classifier.to_bytes(format).expect("Failed to encode model!"), format)
+            .expect("Failed to decode model!");
+        assert_eq!(loaded.scheme(), classifier.scheme());
+        assert_eq!(loaded.models().len(), 3);
+        assert_eq!(loaded.predict_labels(&multiclass_dataset).expect("Failed to predict!"), multiclass_dataset.targets().clone());
+    }
+
+    assert!(matches!(OneVsRestModel::new(vec![sample_model("x")], scheme),
+        Err(ModelError::DimensionMismatch { expected: 3, found: 1 })));
+}
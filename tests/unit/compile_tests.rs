@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use rstest::*;
+
+use mycoforge::common::traits::{Data, Evaluator, Initializer};
+use mycoforge::common::types::VectorFunction;
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::functions::symbolic::{add, div};
+use mycoforge::operators::set::Operators;
+use mycoforge::tree::core::node::Node;
+use mycoforge::tree::core::tree::TreeGenotype;
+use mycoforge::tree::fitness::compile::{CompileError, Compiled, Compiler, Instruction, Program, Workspace};
+use mycoforge::tree::fitness::evaluate::{MAPE, MSE};
+use mycoforge::tree::fitness::parallel::ParallelEvaluator;
+use mycoforge::tree::operators::init::Grow;
+
+fn square(args: &[&[f64]]) -> Vec<f64> { return args[0].iter().map(|a| a * a).collect(); }
+fn row_sum(args: &[&[f64]]) -> Vec<f64> {
+    return (0..args[0].len()).map(|i| args.iter().map(|column| column[i]).sum()).collect();
+}
+fn unprotected_div(args: &[&[f64]]) -> Vec<f64> { return args[0].iter().zip(args[1].iter()).map(|(a, b)| a / b).collect(); }
+
+#[fixture]
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_registered("+", "add", 0.1).expect("Failed to add function!")
+        .add_registered("-", "sub", 0.1).expect("Failed to add function!")
+        .add_registered("*", "mul", 0.1).expect("Failed to add function!")
+        .add_registered("/", "protected_div", 0.1).expect("Failed to add function!")
+        .add_registered("sin", "sin", 0.1).expect("Failed to add function!")
+        .add_registered("cos", "cos", 0.1).expect("Failed to add function!")
+        .add_registered("ln", "protected_ln", 0.1).expect("Failed to add function!")
+        .add_function("square", square, 1, 0.1).expect("Failed to add function!")
+        .add_function("udiv", unprotected_div, 2, 0.1).expect("Failed to add function!")
+        .add_function("sum", row_sum, 0, 0.1).expect("Failed to add function!")
+        .add_variable("x", 0.4).expect("Failed to add variable!")
+        .add_variable("y", 0.4).expect("Failed to add variable!")
+        .add_constant(0.5, 0.2).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+}
+
+#[fixture]
+fn sample_dataset() -> Dataset {
+    let xs = vec![-2.0, -0.5, 0.0, 1e-7, 0.25, 1.0, 3.0];
+    let ys = vec![1.0, 0.0, -1.5, 2.0, 0.5, -3.0, 1e-7];
+    let targets = xs.iter().zip(ys.iter()).map(|(x, y)| x * y + 1.0).collect();
+    return Dataset::new(vec!["x".to_string(), "y".to_string()], "z".to_string(), vec![xs, ys], targets);
+}
+
+#[rstest]
+fn test_compile(sample_operators: Operators) {
+    let tree = TreeGenotype::from_infix("x / sin(square(y)) - udiv(sum, 2)", &sample_operators).expect("Failed to parse tree!");
+
+    let program = Program::compile(&tree, &sample_operators).expect("Failed to compile tree!");
+
+    let instructions = program.instructions();
+    assert_eq!(instructions.len(), tree.arena().len());
+    assert!(matches!(instructions[0], Instruction::Variable(0)));
+    assert!(matches!(instructions[1], Instruction::Variable(1)));
+    assert!(matches!(instructions[2], Instruction::Call(_, 1)), "Custom function should be called!");
+    assert!(matches!(instructions[3], Instruction::Sin));
+    assert!(matches!(instructions[4], Instruction::Div));
+    assert!(matches!(instructions[5], Instruction::Features(_)));
+    assert!(matches!(instructions[6], Instruction::Constant(2.0)));
+    assert!(matches!(instructions[7], Instruction::Call(_, 2)), "Unprotected division should not be native!");
+    assert!(matches!(instructions[8], Instruction::Sub));
+    assert_eq!(program.stack_size(), 3);
+}
+
+#[rstest]
+fn test_compile_invalid(sample_operators: Operators) {
+    let unknown = TreeGenotype::with_arena(vec![Node::function("exp", 1), Node::Variable(0)]);
+    assert_eq!(Program::compile(&unknown, &sample_operators).err(), Some(CompileError::UnknownFunction("exp".to_string())));
+    assert_eq!(Program::compile(&TreeGenotype::default(), &sample_operators).err(), Some(CompileError::EmptyTree));
+
+    // Children maps not matching the arena, e.g. from an unchecked file
+    let arena = vec![Node::function("+", 2), Node::Variable(0), Node::Variable(1)];
+    let malformed = |children: HashMap<usize, Vec<usize>>| Program::compile(&TreeGenotype::new(arena.clone(), children), &sample_operators).err();
+    assert_eq!(malformed(HashMap::new()), Some(CompileError::MalformedTree(0)));
+    assert_eq!(malformed(HashMap::from([(0, vec![1])])), Some(CompileError::MalformedTree(0)));
+    assert_eq!(malformed(HashMap::from([(0, vec![1, 5])])), Some(CompileError::MalformedTree(5)));
+    assert_eq!(malformed(HashMap::from([(0, vec![1, 2]), (1, vec![0])])), Some(CompileError::MalformedTree(1)));
+    assert!(malformed(HashMap::from([(0, vec![0, 0])])).is_some());
+}
+
+#[rstest]
+fn test_compiled_evaluator(sample_operators: Operators, sample_dataset: Dataset) {
+    let mut rng = StdRng::seed_from_u64(7);
+    let map = sample_operators.create_map();
+    let sampler = sample_operators.sampler();
+    let compiled = Compiled::new(MAPE::new(), &sample_operators);
+    let parallel = ParallelEvaluator::new(Compiled::new(MAPE::new(), &sample_operators), Some(2)).expect("Failed to create thread pool!");
+
+    let trees = (0..50).map(|_| Grow::new(0, 5).initialize(&mut rng, &sampler)).collect::<Vec<TreeGenotype>>();
+    let bits = |values: &[f64]| values.iter().map(|value| value.to_bits()).collect::<Vec<u64>>();
+    for tree in &trees {
+        let (expected, expected_cases) = MAPE::new().evaluate_cases(tree, &sample_dataset, &map);
+        let (found, found_cases) = compiled.evaluate_cases(tree, &sample_dataset, &map);
+        assert_eq!(expected.to_bits(), found.to_bits(), "Compiled evaluator differs for {}!", tree.to_infix());
+        assert_eq!(bits(&expected_cases), bits(&found_cases));
+    }
+    assert_eq!(
+        bits(&parallel.evaluate_population(&trees, &sample_dataset, &map)),
+        bits(&MAPE::new().evaluate_population(&trees, &sample_dataset, &map))
+    );
+
+    let unknown = TreeGenotype::with_arena(vec![Node::function("exp", 1), Node::Variable(0)]);
+    assert!(compiled.evaluate(&unknown, &sample_dataset, &map).is_nan());
+    assert!(compiled.evaluate_cases(&unknown, &sample_dataset, &map).1.iter().all(|error| error.is_nan()));
+}
+
+#[rstest]
+fn test_execute_matches_evaluator(sample_operators: Operators, sample_dataset: Dataset) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let map: HashMap<String, (usize, VectorFunction)> = sample_operators.create_map();
+    let sampler = sample_operators.sampler();
+    let compiler = Compiler::new(&sample_operators);
+    let evaluator = MSE::new();
+    let (features, targets) = sample_dataset.data();
+
+    let mut workspace = Workspace::new();
+    for _ in 0..200 {
+        let tree: TreeGenotype = Grow::new(0, 6).initialize(&mut rng, &sampler);
+        let program = compiler.compile(&tree).expect("Failed to compile tree!");
+
+        let predictions = program.execute(features, &mut workspace);
+        let found = predictions.iter().zip(targets.iter()).map(|(p, t)| (p - t).powi(2)).sum::<f64>() / targets.len() as f64;
+        let expected = evaluator.evaluate(&tree, &sample_dataset, &map);
+
+        assert!(expected.to_bits() == found.to_bits() || (expected.is_nan() && found.is_nan()),
+            "Compiled program differs for {}! Expected {}, found {}", tree.to_infix(), expected, found
+        );
+        let bits = |values: &[f64]| values.iter().map(|value| value.to_bits()).collect::<Vec<u64>>();
+        assert_eq!(bits(&program.evaluate(features)), bits(predictions));
+    }
+}
+
+/// Agrees with protected division on finite inputs but differs for large divisors and panics on
+/// non-finite ones.
+fn strict_div(args: &[&[f64]]) -> Vec<f64> {
+    return args[0].iter().zip(args[1].iter()).map(|(&a, &b)| {
+        assert!(a.is_finite() && b.is_finite(), "Expected finite inputs!");
+        return if b.abs() < 1e-6 || b.abs() > 1e12 { 1.0 } else { a / b };
+    }).collect();
+}
+
+#[rstest]
+fn test_only_registered_functions_are_native() {
+    let operators = OperatorsBuilder::default()
+        .add_function("+", add, 2, 0.5).expect("Failed to add function!")
+        .add_function("/", strict_div, 2, 0.5).expect("Failed to add function!")
+        .add_variable("x", 1.0).expect("Failed to add variable!")
+        .build().expect("Failed to build operators!");
+    let tree = TreeGenotype::from_infix("x / (x + x)", &operators).expect("Failed to parse tree!");
+
+    // Compiling never calls functions, even built-in ones added without registry key are called
+    let program = Program::compile(&tree, &operators).expect("Failed to compile tree!");
+    assert!(matches!(program.instructions()[3], Instruction::Call(_, 2)));
+    assert!(matches!(program.instructions()[4], Instruction::Call(_, 2)));
+
+    let features = vec![vec![1e13, 2.0]];
+    assert_eq!(program.evaluate(&features), strict_div(&[&features[0], &add(&[&features[0], &features[0]])]));
+    assert_eq!(program.evaluate(&features)[0], 1.0);
+    assert_ne!(div(&[&[1e13], &[2e13]])[0], 1.0);
+}
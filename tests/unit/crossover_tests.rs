@@ -73,7 +73,7 @@ fn test_subtree_crossover(sample_sampler: OperatorSampler) {
             .variate(&mut rng, &parent1, &parent2, &sample_sampler);
         
         for child in &mut children {
-            *child.children_mut() = child.construct_children(&sample_sampler);
+            *child.children_mut() = child.construct_children();
         }
 
         for i in 0..children.len() {
@@ -10,6 +10,7 @@ use mycoforge::common::types::VectorFunction;
 use mycoforge::common::traits::Data;
 use mycoforge::common::traits::Evaluator;
 
+use mycoforge::tree::core::node::Node;
 use mycoforge::tree::core::tree::TreeGenotype;
 
 use mycoforge::dataset::core::Dataset;
@@ -20,6 +21,12 @@ use mycoforge::operators::functions::symbolic::{add, sub, mul, div};
 
 fn x(args:&[&[f64]]) -> Vec<f64> { return args[0].to_vec(); }
 
+fn to_nodes(arena: &[String], children: &HashMap<usize, Vec<usize>>) -> Vec<Node> {
+    return arena.iter().enumerate()
+        .map(|(i, token)| Node::function(token, children.get(&i).map_or(0, |c| c.len())))
+        .collect();
+}
+
 #[fixture]
 fn sample_function_set() -> Result<Operators, Box<dyn Error>> {
     let sample_operators = OperatorsBuilder::default()
@@ -40,7 +47,7 @@ fn sample_tree() -> TreeGenotype {
     children.insert(0, vec![1, 4]);
     children.insert(1, vec![2, 3]);
 
-    let tree = TreeGenotype::new(arena.clone(), children.clone());
+    let tree = TreeGenotype::new(to_nodes(&arena, &children), children.clone());
 
     return tree;
 }
@@ -62,7 +69,7 @@ fn test_cases() -> Vec<(TreeGenotype, Dataset, f64)> {
             let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
             children.insert(0, vec![1, 2]);
 
-            TreeGenotype::new(arena.clone(), children.clone())
+            TreeGenotype::new(to_nodes(&arena, &children), children.clone())
         }, sample_dataset(), 850.1683501683499)
     ];
 }
@@ -100,7 +107,7 @@ fn deap_test_cases() -> (Vec<(TreeGenotype, f64)>, Dataset) {
                 ))
                 .collect();
             
-            (TreeGenotype::new(ind.arena, children), ind.fitness)
+            (TreeGenotype::new(to_nodes(&ind.arena, &children), children), ind.fitness)
         })
         .collect();
     return (individuals, dataset);
@@ -0,0 +1,74 @@
+use rstest::*;
+
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::functions::symbolic::{add, cos, div, mul, sin, sub};
+use mycoforge::operators::set::Operators;
+use mycoforge::tree::core::tree::TreeGenotype;
+
+#[fixture]
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_function("+", add, 2, 1.0 / 6.0).expect("Failed to add function!")
+        .add_function("-", sub, 2, 1.0 / 6.0).expect("Failed to add function!")
+        .add_function("*", mul, 2, 1.0 / 6.0).expect("Failed to add function!")
+        .add_function("/", div, 2, 1.0 / 6.0).expect("Failed to add function!")
+        .add_function("sin", sin, 1, 1.0 / 6.0).expect("Failed to add function!")
+        .add_function("protected_cos", cos, 1, 1.0 / 6.0).expect("Failed to add function!")
+        .add_variable("x", 0.5).expect("Failed to add variable!")
+        .add_variable("y", 0.5).expect("Failed to add variable!")
+        .build().expect("Failed to build operators!");
+}
+
+#[rstest]
+#[case("(x + sin(y)) / 2.5", "\\frac{x_{0} + \\sin\\left(x_{1}\\right)}{2.5}")]
+#[case("(x + y) * (x - y)", "\\left(x_{0} + x_{1}\\right) \\cdot \\left(x_{0} - x_{1}\\right)")]
+#[case("x - (y - 1)", "x_{0} - \\left(x_{1} - 1\\right)")]
+#[case("x * y + x / (y + 1)", "x_{0} \\cdot x_{1} + \\frac{x_{0}}{x_{1} + 1}")]
+#[case("x * (-2)", "x_{0} \\cdot \\left(-2\\right)")]
+#[case("protected_cos(x * y)", "\\operatorname{protected\\_cos}\\left(x_{0} \\cdot x_{1}\\right)")]
+fn test_latex(#[case] expression: &str, #[case] expected: &str, sample_operators: Operators) {
+    let tree = TreeGenotype::from_infix(expression, &sample_operators).expect("Failed to parse tree!");
+
+    assert_eq!(tree.to_latex(), expected);
+}
+
+#[rstest]
+fn test_dot(sample_operators: Operators) {
+    let tree = TreeGenotype::from_infix("x + sin(y)", &sample_operators).expect("Failed to parse tree!");
+
+    let expected = [
+        "digraph tree {",
+        "    n0 [label=\"+\", shape=ellipse];",
+        "    n1 [label=\"x0\", shape=box];",
+        "    n2 [label=\"sin\", shape=ellipse];",
+        "    n3 [label=\"x1\", shape=box];",
+        "    n0 -> n1;",
+        "    n0 -> n2;",
+        "    n2 -> n3;",
+        "}",
+        ""
+    ].join("\n");
+    assert_eq!(tree.to_dot(None), expected);
+    assert_eq!(tree.to_dot(Some(10)), expected);
+}
+
+#[rstest]
+fn test_dot_highlight(sample_operators: Operators) {
+    let tree = TreeGenotype::from_infix("x + sin(y)", &sample_operators).expect("Failed to parse tree!");
+
+    let dot = tree.to_dot(Some(2));
+
+    assert_eq!(dot.matches("fillcolor").count(), 2, "Only nodes of the subtree should be highlighted!");
+    assert!(dot.contains("n2 [label=\"sin\", shape=ellipse, style=filled, fillcolor=lightblue];"));
+    assert!(dot.contains("n3 [label=\"x1\", shape=box, style=filled, fillcolor=lightblue];"));
+    assert!(dot.contains("n2 -> n3 [color=lightblue, penwidth=2];"));
+    assert!(dot.contains("n0 -> n2;\n"), "Edge entering the subtree should not be highlighted!");
+}
+
+#[test]
+fn test_empty_tree() {
+    let tree = TreeGenotype::default();
+
+    assert_eq!(tree.to_dot(None), "digraph tree {\n}\n");
+    assert_eq!(tree.to_latex(), "");
+}
@@ -0,0 +1,84 @@
+use rstest::*;
+
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::functions::symbolic::{add, cos, div, mul, sin, sub};
+use mycoforge::operators::set::Operators;
+use mycoforge::tree::core::errors::ParseError;
+use mycoforge::tree::core::node::Node;
+use mycoforge::tree::core::tree::TreeGenotype;
+
+#[fixture]
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_function("+", add, 2, 1.0 / 6.0).expect("Failed to add function!")
+        .add_function("-", sub, 2, 1.0 / 6.0).expect("Failed to add function!")
+        .add_function("*", mul, 2, 1.0 / 6.0).expect("Failed to add function!")
+        .add_function("/", div, 2, 1.0 / 6.0).expect("Failed to add function!")
+        .add_function("sin", sin, 1, 1.0 / 6.0).expect("Failed to add function!")
+        .add_function("cos", cos, 1, 1.0 / 6.0).expect("Failed to add function!")
+        .add_variable("x", 0.5).expect("Failed to add variable!")
+        .add_variable("y", 0.5).expect("Failed to add variable!")
+        .build().expect("Failed to build operators!");
+}
+
+#[rstest]
+#[case("(x0 + sin(x1)) / 2.5", "(/ (+ x0 (sin x1)) 2.5)")]
+#[case("x0 + x1 * x0", "(+ x0 (* x1 x0))")]
+#[case("x0 - x1 - x0", "(- (- x0 x1) x0)")]
+#[case("x0 - (x1 - x0)", "(- x0 (- x1 x0))")]
+#[case("x0 * (-1.5)", "(* x0 -1.5)")]
+#[case("sin(cos(x0 / x1))", "(sin (cos (/ x0 x1)))")]
+#[case("-2", "-2")]
+fn test_round_trip(#[case] infix: &str, #[case] sexpr: &str, sample_operators: Operators) {
+    let from_infix = TreeGenotype::from_infix(infix, &sample_operators).expect("Failed to parse infix!");
+    let from_sexpr = TreeGenotype::from_sexpr(sexpr, &sample_operators).expect("Failed to parse S-expression!");
+
+    assert_eq!(from_infix, from_sexpr);
+    assert_eq!(from_infix.to_infix(), infix);
+    assert_eq!(from_infix.to_sexpr(), sexpr);
+    assert_eq!(from_infix.validate(&sample_operators), Ok(()));
+}
+
+#[rstest]
+#[case("x+sin( y )/2", "x0 + sin(x1) / 2")]
+#[case("((x))", "x0")]
+#[case("x * -1e-3", "x0 * (-0.001)")]
+#[case("(x + y) + x", "x0 + x1 + x0")]
+fn test_infix_normalization(#[case] infix: &str, #[case] expected: &str, sample_operators: Operators) {
+    let tree = TreeGenotype::from_infix(infix, &sample_operators).expect("Failed to parse infix!");
+
+    assert_eq!(tree.to_infix(), expected);
+}
+
+#[rstest]
+fn test_parse_nodes(sample_operators: Operators) {
+    let tree = TreeGenotype::from_sexpr("(+ y (sin 3))", &sample_operators).expect("Failed to parse S-expression!");
+
+    assert_eq!(tree.arena(), &vec![Node::function("+", 2), Node::Variable(1), Node::function("sin", 1), Node::Constant(3.0)]);
+    assert_eq!(tree.children()[&0], vec![1, 2]);
+}
+
+#[rstest]
+#[case("", ParseError::UnexpectedEnd)]
+#[case("x0 +", ParseError::UnexpectedEnd)]
+#[case("(x0 + x1", ParseError::UnexpectedEnd)]
+#[case("x0 + )", ParseError::UnexpectedToken(")".to_string()))]
+#[case("x0 x1", ParseError::TrailingTokens(1))]
+#[case("ln(x0)", ParseError::UnknownSymbol("ln".to_string()))]
+#[case("x2", ParseError::UnknownSymbol("x2".to_string()))]
+#[case("sin(x0, x1)", ParseError::ArityMismatch { symbol: "sin".to_string(), expected: 1, found: 2 })]
+#[case("sin", ParseError::ArityMismatch { symbol: "sin".to_string(), expected: 1, found: 0 })]
+fn test_infix_invalid(#[case] infix: &str, #[case] expected: ParseError, sample_operators: Operators) {
+    assert_eq!(TreeGenotype::from_infix(infix, &sample_operators), Err(expected));
+}
+
+#[rstest]
+#[case("", ParseError::UnexpectedEnd)]
+#[case("(+ x0", ParseError::UnexpectedEnd)]
+#[case(")", ParseError::UnexpectedToken(")".to_string()))]
+#[case("(+ x0 x1) x0", ParseError::TrailingTokens(1))]
+#[case("(* x0 (exp x1))", ParseError::UnknownSymbol("exp".to_string()))]
+#[case("(+ x0)", ParseError::ArityMismatch { symbol: "+".to_string(), expected: 2, found: 1 })]
+fn test_sexpr_invalid(#[case] sexpr: &str, #[case] expected: ParseError, sample_operators: Operators) {
+    assert_eq!(TreeGenotype::from_sexpr(sexpr, &sample_operators), Err(expected));
+}
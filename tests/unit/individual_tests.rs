@@ -41,7 +41,7 @@ fn test_from_genotype_vec() {
     let individuals = TreeIndividual::from_genotype_vec(&genotypes);
 
     assert_eq!(individuals.len(), 2);
-    assert_eq!(individuals[0].phenotype(), f64::NEG_INFINITY);
+    assert!(individuals[0].phenotype().is_nan());
 }
 
 #[test]
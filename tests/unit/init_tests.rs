@@ -3,12 +3,16 @@ use rand::SeedableRng;
 
 use rstest::{fixture, rstest};
 
-use mycoforge::common::traits::Initializer;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use mycoforge::tree::core::tree::TreeGenotype;
+use mycoforge::common::traits::{Initializer, PopulationInitializer};
+
+use mycoforge::tree::core::{node::Node, tree::TreeGenotype};
+use mycoforge::operators::builder::OperatorsBuilder;
 use mycoforge::operators::sampler::OperatorSampler;
 
-use mycoforge::tree::operators::init::Grow;
+use mycoforge::tree::operators::init::{Grow, RampedHalfAndHalf};
 
 fn valid_tree(tree: &TreeGenotype) -> bool {
     let mut result: usize = 0;
@@ -69,3 +73,66 @@ fn test_intializer_grow(sample_sampler: OperatorSampler) {
         );
     }
 }
+
+#[rstest]
+#[case(0, 0)]
+#[case(1, 3)]
+#[case(2, 5)]
+fn test_initializer_ramped_half_and_half(sample_sampler: OperatorSampler, #[case] min_depth: usize, #[case] max_depth: usize) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let init_scheme = RampedHalfAndHalf::new(min_depth, max_depth, false)
+        .expect("Failed to create RampedHalfAndHalf!");
+
+    let population = init_scheme.initialize_population(&mut rng, &sample_sampler, 60);
+    assert_eq!(population.len(), 60);
+
+    let depths = population.iter().map(|tree| tree.depth()).collect::<HashSet<usize>>();
+    assert!(population.iter().all(valid_tree));
+    assert_eq!(depths, (min_depth..=max_depth).collect::<HashSet<usize>>(),
+        "Depths should cover whole range ({}, {})! Found {:?}", min_depth, max_depth, depths
+    );
+}
+
+#[rstest]
+fn test_initializer_ramped_half_and_half_unique(sample_sampler: OperatorSampler) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let init_scheme = RampedHalfAndHalf::new(2, 5, true).expect("Failed to create RampedHalfAndHalf!");
+
+    let population = init_scheme.initialize_population(&mut rng, &sample_sampler, 100);
+    let unique = population.iter().cloned().collect::<HashSet<TreeGenotype>>();
+
+    assert_eq!(unique.len(), population.len(), "Population should not contain duplicates!");
+}
+
+#[rstest]
+fn test_initializer_ramped_half_and_half_invalid_range() {
+    assert!(RampedHalfAndHalf::new(3, 2, false).is_err());
+}
+
+#[rstest]
+fn test_initializer_as_population_initializer(sample_sampler: OperatorSampler) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let population = Grow::new(1, 3).initialize_population(&mut rng, &sample_sampler, 10);
+
+    assert_eq!(population.len(), 10);
+    assert!(population.iter().all(valid_tree));
+}
+
+#[rstest]
+fn test_initializer_ephemeral_constants() {
+    // Every sampled ephemeral terminal calls the generator, so constants are all different
+    let counter = AtomicUsize::new(0);
+    let operators = OperatorsBuilder::default()
+        .add_registered("+", "add", 1.0).expect("Failed to add function!")
+        .add_ephemeral(Box::new(move || counter.fetch_add(1, Ordering::Relaxed) as f64), 1.0).expect("Failed to add ephemeral!")
+        .build().expect("Failed to build operators!");
+    let mut rng = StdRng::seed_from_u64(42);
+    let population = Grow::new(2, 4).initialize_population(&mut rng, &operators.sampler(), 10);
+
+    let constants = population.iter()
+        .flat_map(|tree| tree.arena().iter().filter_map(|node| match node { Node::Constant(value) => Some(*value), _ => None }))
+        .collect::<Vec<f64>>();
+    let unique = constants.iter().map(|value| value.to_bits()).collect::<HashSet<u64>>();
+    assert!(constants.len() > 10);
+    assert_eq!(unique.len(), constants.len(), "Ephemeral constants were reused: {:?}", constants);
+}
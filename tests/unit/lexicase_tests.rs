@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rstest::*;
+
+use mycoforge::common::objectives::Direction;
+use mycoforge::common::traits::{Evaluator, Individual, Selector};
+use mycoforge::common::types::VectorFunction;
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::set::Operators;
+use mycoforge::optimizers::ga::{EABuilder, EAComponents};
+use mycoforge::optimizers::steady_state::SteadyStateReplacement;
+use mycoforge::optimizers::termination::{MaxGenerations, TerminationCriterion};
+use mycoforge::tree::core::{individual::TreeIndividual, tree::TreeGenotype};
+use mycoforge::tree::fitness::classify::Accuracy;
+use mycoforge::tree::fitness::evaluate::{Huber, LinearScaling, Loss, MAPE, MSE};
+use mycoforge::tree::fitness::parallel::ParallelEvaluator;
+use mycoforge::tree::operators::{
+    crossover::SubtreeCrossover, init::Grow, mutation::SubtreeMutation,
+    select::{median_absolute_deviation, EpsilonLexicaseSelection, LexicaseSelection}
+};
+
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_registered("+", "add", 0.5).expect("Failed to add function!")
+        .add_registered("*", "mul", 0.5).expect("Failed to add function!")
+        .add_variable("x", 0.5).expect("Failed to add variable!")
+        .add_constant(1.0, 0.5).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+}
+
+/// Individuals with distinct genotypes (`x`, `x + 1`, `x + 1 + 1`, ...) and given case errors.
+fn individuals(cases: &[Vec<f64>]) -> Vec<TreeIndividual<TreeGenotype>> {
+    let operators = sample_operators();
+    return cases.iter().enumerate().map(|(i, errors)| {
+        let infix = std::iter::once("x").chain(std::iter::repeat_n("1", i)).collect::<Vec<&str>>().join(" + ");
+        let tree = TreeGenotype::from_infix(&infix, &operators).expect("Failed to parse tree!");
+        let fitness = errors.iter().sum::<f64>();
+        return TreeIndividual::new(tree, fitness).with_cases(errors.clone());
+    }).collect();
+}
+
+/// Counts how often each individual is selected.
+fn selection_counts<S: Selector<TreeGenotype, I = TreeIndividual<TreeGenotype>>>(
+    selector: &S, population: &[TreeIndividual<TreeGenotype>], draws: usize
+) -> Vec<usize> {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut counts = vec![0; population.len()];
+    for _ in 0..draws {
+        let chosen = selector.select(&mut rng, population, Direction::Minimize);
+        let index = population.iter().position(|i| *i.genotype() == chosen).expect("Selected unknown genotype!");
+        counts[index] += 1;
+    }
+    return counts;
+}
+
+#[fixture]
+fn sample_dataset() -> Dataset {
+    let xs: Vec<f64> = (0..=10).map(|i| i as f64 / 10.0).collect();
+    let ys = xs.iter().map(|&v| v.powi(2) + v).collect::<Vec<f64>>();
+    return Dataset::new(vec!["x".to_string()], "y".to_string(), vec![xs], ys);
+}
+
+#[rstest]
+fn test_lexicase_selects_specialists() {
+    // Individual 2 has the best total error but is best on no single case, individual 3 is
+    // dominated on every case
+    let population = individuals(&[
+        vec![0.0, 5.0, 5.0],
+        vec![5.0, 0.0, 5.0],
+        vec![1.0, 1.0, 1.0],
+        vec![6.0, 6.0, 6.0],
+        vec![5.0, 5.0, 0.0],
+    ]);
+    let counts = selection_counts(&LexicaseSelection::new(), &population, 300);
+
+    assert_eq!(counts[2], 0, "Generalist should never be selected, found {:?}", counts);
+    assert_eq!(counts[3], 0, "Dominated individual should never be selected, found {:?}", counts);
+    for specialist in [0, 1, 4] {
+        assert!(counts[specialist] > 50, "Specialists should be selected evenly, found {:?}", counts);
+    }
+}
+
+#[rstest]
+fn test_lexicase_breaks_ties_randomly() {
+    let population = individuals(&[vec![1.0, 2.0], vec![1.0, 2.0], vec![3.0, 3.0]]);
+    let counts = selection_counts(&LexicaseSelection::new(), &population, 200);
+
+    assert_eq!(counts[2], 0);
+    assert!(counts[0] > 50 && counts[1] > 50, "Tied individuals should share selections, found {:?}", counts);
+}
+
+#[rstest]
+fn test_lexicase_nan_is_worst() {
+    let population = individuals(&[vec![f64::NAN, 0.0], vec![1e9, 0.0]]);
+    let counts = selection_counts(&LexicaseSelection::new(), &population, 50);
+
+    assert_eq!(counts, vec![0, 50]);
+}
+
+#[rstest]
+fn test_epsilon_lexicase_keeps_near_best() {
+    // Errors 1.0 and 1.1 are within MAD (0.1) of each other on every case, so plain lexicase
+    // always prefers individual 0 while epsilon-lexicase treats both as equal
+    let population = individuals(&[
+        vec![1.0, 1.0, 1.0],
+        vec![1.1, 1.1, 1.1],
+        vec![1.2, 1.2, 1.2],
+        vec![9.0, 9.0, 9.0],
+    ]);
+
+    assert_eq!(selection_counts(&LexicaseSelection::new(), &population, 100), vec![100, 0, 0, 0]);
+    let counts = selection_counts(&EpsilonLexicaseSelection::new(), &population, 200);
+    assert!(counts[0] > 50 && counts[1] > 50, "Near-best individuals should share selections, found {:?}", counts);
+    assert_eq!(counts[2] + counts[3], 0);
+}
+
+#[rstest]
+fn test_epsilon_lexicase_select_many_matches_select() {
+    // Epsilons shared across one select_many call are the same as computed by every select
+    let population = individuals(&[
+        vec![0.1, 3.0, 2.0, 0.5],
+        vec![1.0, 0.2, 2.5, 0.4],
+        vec![0.3, 0.9, 0.1, 2.0],
+        vec![2.0, 1.0, 0.7, 0.1],
+    ]);
+    let selection = EpsilonLexicaseSelection::new();
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let many = selection.select_many(&mut rng, &population, 50, Direction::Minimize);
+    let mut rng = StdRng::seed_from_u64(7);
+    let single = (0..50).map(|_| selection.select(&mut rng, &population, Direction::Minimize)).collect::<Vec<TreeGenotype>>();
+
+    assert_eq!(many, single);
+}
+
+#[rstest]
+#[should_panic]
+fn test_lexicase_requires_cases() {
+    let population = vec![TreeIndividual::new(TreeGenotype::default(), 1.0)];
+    let _ = LexicaseSelection::new().select(&mut StdRng::seed_from_u64(42), &population, Direction::Minimize);
+}
+
+#[rstest]
+#[should_panic]
+fn test_lexicase_requires_equal_case_counts() {
+    let population = individuals(&[vec![1.0, 2.0], vec![1.0]]);
+    let _ = EpsilonLexicaseSelection::new().select(&mut StdRng::seed_from_u64(42), &population, Direction::Minimize);
+}
+
+#[rstest]
+#[case(vec![1.0, 2.0, 3.0, 4.0, 100.0], 1.0)]
+#[case(vec![1.0, 2.0, 4.0, 7.0], 1.5)]
+#[case(vec![3.0, 3.0, 3.0], 0.0)]
+#[case(vec![f64::NAN, 2.0, f64::INFINITY, 4.0], 1.0)]
+#[case(vec![], 0.0)]
+fn test_median_absolute_deviation(#[case] errors: Vec<f64>, #[case] expected: f64) {
+    assert_eq!(median_absolute_deviation(&errors), expected);
+}
+
+#[rstest]
+fn test_case_errors() {
+    let predictions = [1.0, 3.0, -1.0];
+    let targets = [2.0, 1.0, -1.0];
+
+    assert_eq!(MSE::new().case_errors(&predictions, &targets), vec![1.0, 2.0, 0.0]);
+    assert_eq!(MAPE::new().case_errors(&predictions, &targets), vec![0.5, 2.0, 0.0]);
+    assert_eq!(Huber::default().case_errors(&predictions, &targets), vec![0.5, 1.5, 0.0]);
+    assert_eq!(Accuracy::default().case_errors(&[2.0, -2.0, 1.0], &[1.0, 1.0, 0.0]), vec![0.0, 1.0, 1.0]);
+
+    // Scaled predictions 2 * p fit targets exactly
+    let scaled = LinearScaling::new(MSE::new()).case_errors(&[1.0, 2.0, 3.0], &[2.0, 4.0, 6.0]);
+    assert_eq!(scaled, vec![0.0, 0.0, 0.0]);
+}
+
+#[rstest]
+fn test_evaluate_cases(sample_dataset: Dataset) {
+    let operators = sample_operators();
+    let map = operators.create_map();
+    let trees = ["x", "x * x + x", "x + 1"].iter()
+        .map(|infix| TreeGenotype::from_infix(infix, &operators).expect("Failed to parse tree!"))
+        .collect::<Vec<TreeGenotype>>();
+
+    let evaluator = MSE::new();
+    let sequential = evaluator.evaluate_population_cases(&trees, &sample_dataset, &map);
+    for (tree, (fitness, cases)) in trees.iter().zip(&sequential) {
+        assert_eq!(*fitness, evaluator.evaluate(tree, &sample_dataset, &map));
+        assert_eq!(cases.len(), sample_dataset.targets().len());
+    }
+    assert!(sequential[1].1.iter().all(|&error| error < 1e-12));
+
+    let parallel = ParallelEvaluator::new(MSE::new(), Some(2)).expect("Failed to create thread pool!");
+    assert_eq!(parallel.evaluate_population_cases(&trees, &sample_dataset, &map), sequential);
+}
+
+struct Components;
+impl EAComponents<TreeGenotype> for Components {
+    type I = TreeIndividual<TreeGenotype>;
+    type Init = Grow;
+    type Mut = SubtreeMutation;
+    type Cross = SubtreeCrossover;
+    type Eval = MSE;
+    type Sel = EpsilonLexicaseSelection;
+}
+
+fn build_ea(map: HashMap<String, (usize, VectorFunction)>, operators: &Operators) -> EABuilder<Components, TreeGenotype> {
+    return EABuilder::<Components, TreeGenotype>::new()
+        .set_initializer(Grow::new(1, 3))
+        .set_mutator(SubtreeMutation::new(0.2, (1, 2)).expect("Failed to create mutation scheme!"))
+        .set_crossoverer(SubtreeCrossover::new(0.9).expect("Failed to create crossover scheme!"))
+        .set_evaluator(MSE::new())
+        .set_selector(EpsilonLexicaseSelection::new())
+        .set_sampler(operators.sampler())
+        .set_map(map);
+}
+
+#[rstest]
+fn test_ea_with_lexicase(sample_dataset: Dataset) {
+    let operators = sample_operators();
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![Box::new(MaxGenerations::new(5))];
+    let rows = sample_dataset.targets().len();
+
+    // Cache is bypassed, as lexicase needs case errors of every individual
+    let ea = build_ea(operators.create_map(), &operators).set_cache_capacity(100).build().expect("Failed to build EA!");
+    let result = ea.run(&mut StdRng::seed_from_u64(42), 20, &sample_dataset, &criteria);
+    assert!(result.population().iter().all(|individual| individual.cases().len() == rows));
+    assert_eq!(result.evaluations(), 20 * 6);
+    assert!(ea.cache().expect("Cache should be enabled!").lock().expect("Failed to lock cache!").is_empty());
+
+    let steady_state = build_ea(operators.create_map(), &operators)
+        .build_steady_state(SteadyStateReplacement::Worst, 2).expect("Failed to build steady-state EA!");
+    let result = steady_state.run(&mut StdRng::seed_from_u64(42), 20, &sample_dataset, &criteria);
+    assert!(result.population().iter().all(|individual| individual.cases().len() == rows));
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use rstest::*;
+
+use mycoforge::common::traits::Evaluator;
+use mycoforge::common::types::VectorFunction;
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::functions::symbolic::{add, mul, sub};
+use mycoforge::operators::set::Operators;
+use mycoforge::tree::core::tree::TreeGenotype;
+use mycoforge::tree::fitness::evaluate::{
+    predict, EvaluatorError, Huber, LinearScaling, Loss, MAE, MAPE, MSE, MaxError, NRMSE, Normalization, R2, RMSE, SSE
+};
+
+#[fixture]
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_function("+", add, 2, 1.0 / 3.0).expect("Failed to add function!")
+        .add_function("-", sub, 2, 1.0 / 3.0).expect("Failed to add function!")
+        .add_function("*", mul, 2, 1.0 / 3.0).expect("Failed to add function!")
+        .add_variable("x", 0.8).expect("Failed to add variable!")
+        .add_constant(1.0, 0.2).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+}
+
+#[fixture]
+fn sample_dataset() -> Dataset {
+    // y = 2x
+    let xs = vec![1.0, 2.0, 3.0, 4.0];
+    let ys = xs.iter().map(|x| 2.0 * x).collect();
+    return Dataset::new(vec!["x".to_string()], "y".to_string(), vec![xs], ys);
+}
+
+/// Wraps boxed losses, so cases of different types can share a test.
+struct Boxed(Box<dyn Loss>);
+
+impl Loss for Boxed {
+    fn loss(&self, predictions: &[f64], targets: &[f64]) -> f64 { return self.0.loss(predictions, targets); }
+}
+
+/// Evaluates tree given in infix notation.
+fn evaluate<E: Evaluator<TreeGenotype, D = Dataset>>(evaluator: &E, infix: &str, operators: &Operators, dataset: &Dataset) -> f64 {
+    let tree = TreeGenotype::from_infix(infix, operators).expect("Failed to parse tree!");
+    return evaluator.evaluate(&tree, dataset, &operators.create_map());
+}
+
+#[rstest]
+fn test_predict(sample_operators: Operators) {
+    let tree = TreeGenotype::from_infix("x * x + 1", &sample_operators).expect("Failed to parse tree!");
+    let map: HashMap<String, (usize, VectorFunction)> = sample_operators.create_map();
+
+    assert_eq!(predict(&tree, &[vec![0.0, 1.0, -2.0]], &map), vec![1.0, 2.0, 5.0]);
+}
+
+#[rstest]
+#[case::sse(Box::new(SSE::new()), 14.0)]
+#[case::mse(Box::new(MSE::new()), 3.5)]
+#[case::rmse(Box::new(RMSE::new()), 3.5f64.sqrt())]
+#[case::mae(Box::new(MAE::new()), 1.5)]
+#[case::r2(Box::new(R2::new()), 14.0 / 20.0)]
+#[case::nrmse_range(Box::new(NRMSE::new(Normalization::Range)), 3.5f64.sqrt() / 6.0)]
+#[case::nrmse_std(Box::new(NRMSE::new(Normalization::StdDev)), (3.5f64 / 5.0).sqrt())]
+#[case::nrmse_mean(Box::new(NRMSE::new(Normalization::Mean)), 3.5f64.sqrt() / 5.0)]
+#[case::mape(Box::new(MAPE::new()), (0.0 + 0.25 + 1.0 / 3.0 + 0.375) / 4.0)]
+#[case::huber(Box::new(Huber::default()), (0.0 + 0.5 + 1.5 + 2.5) / 4.0)]
+#[case::max_error(Box::new(MaxError::new()), 3.0)]
+// Predictions of `x + 1` are [2, 3, 4, 5] against targets [2, 4, 6, 8]
+fn test_metrics(#[case] evaluator: Box<dyn Loss>, #[case] expected: f64, sample_operators: Operators, sample_dataset: Dataset) {
+    let targets = sample_dataset.targets();
+    let predictions = [2.0, 3.0, 4.0, 5.0];
+    let result = evaluator.loss(&predictions, targets);
+    assert!((result - expected).abs() < 1e-12, "{} != {}", result, expected);
+
+    let perfect = evaluator.loss(targets, targets);
+    assert_eq!(perfect, 0.0);
+
+    // Same through tree evaluation
+    let result = evaluate(&Boxed(evaluator), "x + 1", &sample_operators, &sample_dataset);
+    assert!((result - expected).abs() < 1e-12, "{} != {}", result, expected);
+}
+
+#[rstest]
+fn test_mse_matches_manual_reduction(sample_operators: Operators) {
+    let xs: Vec<f64> = (0..50).map(|i| i as f64 * 0.37 - 9.0).collect();
+    let ys: Vec<f64> = xs.iter().map(|x| x * x * x - 0.1).collect();
+    let dataset = Dataset::new(vec!["x".to_string()], "y".to_string(), vec![xs.clone()], ys.clone());
+
+    let result = evaluate(&MSE::new(), "x * x - x * 1", &sample_operators, &dataset);
+    let expected = xs.iter().zip(ys.iter())
+        .map(|(x, y)| (x * x - x * 1.0 - y).powi(2))
+        .sum::<f64>() / (ys.len() as f64);
+    assert_eq!(result.to_bits(), expected.to_bits());
+    assert_eq!(evaluate(&RMSE::new(), "x * x - x * 1", &sample_operators, &dataset).to_bits(), expected.sqrt().to_bits());
+}
+
+#[rstest]
+fn test_degenerate_targets() {
+    let targets = [3.0, 3.0, 3.0];
+
+    assert_eq!(R2::new().loss(&[3.0, 3.0, 4.0], &targets), 1.0);
+    assert_eq!(NRMSE::default().loss(&[3.0, 3.0, 4.0], &targets), f64::INFINITY);
+    assert_eq!(NRMSE::new(Normalization::StdDev).loss(&targets, &targets), 0.0);
+
+    let result = MAPE::new().loss(&[1.0], &[0.0]);
+    assert!(result.is_finite() && result > 1e15);
+}
+
+#[rstest]
+fn test_nan_predictions() {
+    let targets = [1.0, 2.0];
+    let predictions = [f64::NAN, 2.0];
+
+    assert!(MAE::new().loss(&predictions, &targets).is_nan());
+    assert!(Huber::default().loss(&predictions, &targets).is_nan());
+    assert!(MaxError::new().loss(&predictions, &targets).is_nan());
+    assert!(MaxError::new().loss(&[2.0, f64::NAN], &targets).is_nan());
+}
+
+#[rstest]
+#[case(1.0, 1.0, 0.5)]
+#[case(1.0, 3.0, 2.5)]
+#[case(0.5, 1.0, 0.375)]
+#[case(3.0, 2.0, 2.0)]
+fn test_huber_delta(#[case] delta: f64, #[case] error: f64, #[case] expected: f64) {
+    let evaluator = Huber::new(delta).expect("Failed to create evaluator!");
+
+    assert_eq!(evaluator.delta(), delta);
+    assert_eq!(evaluator.loss(&[error], &[0.0]), expected);
+    assert_eq!(evaluator.loss(&[0.0], &[error]), expected);
+}
+
+#[rstest]
+#[case(0.0)]
+#[case(-1.0)]
+#[case(f64::NAN)]
+#[case(f64::INFINITY)]
+fn test_huber_invalid_delta(#[case] delta: f64) {
+    assert!(matches!(Huber::new(delta), Err(EvaluatorError::InvalidDelta(_))));
+}
+
+#[rstest]
+fn test_linear_scaling(sample_operators: Operators, sample_dataset: Dataset) {
+    let evaluator = LinearScaling::new(MSE::new());
+    let map = sample_operators.create_map();
+
+    // Targets are 2x, so any affine transformation of x is exact
+    for infix in ["x", "x + 1", "x * x * 0 + x - 1", "1 - x"] {
+        let result = evaluate(&evaluator, infix, &sample_operators, &sample_dataset);
+        assert!(result.abs() < 1e-20, "Scaled error of {} should vanish, found {}", infix, result);
+    }
+    let tree = TreeGenotype::from_infix("1 - x", &sample_operators).expect("Failed to parse tree!");
+    assert_eq!(evaluator.coefficients(&tree, &sample_dataset, &map), (2.0, -2.0));
+
+    // Scaling never makes squared error worse
+    let unscaled = evaluate(&MSE::new(), "x * x", &sample_operators, &sample_dataset);
+    let scaled = evaluate(&evaluator, "x * x", &sample_operators, &sample_dataset);
+    assert!(scaled < unscaled, "{} >= {}", scaled, unscaled);
+}
+
+#[rstest]
+fn test_linear_scaling_wraps_any_loss(sample_operators: Operators, sample_dataset: Dataset) {
+    assert!(evaluate(&LinearScaling::new(MAE::new()), "x + 1", &sample_operators, &sample_dataset).abs() < 1e-12);
+    assert!(evaluate(&LinearScaling::new(R2::new()), "x * 1", &sample_operators, &sample_dataset).abs() < 1e-12);
+    assert_eq!(LinearScaling::new(MaxError::new()).inner().loss(&[1.0], &[3.0]), 2.0);
+}
+
+#[rstest]
+fn test_linear_scaling_degenerate() {
+    // Constant predictions are scaled to the mean of targets
+    assert_eq!(LinearScaling::<MSE>::fit(&[5.0, 5.0, 5.0], &[1.0, 2.0, 3.0]), (2.0, 0.0));
+    assert_eq!(LinearScaling::new(MSE::new()).loss(&[5.0, 5.0, 5.0], &[1.0, 2.0, 3.0]), 2.0 / 3.0);
+
+    // Invalid predictions stay invalid
+    assert!(LinearScaling::new(MSE::new()).loss(&[f64::NAN, 1.0], &[1.0, 2.0]).is_nan());
+    assert!(LinearScaling::new(MSE::new()).loss(&[f64::INFINITY, 1.0], &[1.0, 2.0]).is_nan());
+}
@@ -1,16 +1,31 @@
 mod tree_tests;
+mod node_tests;
+mod expression_tests;
+mod export_tests;
 mod individual_tests;
 
 mod functions_test;
 mod set_tests;
+mod registry_tests;
 mod sampler_tests;
 
 mod init_tests;
 mod mutation_tests;
 mod crossover_tests;
+mod simplify_tests;
 
 mod evaluate_tests;
+mod metrics_tests;
+mod classify_tests;
+mod parallel_tests;
+mod compile_tests;
+mod model_tests;
+mod cache_tests;
 mod select_tests;
+mod lexicase_tests;
+mod parsimony_tests;
+mod replacement_tests;
+mod pareto_tests;
 
 mod dataset_tests;
 mod logger_tests;
@@ -0,0 +1,254 @@
+use std::time::Duration;
+
+use rstest::*;
+
+use mycoforge::common::traits::{Evaluator, Individual};
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::functions::symbolic::add;
+use mycoforge::operators::set::Operators;
+use mycoforge::optimizers::ga::RunResult;
+use mycoforge::optimizers::stats::GenerationStats;
+use mycoforge::tree::core::errors::TreeError;
+use mycoforge::tree::core::individual::TreeIndividual;
+use mycoforge::tree::core::tree::TreeGenotype;
+use mycoforge::tree::fitness::evaluate::{LinearScaling, MSE};
+use mycoforge::tree::model::{ModelError, ModelFormat, SymbolicModel, TrainingMetadata};
+
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_registered("+", "add", 0.2).expect("Failed to add function!")
+        .add_registered("-", "sub", 0.2).expect("Failed to add function!")
+        .add_registered("*", "mul", 0.2).expect("Failed to add function!")
+        .add_registered("/", "protected_div", 0.2).expect("Failed to add function!")
+        .add_registered("sin", "sin", 0.2).expect("Failed to add function!")
+        .add_variable("x", 0.4).expect("Failed to add variable!")
+        .add_variable("y", 0.4).expect("Failed to add variable!")
+        .add_constant(2.5, 0.2).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+}
+
+#[fixture]
+fn sample_dataset() -> Dataset {
+    let xs = vec![-2.0, -0.5, 0.0, 0.25, 1.0, 3.0];
+    let ys = vec![1.0, 0.0, -1.5, 2.0, 0.5, -3.0];
+    let targets = xs.iter().zip(ys.iter()).map(|(x, y)| x / y + 1.0).collect();
+    return Dataset::new(vec!["x".to_string(), "y".to_string()], "z".to_string(), vec![xs, ys], targets);
+}
+
+#[fixture]
+fn sample_model(sample_dataset: Dataset) -> SymbolicModel {
+    let operators = sample_operators();
+    let tree = TreeGenotype::from_infix("x / y + sin(x * 2.5) - 1", &operators).expect("Failed to parse tree!");
+    let fitness = MSE::new().evaluate(&tree, &sample_dataset, &operators.create_map());
+    let history = vec![GenerationStats::new(0, 10, 1.5, 2.0, f64::INFINITY), GenerationStats::new(1, 20, fitness, 1.0, 3.0)];
+    let metadata = TrainingMetadata::new(1, 20, Duration::from_millis(1500), history);
+    return SymbolicModel::new(tree, operators, vec!["x".to_string(), "y".to_string()], "z".to_string(), fitness, metadata);
+}
+
+fn assert_same_model(found: &SymbolicModel, expected: &SymbolicModel, dataset: &Dataset) {
+    assert_eq!(found.tree(), expected.tree());
+    assert_eq!(found.feature_names(), expected.feature_names());
+    assert_eq!(found.target_name(), expected.target_name());
+    assert_eq!(found.fitness(), expected.fitness());
+    assert_eq!(found.metadata(), expected.metadata());
+    assert_eq!(found.intercept(), expected.intercept());
+    assert_eq!(found.slope(), expected.slope());
+    assert_eq!(found.predict(dataset).expect("Failed to predict!"), expected.predict(dataset).expect("Failed to predict!"));
+}
+
+#[rstest]
+fn test_predict(sample_model: SymbolicModel, sample_dataset: Dataset) {
+    let predictions = sample_model.predict(&sample_dataset).expect("Failed to predict!");
+
+    let (_, targets) = (sample_dataset.features(), sample_dataset.targets());
+    let mse = predictions.iter().zip(targets.iter()).map(|(p, t)| (p - t).powi(2)).sum::<f64>() / targets.len() as f64;
+    assert_eq!(mse, sample_model.fitness());
+
+    for (row, &expected) in predictions.iter().enumerate() {
+        let values = sample_dataset.features().iter().map(|column| column[row]).collect::<Vec<f64>>();
+        assert_eq!(sample_model.predict_row(&values).expect("Failed to predict!"), expected);
+    }
+}
+
+/// Agrees with addition on small inputs only.
+fn saturating_add(args: &[&[f64]]) -> Vec<f64> {
+    return args[0].iter().zip(args[1].iter()).map(|(a, b)| (a + b).clamp(-10.0, 10.0)).collect();
+}
+
+#[rstest]
+fn test_predict_with_custom_function() {
+    let operators = OperatorsBuilder::default()
+        .add_function("+", saturating_add, 2, 1.0).expect("Failed to add function!")
+        .add_variable("x", 1.0).expect("Failed to add variable!")
+        .build().expect("Failed to build operators!");
+    let tree = TreeGenotype::from_infix("x + x", &operators).expect("Failed to parse tree!");
+    let dataset = Dataset::new(vec!["x".to_string()], "z".to_string(), vec![vec![1.0, 100.0]], vec![2.0, 20.0]);
+    let fitness = MSE::new().evaluate(&tree, &dataset, &operators.create_map());
+    let model = SymbolicModel::new(tree, operators, vec!["x".to_string()], "z".to_string(), fitness, TrainingMetadata::default());
+
+    // Predictions follow the evaluated tree, also on repeated calls reusing the compiled program
+    assert_eq!(model.predict(&dataset).expect("Failed to predict!"), vec![2.0, 10.0]);
+    assert_eq!(model.predict_row(&[100.0]).expect("Failed to predict!"), 10.0);
+    assert_eq!(model.predict_row(&[-100.0]).expect("Failed to predict!"), -10.0);
+    assert_eq!(fitness, 50.0);
+}
+
+#[rstest]
+fn test_predict_matches_features_by_name(sample_model: SymbolicModel, sample_dataset: Dataset) {
+    let (xs, ys) = (sample_dataset.features()[0].clone(), sample_dataset.features()[1].clone());
+    let reordered = Dataset::new(vec!["w".to_string(), "y".to_string(), "x".to_string()], "z".to_string(),
+        vec![vec![0.0; xs.len()], ys, xs], sample_dataset.targets().clone());
+
+    assert_eq!(sample_model.predict(&reordered).expect("Failed to predict!"), sample_model.predict(&sample_dataset).expect("Failed to predict!"));
+}
+
+#[rstest]
+fn test_predict_errors(sample_model: SymbolicModel) {
+    let missing = Dataset::new(vec!["x".to_string()], "z".to_string(), vec![vec![1.0]], vec![1.0]);
+
+    assert!(matches!(sample_model.predict(&missing), Err(ModelError::MissingFeature(name)) if name == "y"));
+    assert!(matches!(sample_model.predict_row(&[1.0]), Err(ModelError::DimensionMismatch { expected: 2, found: 1 })));
+}
+
+#[rstest]
+#[case(ModelFormat::Json)]
+#[case(ModelFormat::Binary)]
+fn test_round_trip(#[case] format: ModelFormat, sample_model: SymbolicModel, sample_dataset: Dataset) {
+    let bytes = sample_model.to_bytes(format).expect("Failed to encode model!");
+    let loaded = SymbolicModel::from_bytes(&bytes, format).expect("Failed to decode model!");
+
+    assert_same_model(&loaded, &sample_model, &sample_dataset);
+}
+
+#[rstest]
+fn test_binary_is_compact(sample_model: SymbolicModel) {
+    let json = sample_model.to_bytes(ModelFormat::Json).expect("Failed to encode model!");
+    let binary = sample_model.to_bytes(ModelFormat::Binary).expect("Failed to encode model!");
+
+    assert!(binary.len() < json.len(), "Binary model has {} bytes, JSON {}", binary.len(), json.len());
+    assert!(SymbolicModel::from_bytes(&binary, ModelFormat::Json).is_err());
+}
+
+#[rstest]
+fn test_save_load(sample_model: SymbolicModel, sample_dataset: Dataset) {
+    let directory = std::env::temp_dir().join(format!("mycoforge_model_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).expect("Failed to create directory!");
+
+    for (format, file) in [(ModelFormat::Json, "model.json"), (ModelFormat::Binary, "model.cbor")] {
+        let path = directory.join(file);
+        let path = path.to_str().expect("Path is not UTF-8!");
+        sample_model.save(path, format).expect("Failed to save model!");
+
+        let loaded = SymbolicModel::load(path, format).expect("Failed to load model!");
+        assert_same_model(&loaded, &sample_model, &sample_dataset);
+    }
+    assert!(matches!(SymbolicModel::load(directory.join("missing.json").to_str().unwrap(), ModelFormat::Json), Err(ModelError::IoError(_))));
+    let _ = std::fs::remove_dir_all(&directory);
+}
+
+#[rstest]
+fn test_unregistered_operators_cannot_be_saved() {
+    let operators = OperatorsBuilder::default()
+        .add_function("+", add, 2, 1.0).expect("Failed to add function!")
+        .add_variable("x", 1.0).expect("Failed to add variable!")
+        .build().expect("Failed to build operators!");
+    let tree = TreeGenotype::from_infix("x + x", &operators).expect("Failed to parse tree!");
+    let model = SymbolicModel::new(tree, operators, vec!["x".to_string()], "y".to_string(), 0.0, TrainingMetadata::default());
+
+    assert_eq!(model.predict_row(&[1.5]).expect("Failed to predict!"), 3.0);
+    assert!(matches!(model.to_bytes(ModelFormat::Json), Err(ModelError::SerializationError(_))));
+}
+
+#[rstest]
+fn test_from_run(sample_dataset: Dataset) {
+    let operators = sample_operators();
+    let tree = TreeGenotype::from_infix("x / y + 1", &operators).expect("Failed to parse tree!");
+    let best = TreeIndividual::new(tree.clone(), 0.0);
+    let history = vec![GenerationStats::new(0, 4, 1.0, 2.0, 3.0), GenerationStats::new(1, 8, 0.0, 1.0, 2.0)];
+    let result = RunResult::new(best.clone(), vec![best], history.clone(), 8, Duration::from_secs(2));
+
+    let model = SymbolicModel::from_run(&result, operators, &sample_dataset);
+
+    assert_eq!(model.tree(), &tree);
+    assert_eq!(model.fitness(), result.best().phenotype());
+    assert_eq!(model.feature_names(), sample_dataset.feature_names());
+    assert_eq!(model.target_name(), "z");
+    assert_eq!(model.metadata(), &TrainingMetadata::new(1, 8, Duration::from_secs(2), history));
+}
+
+#[rstest]
+fn test_from_scaled_run() {
+    let operators = sample_operators();
+    let xs = vec![-2.0, -0.5, 0.0, 0.25, 1.0, 3.0];
+    let ys = vec![1.0, 4.0, -1.5, 2.0, 0.5, -3.0];
+    let targets = xs.iter().zip(ys.iter()).map(|(x, y)| x / y + 1.0).collect();
+    let sample_dataset = Dataset::new(vec!["x".to_string(), "y".to_string()], "z".to_string(), vec![xs, ys], targets);
+    // Targets are x / y + 1, so the tree is exact with intercept 1 and slope 0.4
+    let tree = TreeGenotype::from_infix("x / y * 2.5", &operators).expect("Failed to parse tree!");
+    let evaluator = LinearScaling::new(MSE::new());
+    let fitness = evaluator.evaluate(&tree, &sample_dataset, &operators.create_map());
+    let best = TreeIndividual::new(tree, fitness);
+    let history = vec![GenerationStats::new(0, 1, fitness, fitness, fitness)];
+    let result = RunResult::new(best.clone(), vec![best], history, 1, Duration::from_secs(1));
+
+    let model = SymbolicModel::from_scaled_run(&result, operators, &sample_dataset, &evaluator);
+
+    assert!((model.intercept() - 1.0).abs() < 1e-12 && (model.slope() - 0.4).abs() < 1e-12,
+        "Expected intercept 1 and slope 0.4, found {} and {}", model.intercept(), model.slope());
+    let predictions = model.predict(&sample_dataset).expect("Failed to predict!");
+    for (prediction, target) in predictions.iter().zip(sample_dataset.targets()) {
+        assert!((prediction - target).abs() < 1e-12, "{} != {}", prediction, target);
+    }
+    assert!((model.predict_row(&[3.0, -3.0]).expect("Failed to predict!") - 0.0).abs() < 1e-12);
+
+    for format in [ModelFormat::Json, ModelFormat::Binary] {
+        let loaded = SymbolicModel::from_bytes(&model.to_bytes(format).expect("Failed to encode model!"), format)
+            .expect("Failed to decode model!");
+        assert_same_model(&loaded, &model, &sample_dataset);
+    }
+}
+
+#[rstest]
+fn test_unscaled_model_defaults(sample_model: SymbolicModel, sample_dataset: Dataset) {
+    assert_eq!((sample_model.intercept(), sample_model.slope()), (0.0, 1.0));
+
+    // Models saved before linear scaling existed have no coefficients
+    let mut json: serde_json::Value = serde_json::from_slice(&sample_model.to_bytes(ModelFormat::Json)
+        .expect("Failed to encode model!")).expect("Failed to parse JSON!");
+    let object = json.as_object_mut().expect("Model is not a JSON object!");
+    object.remove("intercept");
+    object.remove("slope");
+    let loaded = SymbolicModel::from_bytes(&serde_json::to_vec(&json).expect("Failed to encode JSON!"), ModelFormat::Json)
+        .expect("Failed to decode model!");
+    assert_same_model(&loaded, &sample_model, &sample_dataset);
+}
+
+#[rstest]
+fn test_load_rejects_invalid_tree(sample_model: SymbolicModel) {
+    // Binary root without children would underflow the compiler on first prediction
+    let mut json: serde_json::Value = serde_json::from_slice(&sample_model.to_bytes(ModelFormat::Json)
+        .expect("Failed to encode model!")).expect("Failed to parse JSON!");
+    json["tree"]["children"] = serde_json::json!({});
+    let bytes = serde_json::to_vec(&json).expect("Failed to encode JSON!");
+    assert!(matches!(SymbolicModel::from_bytes(&bytes, ModelFormat::Json), Err(ModelError::InvalidTree(_))));
+
+    // Variable y has no feature name
+    let operators = sample_operators();
+    let tree = TreeGenotype::from_infix("x + y", &operators).expect("Failed to parse tree!");
+    let model = SymbolicModel::new(tree, operators, vec!["x".to_string()], "z".to_string(), 0.0, TrainingMetadata::default());
+    let bytes = model.to_bytes(ModelFormat::Binary).expect("Failed to encode model!");
+    assert!(matches!(SymbolicModel::from_bytes(&bytes, ModelFormat::Binary),
+        Err(ModelError::InvalidTree(TreeError::UnknownSymbol { index: 2, .. }))));
+}
+
+#[rstest]
+fn test_predict_without_features() {
+    let operators = sample_operators();
+    let tree = TreeGenotype::from_infix("2.5 * 2.5", &operators).expect("Failed to parse tree!");
+    let model = SymbolicModel::new(tree, operators, Vec::new(), "z".to_string(), 0.0, TrainingMetadata::default());
+    let dataset = Dataset::new(Vec::new(), "z".to_string(), Vec::new(), vec![1.0, 2.0, 3.0]);
+
+    assert_eq!(model.predict_row(&[]).expect("Failed to predict!"), 6.25);
+    assert_eq!(model.predict(&dataset).expect("Failed to predict!"), vec![6.25; 3]);
+}
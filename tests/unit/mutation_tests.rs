@@ -7,6 +7,7 @@ use mycoforge::common::traits::{Initializer, Mutator};
 
 use mycoforge::operators::sampler::OperatorSampler;
 
+use mycoforge::tree::core::node::Node;
 use mycoforge::tree::core::tree::TreeGenotype;
 
 use mycoforge::tree::operators::init::Grow;
@@ -140,8 +141,7 @@ fn test_point_mutation(sample_sampler: OperatorSampler) {
 fn test_constant_mutation_basic(sample_sampler: OperatorSampler) {
     let mut rng = StdRng::seed_from_u64(42);
 
-    let arena = vec!["0.5".to_string()];
-    let tree = TreeGenotype::with_arena(arena);
+    let tree = TreeGenotype::from_tokens(&["0.5"], &sample_sampler).expect("Failed to create tree!");
 
     let mutator = ConstantMutation::new(1.0, 0.1, None)
         .expect("Failed to create mutation scheme!");
@@ -150,7 +150,7 @@ fn test_constant_mutation_basic(sample_sampler: OperatorSampler) {
     assert_ne!(mutant.arena()[0], tree.arena()[0],
         "Value should have changed! Original {}, found {}", tree.arena()[0], mutant.arena()[0]
     );
-    assert!(mutant.arena()[0].parse::<f64>().is_ok(),
+    assert!(matches!(mutant.arena()[0], Node::Constant(_)),
         "Invalid float was created! Original {}, found {}", tree.arena()[0], mutant.arena()[0]
     );
 }
@@ -159,8 +159,7 @@ fn test_constant_mutation_basic(sample_sampler: OperatorSampler) {
 fn test_constant_mutation_no_constants(sample_sampler: OperatorSampler) {
     let mut rng = StdRng::seed_from_u64(42);
 
-    let arena = vec!["x".to_string()];
-    let tree = TreeGenotype::with_arena(arena);
+    let tree = TreeGenotype::from_tokens(&["x"], &sample_sampler).expect("Failed to create tree!");
 
     let mutator = ConstantMutation::new(1.0, 0.1, None)
         .expect("Failed to create mutation scheme!");
@@ -175,8 +174,7 @@ fn test_constant_mutation_no_constants(sample_sampler: OperatorSampler) {
 fn test_constant_mutation(sample_sampler: OperatorSampler) {
     let mut rng = StdRng::seed_from_u64(42);
 
-    let arena = ["+", "1.0", "3.14"].iter().map(|s| s.to_string()).collect::<Vec<String>>();
-    let tree = TreeGenotype::with_arena(arena);
+    let tree = TreeGenotype::from_tokens(&["+", "1.0", "3.14"], &sample_sampler).expect("Failed to create tree!");
 
     let mutator = ConstantMutation::new(1.0, 0.1, None)
         .expect("Failed to create mutation scheme!");
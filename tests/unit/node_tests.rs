@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use rstest::rstest;
+
+use mycoforge::common::traits::Evaluator;
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::functions::symbolic::{add, mul};
+use mycoforge::tree::core::node::{intern, symbol, Node};
+use mycoforge::tree::core::tree::TreeGenotype;
+use mycoforge::tree::fitness::evaluate::MSE;
+
+#[rstest]
+fn test_intern() {
+    let id = intern("node_tests_function");
+
+    assert_eq!(intern("node_tests_function"), id);
+    assert_ne!(intern("node_tests_other"), id);
+    assert_eq!(symbol(id), "node_tests_function");
+}
+
+#[rstest]
+#[case(Node::function("+", 2), 2, "+")]
+#[case(Node::Variable(3), 0, "x3")]
+#[case(Node::Constant(-1.5), 0, "-1.5")]
+fn test_node_properties(#[case] node: Node, #[case] arity: usize, #[case] name: &str) {
+    assert_eq!(node.arity(), arity);
+    assert_eq!(node.name(), name);
+    assert_eq!(node.is_function(), matches!(node, Node::Function(_, _)));
+    assert_eq!(node.is_terminal(), arity == 0);
+}
+
+#[rstest]
+fn test_node_equality() {
+    assert_eq!(Node::function("+", 2), Node::function("+", 2));
+    assert_ne!(Node::Variable(1), Node::Constant(1.0));
+    assert_ne!(Node::function("+", 2), Node::function("+", 3));
+}
+
+#[cfg(feature = "serde")]
+#[rstest]
+fn test_node_serde() {
+    let arena = vec![Node::function("+", 2), Node::Variable(0), Node::Constant(2.0)];
+    let tree = TreeGenotype::with_arena(arena);
+
+    let json = serde_json::to_string(&tree).expect("Failed to serialize tree!");
+    assert!(json.contains("\"+\""), "Functions should be serialized by name! Found {}", json);
+
+    let restored: TreeGenotype = serde_json::from_str(&json).expect("Failed to deserialize tree!");
+    assert_eq!(restored, tree);
+}
+
+#[rstest]
+fn test_evaluate_variables_and_constants() {
+    let operators = OperatorsBuilder::default()
+        .add_function("+", add, 2, 0.5).expect("Failed to add function!")
+        .add_function("*", mul, 2, 0.5).expect("Failed to add function!")
+        .add_variable("a", 0.4).expect("Failed to add variable!")
+        .add_variable("b", 0.4).expect("Failed to add variable!")
+        .add_constant(2.0, 0.2).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+    let sampler = operators.sampler();
+
+    // a * b + 2
+    let tree = TreeGenotype::from_tokens(&["+", "*", "a", "b", "2"], &sampler).expect("Failed to create tree!");
+    assert_eq!(tree.arena()[2], Node::Variable(0));
+    assert_eq!(tree.arena()[3], Node::Variable(1));
+    assert_eq!(tree.arena()[4], Node::Constant(2.0));
+
+    let dataset = Dataset::new(
+        vec!["a".to_string(), "b".to_string()], "y".to_string(),
+        vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]], vec![6.0, 12.0, 20.0]
+    );
+    let map: HashMap<_, _> = operators.create_map();
+
+    assert_eq!(MSE::new().evaluate(&tree, &dataset, &map), 0.0);
+}
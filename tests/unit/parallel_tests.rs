@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, SeedableRng};
+use rstest::{fixture, rstest};
+
+use mycoforge::common::traits::{Evaluator, Initializer};
+use mycoforge::common::types::VectorFunction;
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::functions::symbolic::{add, sub, mul, div};
+use mycoforge::operators::sampler::OperatorSampler;
+
+use mycoforge::tree::core::tree::TreeGenotype;
+use mycoforge::tree::fitness::evaluate::MSE;
+use mycoforge::tree::fitness::parallel::ParallelEvaluator;
+use mycoforge::tree::operators::init::Grow;
+
+fn x(args: &[&[f64]]) -> Vec<f64> { return args[0].to_vec(); }
+
+#[fixture]
+fn sample_map() -> HashMap<String, (usize, VectorFunction)> {
+    let mut map: HashMap<String, (usize, VectorFunction)> = HashMap::new();
+    map.insert("+".to_string(), (2, add));
+    map.insert("-".to_string(), (2, sub));
+    map.insert("*".to_string(), (2, mul));
+    map.insert("/".to_string(), (2, div));
+    map.insert("x".to_string(), (0, x));
+    return map;
+}
+
+#[fixture]
+fn sample_population() -> Vec<TreeGenotype> {
+    let operators: Vec<String> = ["+", "-", "*", "/", "x"].iter().map(|&w| w.to_string()).collect();
+    let sampler = OperatorSampler::new(operators, vec![2, 2, 2, 2, 0], vec![0.2; 5]);
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let init_scheme = Grow::new(2, 6);
+    return (0..100).map(|_| init_scheme.initialize(&mut rng, &sampler)).collect();
+}
+
+#[fixture]
+fn sample_dataset() -> Dataset {
+    let xs: Vec<f64> = (0..1000).map(|i| i as f64 / 100.0).collect();
+    let ys = xs.iter().map(|&v| v.powi(3) - v).collect::<Vec<f64>>();
+
+    return Dataset::new(vec!["x".to_string()], "y".to_string(), vec![xs], ys);
+}
+
+#[rstest]
+#[case(None)]
+#[case(Some(1))]
+#[case(Some(4))]
+fn test_parallel_matches_sequential(
+    #[case] threads: Option<usize>,
+    sample_population: Vec<TreeGenotype>, sample_dataset: Dataset,
+    sample_map: HashMap<String, (usize, VectorFunction)>
+) {
+    let sequential = MSE::new().evaluate_population(&sample_population, &sample_dataset, &sample_map);
+
+    let evaluator = ParallelEvaluator::new(MSE::new(), threads)
+        .expect("Failed to create ParallelEvaluator!");
+    let parallel = evaluator.evaluate_population(&sample_population, &sample_dataset, &sample_map);
+
+    assert_eq!(parallel.len(), sample_population.len());
+    assert!(sequential.iter().zip(parallel.iter()).all(|(a, b)| a.to_bits() == b.to_bits()),
+        "Parallel evaluation differs from sequential! threads {:?}", threads
+    );
+}
+
+#[rstest]
+fn test_single_evaluation_is_delegated(
+    sample_population: Vec<TreeGenotype>, sample_dataset: Dataset,
+    sample_map: HashMap<String, (usize, VectorFunction)>
+) {
+    let evaluator = ParallelEvaluator::from(MSE::new());
+    let tree = &sample_population[0];
+
+    assert_eq!(
+        evaluator.evaluate(tree, &sample_dataset, &sample_map).to_bits(),
+        MSE::new().evaluate(tree, &sample_dataset, &sample_map).to_bits()
+    );
+}
@@ -0,0 +1,209 @@
+use std::cmp::Ordering;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rstest::*;
+
+use mycoforge::common::objectives::{dominates, Direction, Objectives};
+use mycoforge::common::traits::{Individual, Selector};
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::set::Operators;
+use mycoforge::optimizers::nsga2::{
+    crowding_distance, non_dominated_sort, pareto_front, rank_population, select_survivors,
+    CrowdedTournament, MultiObjectiveIndividual
+};
+use mycoforge::tree::core::tree::TreeGenotype;
+use mycoforge::tree::fitness::complexity::{Depth, Size};
+use mycoforge::tree::fitness::evaluate::MSE;
+
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_registered("+", "add", 0.5).expect("Failed to add function!")
+        .add_registered("*", "mul", 0.5).expect("Failed to add function!")
+        .add_variable("x", 0.5).expect("Failed to add variable!")
+        .add_constant(1.0, 0.5).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+}
+
+/// Individuals with distinct genotypes (`x`, `x + 1`, `x + 1 + 1`, ...), so selection results
+/// can be told apart.
+fn individuals(objectives: &[[f64; 2]]) -> Vec<MultiObjectiveIndividual<TreeGenotype>> {
+    let operators = sample_operators();
+    return objectives.iter().enumerate().map(|(i, values)| {
+        let infix = std::iter::once("x").chain(std::iter::repeat_n("1", i)).collect::<Vec<&str>>().join(" + ");
+        let tree = TreeGenotype::from_infix(&infix, &operators).expect("Failed to parse tree!");
+        return MultiObjectiveIndividual::new(tree, values.to_vec());
+    }).collect();
+}
+
+/// Error and size of five models: three on the front, one dominated once and one twice.
+#[fixture]
+fn sample_objectives() -> Vec<[f64; 2]> {
+    return vec![[0.1, 9.0], [0.5, 5.0], [0.6, 7.0], [2.0, 1.0], [0.7, 9.0]];
+}
+
+#[rstest]
+fn test_direction() {
+    assert!(Direction::Minimize.is_better(1.0, 2.0));
+    assert!(Direction::Maximize.is_better(2.0, 1.0));
+    assert!(!Direction::Maximize.is_better(1.0, 1.0));
+    assert!(Direction::Minimize.is_better(f64::INFINITY, f64::NAN));
+    assert_eq!(Direction::Minimize.compare(f64::NAN, f64::NAN), Ordering::Equal);
+    assert_eq!(Direction::Maximize.key(3.0), -3.0);
+    assert_eq!(Direction::Maximize.key(f64::NAN), f64::INFINITY);
+    assert_eq!(Direction::default(), Direction::Minimize);
+}
+
+#[rstest]
+fn test_dominates() {
+    let minimize = [Direction::Minimize; 2];
+    let mixed = [Direction::Minimize, Direction::Maximize];
+
+    assert!(dominates(&[1.0, 1.0], &[1.0, 2.0], &minimize));
+    assert!(!dominates(&[1.0, 2.0], &[1.0, 1.0], &minimize));
+    assert!(!dominates(&[1.0, 2.0], &[2.0, 1.0], &minimize));
+    assert!(dominates(&[1.0, 2.0], &[2.0, 1.0], &mixed));
+    assert!(dominates(&[1.0, 2.0], &[f64::NAN, 2.0], &minimize));
+    assert!(!dominates(&[f64::NAN, 2.0], &[1.0, 2.0], &minimize));
+}
+
+#[rstest]
+fn test_non_dominated_sort(sample_objectives: Vec<[f64; 2]>) {
+    let objectives = sample_objectives.iter().map(|o| o.to_vec()).collect::<Vec<Vec<f64>>>();
+
+    assert_eq!(non_dominated_sort(&objectives, &[Direction::Minimize; 2]), vec![vec![0, 1, 3], vec![2], vec![4]]);
+    // Maximizing size, index 0 has both the lowest error and the largest size
+    assert_eq!(non_dominated_sort(&objectives, &[Direction::Minimize, Direction::Maximize]),
+        vec![vec![0], vec![1, 2, 4], vec![3]]);
+    assert!(non_dominated_sort(&[], &[Direction::Minimize]).is_empty());
+
+    // Duplicates do not dominate each other
+    let duplicates = vec![vec![1.0, 1.0], vec![1.0, 1.0], vec![2.0, 2.0]];
+    assert_eq!(non_dominated_sort(&duplicates, &[Direction::Minimize; 2]), vec![vec![0, 1], vec![2]]);
+}
+
+#[rstest]
+fn test_crowding_distance() {
+    let objectives = vec![vec![0.0, 4.0], vec![1.0, 3.0], vec![3.0, 1.0], vec![4.0, 0.0]];
+    let distances = crowding_distance(&objectives, &[0, 1, 2, 3], &[Direction::Minimize; 2]);
+
+    assert_eq!(distances[0], f64::INFINITY);
+    assert_eq!(distances[3], f64::INFINITY);
+    assert_eq!(distances[1], 3.0 / 4.0 + 3.0 / 4.0);
+    assert_eq!(distances[2], 3.0 / 4.0 + 3.0 / 4.0);
+    // Direction does not change distances
+    assert_eq!(crowding_distance(&objectives, &[0, 1, 2, 3], &[Direction::Maximize; 2]), distances);
+
+    assert_eq!(crowding_distance(&objectives, &[1, 2], &[Direction::Minimize; 2]), vec![f64::INFINITY; 2]);
+    let flat = vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]];
+    assert_eq!(crowding_distance(&flat, &[0, 1, 2], &[Direction::Minimize; 2])[1], 1.0);
+}
+
+#[rstest]
+fn test_rank_population(sample_objectives: Vec<[f64; 2]>) {
+    let mut population = individuals(&sample_objectives);
+    assert!(population.iter().all(|i| i.rank() == 0 && i.crowding_distance() == 0.0));
+
+    rank_population(&mut population, &[Direction::Minimize; 2]);
+
+    let ranks = population.iter().map(|i| i.rank()).collect::<Vec<usize>>();
+    assert_eq!(ranks, vec![0, 0, 1, 0, 2]);
+    assert_eq!(population.iter().map(|i| i.phenotype()).collect::<Vec<f64>>(), vec![0.0, 0.0, 1.0, 0.0, 2.0]);
+    assert!(population[1].crowding_distance().is_finite());
+    assert_eq!(population[0].crowding_distance(), f64::INFINITY);
+}
+
+#[rstest]
+fn test_select_survivors(sample_objectives: Vec<[f64; 2]>) {
+    let directions = [Direction::Minimize; 2];
+    let population = individuals(&sample_objectives);
+
+    let survivors = select_survivors(population.clone(), 4, &directions);
+    let objectives = survivors.iter().map(|i| i.objectives().to_vec()).collect::<Vec<Vec<f64>>>();
+    assert_eq!(objectives, vec![vec![0.1, 9.0], vec![0.5, 5.0], vec![2.0, 1.0], vec![0.6, 7.0]]);
+    assert_eq!(survivors.iter().map(|i| i.rank()).collect::<Vec<usize>>(), vec![0, 0, 0, 1]);
+
+    // Truncated front keeps its boundary points
+    let survivors = select_survivors(population.clone(), 2, &directions);
+    let objectives = survivors.iter().map(|i| i.objectives().to_vec()).collect::<Vec<Vec<f64>>>();
+    assert_eq!(objectives, vec![vec![0.1, 9.0], vec![2.0, 1.0]]);
+
+    assert_eq!(select_survivors(population, 10, &directions).len(), 5);
+}
+
+#[rstest]
+fn test_pareto_front(sample_objectives: Vec<[f64; 2]>) {
+    let population = individuals(&sample_objectives);
+
+    let front = pareto_front(&population, &[Direction::Minimize; 2]);
+    let objectives = front.iter().map(|i| i.objectives().to_vec()).collect::<Vec<Vec<f64>>>();
+    assert_eq!(objectives, vec![vec![0.1, 9.0], vec![0.5, 5.0], vec![2.0, 1.0]]);
+
+    // Sorted by the first objective in its direction
+    let front = pareto_front(&population, &[Direction::Maximize, Direction::Minimize]);
+    let objectives = front.iter().map(|i| i.objectives().to_vec()).collect::<Vec<Vec<f64>>>();
+    assert_eq!(objectives, vec![vec![2.0, 1.0]]);
+
+    assert!(pareto_front::<TreeGenotype>(&[], &[Direction::Minimize]).is_empty());
+}
+
+#[rstest]
+fn test_crowded_tournament(sample_objectives: Vec<[f64; 2]>) {
+    let mut population = individuals(&sample_objectives);
+    rank_population(&mut population, &[Direction::Minimize; 2]);
+    let mut rng = StdRng::seed_from_u64(42);
+
+    // Full tournament always selects a boundary point of the Pareto front, the first sampled wins ties
+    let selection = CrowdedTournament::new(population.len());
+    for _ in 0..20 {
+        let selected = selection.select(&mut rng, &population, Direction::Minimize);
+        let winner = population.iter().find(|i| i.genotype() == &selected).expect("Selected unknown genotype!");
+        assert_eq!((winner.rank(), winner.crowding_distance()), (0, f64::INFINITY));
+    }
+
+    // Worst individual never wins a binary tournament
+    let selection = CrowdedTournament::default();
+    assert_eq!(selection.tournament_size(), 2);
+    for _ in 0..50 {
+        assert_ne!(&selection.select(&mut rng, &population, Direction::Minimize), population[4].genotype());
+    }
+}
+
+#[rstest]
+#[should_panic]
+fn test_crowded_tournament_too_large(sample_objectives: Vec<[f64; 2]>) {
+    let population = individuals(&sample_objectives);
+    CrowdedTournament::new(6).select(&mut StdRng::seed_from_u64(0), &population, Direction::Minimize);
+}
+
+#[rstest]
+fn test_objectives() {
+    let operators = sample_operators();
+    let map = operators.create_map();
+    let dataset = Dataset::new(vec!["x".to_string()], "y".to_string(), vec![vec![1.0, 2.0]], vec![2.0, 3.0]);
+    let trees = ["x + 1", "x * x", "x"].iter()
+        .map(|infix| TreeGenotype::from_infix(infix, &operators).expect("Failed to parse tree!"))
+        .collect::<Vec<TreeGenotype>>();
+
+    let objectives = Objectives::new()
+        .add(MSE::new())
+        .add(Size::new())
+        .add_with_direction(Depth::new(), Direction::Maximize);
+
+    assert_eq!(objectives.directions(), vec![Direction::Minimize, Direction::Minimize, Direction::Maximize]);
+    assert_eq!(objectives.evaluate(&trees[0], &dataset, &map), vec![0.0, 3.0, 1.0]);
+    assert_eq!(objectives.evaluate_population(&trees, &dataset, &map),
+        vec![vec![0.0, 3.0, 1.0], vec![1.0, 3.0, 1.0], vec![1.0, 1.0, 0.0]]);
+    assert!(Objectives::<TreeGenotype, Dataset>::default().is_empty());
+}
+
+#[rstest]
+fn test_from_vecs() {
+    let genotypes = vec![TreeGenotype::default(); 2];
+    let population = MultiObjectiveIndividual::from_vecs(&genotypes, &[1.0, 2.0]);
+
+    assert_eq!(population[1].objectives(), &[2.0]);
+    assert_eq!(MultiObjectiveIndividual::to_genotype_vec(&population).len(), 2);
+    assert!(MultiObjectiveIndividual::from_genotype_vec(&genotypes)[0].objectives().is_empty());
+}
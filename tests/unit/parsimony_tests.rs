@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rstest::*;
+
+use mycoforge::common::objectives::Direction;
+use mycoforge::common::traits::{Evaluator, Individual, Selector};
+use mycoforge::common::types::VectorFunction;
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::set::Operators;
+use mycoforge::optimizers::ga::{BuilderError, EABuilder, EAComponents};
+use mycoforge::optimizers::steady_state::{SteadyStateEA, SteadyStateReplacement};
+use mycoforge::optimizers::termination::{MaxGenerations, TerminationCriterion};
+use mycoforge::tree::core::{individual::TreeIndividual, tree::TreeGenotype};
+use mycoforge::tree::fitness::evaluate::{EvaluatorError, MSE};
+use mycoforge::tree::fitness::parallel::ParallelEvaluator;
+use mycoforge::tree::fitness::parsimony::{covariant_coefficient, ParsimonyCoefficient, ParsimonyPressure, Tarpeian};
+use mycoforge::tree::operators::errors::SelectionError;
+use mycoforge::tree::operators::{
+    crossover::SubtreeCrossover, init::Grow, mutation::SubtreeMutation,
+    select::{DoubleTournament, LexicographicParsimonyTournament, TournamentSelection}
+};
+
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_registered("+", "add", 0.5).expect("Failed to add function!")
+        .add_registered("*", "mul", 0.5).expect("Failed to add function!")
+        .add_variable("x", 0.5).expect("Failed to add variable!")
+        .add_constant(1.0, 0.5).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+}
+
+/// Trees `x`, `x + 1`, `x + 1 + 1`, ... with 1, 3, 5, ... nodes.
+fn trees(count: usize) -> Vec<TreeGenotype> {
+    let operators = sample_operators();
+    return (0..count).map(|i| {
+        let infix = std::iter::once("x").chain(std::iter::repeat_n("1", i)).collect::<Vec<&str>>().join(" + ");
+        return TreeGenotype::from_infix(&infix, &operators).expect("Failed to parse tree!");
+    }).collect();
+}
+
+/// Individuals of growing size with given fitness values.
+fn individuals(fitness: &[f64]) -> Vec<TreeIndividual<TreeGenotype>> {
+    return TreeIndividual::from_vecs(&trees(fitness.len()), fitness);
+}
+
+/// Relative frequency of selecting each individual.
+fn selection_frequencies<S: Selector<TreeGenotype, I = TreeIndividual<TreeGenotype>>>(
+    selector: &S, population: &[TreeIndividual<TreeGenotype>], draws: usize, direction: Direction
+) -> Vec<f64> {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut counts = vec![0.0; population.len()];
+    for chosen in selector.select_many(&mut rng, population, draws, direction) {
+        let index = population.iter().position(|i| *i.genotype() == chosen).expect("Selected unknown genotype!");
+        counts[index] += 1.0;
+    }
+    return counts.iter().map(|count| count / draws as f64).collect();
+}
+
+fn assert_frequencies(found: &[f64], expected: &[f64]) {
+    for (f, e) in found.iter().zip(expected) {
+        assert!((f - e).abs() < 0.03, "Selection frequencies differ! Expected {:?}, found {:?}", expected, found);
+    }
+}
+
+/// Negated MSE, a score to maximize.
+struct NegatedMSE;
+
+impl Evaluator<TreeGenotype> for NegatedMSE {
+    type D = Dataset;
+
+    fn evaluate(&self, tree: &TreeGenotype, data: &Dataset, map: &HashMap<String, (usize, VectorFunction)>) -> f64 {
+        return -MSE::new().evaluate(tree, data, map);
+    }
+
+    fn direction(&self) -> Direction { return Direction::Maximize; }
+}
+
+#[fixture]
+fn sample_dataset() -> Dataset {
+    let xs: Vec<f64> = (0..=10).map(|i| i as f64 / 10.0).collect();
+    let ys = xs.iter().map(|&v| v.powi(2) + v).collect::<Vec<f64>>();
+    return Dataset::new(vec!["x".to_string()], "y".to_string(), vec![xs], ys);
+}
+
+#[rstest]
+fn test_lexicographic_parsimony_tournament() {
+    let population = individuals(&[2.0, 1.0, 1.0, 3.0]);
+    let full = LexicographicParsimonyTournament::new(4);
+
+    // Equal fitness is decided by size, better fitness wins regardless of size
+    assert_eq!(selection_frequencies(&full, &population, 100, Direction::Minimize), vec![0.0, 1.0, 0.0, 0.0]);
+    assert_eq!(selection_frequencies(&full, &population, 100, Direction::Maximize), vec![0.0, 0.0, 0.0, 1.0]);
+
+    let population = individuals(&[5.0, 5.0, 5.0]);
+    let pairs = selection_frequencies(&LexicographicParsimonyTournament::new(2), &population, 30000, Direction::Minimize);
+    assert_frequencies(&pairs, &[2.0 / 3.0, 1.0 / 3.0, 0.0]);
+}
+
+#[rstest]
+#[should_panic]
+fn test_lexicographic_parsimony_tournament_too_large() {
+    let population = individuals(&[1.0, 2.0]);
+    let _ = LexicographicParsimonyTournament::new(3).select(&mut StdRng::seed_from_u64(42), &population, Direction::Minimize);
+}
+
+#[rstest]
+#[case(1.0, 0.5)]
+#[case(1.4, 0.6)]
+#[case(2.0, 0.75)]
+fn test_double_tournament_pressure(#[case] pressure: f64, #[case] smaller: f64) {
+    // Fitness tournaments of size 1 draw uniformly, the smaller tree wins when qualifiers differ
+    // with probability pressure / 2
+    let population = individuals(&[2.0, 1.0]);
+    let selection = DoubleTournament::new(1, pressure).expect("Failed to create selection!");
+    let frequencies = selection_frequencies(&selection, &population, 30000, Direction::Minimize);
+
+    assert_frequencies(&frequencies, &[smaller, 1.0 - smaller]);
+}
+
+#[rstest]
+fn test_double_tournament_fitness_first() {
+    // Both qualifiers are the best individual when fitness tournaments cover the population
+    let population = individuals(&[3.0, 1.0, 2.0]);
+    let selection = DoubleTournament::new(3, 2.0).expect("Failed to create selection!");
+
+    assert_eq!(selection_frequencies(&selection, &population, 100, Direction::Minimize), vec![0.0, 1.0, 0.0]);
+    assert_eq!(selection_frequencies(&selection, &population, 100, Direction::Maximize), vec![1.0, 0.0, 0.0]);
+}
+
+#[rstest]
+#[case(0.9)]
+#[case(2.1)]
+#[case(f64::NAN)]
+fn test_double_tournament_invalid_pressure(#[case] pressure: f64) {
+    assert!(matches!(DoubleTournament::new(7, pressure), Err(SelectionError::InvalidPressure(_))));
+}
+
+#[rstest]
+#[case(vec![1.0, 2.0, 3.0], vec![5.0, 3.0, 1.0], -2.0)]
+#[case(vec![1.0, 3.0, 5.0, 7.0], vec![0.0, 1.0, 1.0, 2.0], 0.3)]
+#[case(vec![1.0, 3.0, 5.0], vec![1.0, f64::INFINITY, 2.0], 0.25)]
+#[case(vec![1.0], vec![4.0], 0.0)]
+#[case(vec![], vec![], 0.0)]
+fn test_covariant_coefficient(#[case] sizes: Vec<f64>, #[case] fitness: Vec<f64>, #[case] expected: f64) {
+    assert!((covariant_coefficient(&sizes, &fitness) - expected).abs() < 1e-12);
+}
+
+#[rstest]
+fn test_constant_parsimony(sample_dataset: Dataset) {
+    let map = sample_operators().create_map();
+    let trees = trees(3);
+    let raw = MSE::new().evaluate_population(&trees, &sample_dataset, &map);
+
+    let evaluator = ParsimonyPressure::new(MSE::new(), 0.1).expect("Failed to create evaluator!");
+    assert_eq!(evaluator.coefficient(), ParsimonyCoefficient::Constant(0.1));
+    let penalized = evaluator.evaluate_population(&trees, &sample_dataset, &map);
+    for ((penalized, raw), size) in penalized.iter().zip(&raw).zip([1.0, 3.0, 5.0]) {
+        assert!((penalized - raw - 0.1 * size).abs() < 1e-12);
+    }
+    assert_eq!(evaluator.evaluate(&trees[1], &sample_dataset, &map), penalized[1]);
+
+    // Penalty makes scores lower when maximizing
+    let evaluator = ParsimonyPressure::new(NegatedMSE, 0.1).expect("Failed to create evaluator!");
+    assert_eq!(evaluator.direction(), Direction::Maximize);
+    let penalized = evaluator.evaluate_population(&trees, &sample_dataset, &map);
+    for ((penalized, raw), size) in penalized.iter().zip(&raw).zip([1.0, 3.0, 5.0]) {
+        assert!((penalized + raw + 0.1 * size).abs() < 1e-12);
+    }
+}
+
+#[rstest]
+fn test_covariant_parsimony(sample_dataset: Dataset) {
+    let operators = sample_operators();
+    let map = operators.create_map();
+    let trees = ["x", "x * x", "x * x + x", "x * x + x + 1", "(x + 1) * (x + 1) * x"].iter()
+        .map(|infix| TreeGenotype::from_infix(infix, &operators).expect("Failed to parse tree!"))
+        .collect::<Vec<TreeGenotype>>();
+    let sizes = trees.iter().map(|tree| tree.arena().len() as f64).collect::<Vec<f64>>();
+
+    let evaluator = ParsimonyPressure::covariant(MSE::new());
+    let raw = MSE::new().evaluate_population(&trees, &sample_dataset, &map);
+    let coefficient = covariant_coefficient(&sizes, &raw);
+    let penalized = evaluator.evaluate_population(&trees, &sample_dataset, &map);
+
+    for ((penalized, raw), size) in penalized.iter().zip(&raw).zip(&sizes) {
+        assert!((penalized - (raw - coefficient * size)).abs() < 1e-12);
+    }
+    assert!(covariant_coefficient(&sizes, &penalized).abs() < 1e-12, "Penalized fitness should not covary with size!");
+    // Single genotype has no population to compute coefficient from
+    assert_eq!(evaluator.evaluate(&trees[2], &sample_dataset, &map), raw[2]);
+
+    let cases = evaluator.evaluate_population_cases(&trees, &sample_dataset, &map);
+    assert_eq!(cases.iter().map(|(fitness, _)| *fitness).collect::<Vec<f64>>(), penalized);
+    assert!(cases.iter().all(|(_, errors)| errors.len() == sample_dataset.targets().len()));
+}
+
+#[rstest]
+#[case(-0.1)]
+#[case(f64::INFINITY)]
+#[case(f64::NAN)]
+fn test_invalid_parsimony_coefficient(#[case] coefficient: f64) {
+    assert!(matches!(ParsimonyPressure::new(MSE::new(), coefficient), Err(EvaluatorError::InvalidParsimonyCoefficient(_))));
+}
+
+#[rstest]
+fn test_tarpeian(sample_dataset: Dataset) {
+    let map = sample_operators().create_map();
+    let trees = trees(5);
+    let raw = MSE::new().evaluate_population(&trees, &sample_dataset, &map);
+
+    let disabled = Tarpeian::new(MSE::new(), 0.0, 42).expect("Failed to create evaluator!");
+    assert_eq!(disabled.evaluate_population(&trees, &sample_dataset, &map), raw);
+
+    // Trees with 7 and 9 nodes are above the average of 5 nodes
+    let always = Tarpeian::new(MSE::new(), 1.0, 42).expect("Failed to create evaluator!");
+    let fitness = always.evaluate_population(&trees, &sample_dataset, &map);
+    assert_eq!(fitness[..3], raw[..3]);
+    assert_eq!(fitness[3..], [f64::INFINITY, f64::INFINITY]);
+
+    let maximizing = Tarpeian::new(NegatedMSE, 1.0, 42).expect("Failed to create evaluator!");
+    let fitness = maximizing.evaluate_population(&trees, &sample_dataset, &map);
+    assert_eq!(fitness[3..], [f64::NEG_INFINITY, f64::NEG_INFINITY]);
+
+    let cases = always.evaluate_population_cases(&trees, &sample_dataset, &map);
+    let rows = sample_dataset.targets().len();
+    assert!(cases.iter().all(|(_, errors)| errors.len() == rows));
+    assert_eq!(cases[4], (f64::INFINITY, vec![f64::INFINITY; rows]));
+}
+
+#[rstest]
+fn test_tarpeian_rate(sample_dataset: Dataset) {
+    let map = sample_operators().create_map();
+    let trees = trees(2).into_iter().cycle().take(2000).collect::<Vec<TreeGenotype>>();
+
+    let evaluator = Tarpeian::new(MSE::new(), 0.3, 42).expect("Failed to create evaluator!");
+    let fitness = evaluator.evaluate_population(&trees, &sample_dataset, &map);
+    let discarded = fitness.iter().filter(|f| f.is_infinite()).count() as f64 / 1000.0;
+
+    assert!(fitness.iter().step_by(2).all(|f| f.is_finite()), "Smaller trees should never be discarded!");
+    assert!((discarded - 0.3).abs() < 0.05, "Expected about 30% of larger trees discarded, found {}", discarded);
+}
+
+#[rstest]
+#[case(-0.1)]
+#[case(1.1)]
+fn test_invalid_tarpeian_rate(#[case] rate: f64) {
+    assert!(matches!(Tarpeian::new(MSE::new(), rate, 42), Err(EvaluatorError::InvalidTarpeianRate(_))));
+}
+
+struct Components;
+impl EAComponents<TreeGenotype> for Components {
+    type I = TreeIndividual<TreeGenotype>;
+    type Init = Grow;
+    type Mut = SubtreeMutation;
+    type Cross = SubtreeCrossover;
+    type Eval = ParsimonyPressure<MSE>;
+    type Sel = TournamentSelection;
+}
+
+struct DoubleTournamentComponents;
+impl EAComponents<TreeGenotype> for DoubleTournamentComponents {
+    type I = TreeIndividual<TreeGenotype>;
+    type Init = Grow;
+    type Mut = SubtreeMutation;
+    type Cross = SubtreeCrossover;
+    type Eval = Tarpeian<MSE>;
+    type Sel = DoubleTournament;
+}
+
+/// Average number of nodes of trees in population.
+fn average_size(population: &[TreeIndividual<TreeGenotype>]) -> f64 {
+    return population.iter().map(|i| i.genotype().arena().len() as f64).sum::<f64>() / population.len() as f64;
+}
+
+#[rstest]
+fn test_ea_with_parsimony(sample_dataset: Dataset) {
+    let operators = sample_operators();
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![Box::new(MaxGenerations::new(10))];
+    let run = |evaluator: ParsimonyPressure<MSE>| {
+        let ea = EABuilder::<Components, TreeGenotype>::new()
+            .set_initializer(Grow::new(2, 4))
+            .set_mutator(SubtreeMutation::new(0.2, (1, 3)).expect("Failed to create mutation scheme!"))
+            .set_crossoverer(SubtreeCrossover::new(0.9).expect("Failed to create crossover scheme!"))
+            .set_evaluator(evaluator)
+            .set_selector(TournamentSelection::new(3))
+            .set_sampler(operators.sampler())
+            .set_map(operators.create_map())
+            .build().expect("Failed to build EA!");
+        return ea.run(&mut StdRng::seed_from_u64(42), 50, &sample_dataset, &criteria);
+    };
+
+    let unpenalized = run(ParsimonyPressure::new(MSE::new(), 0.0).expect("Failed to create evaluator!"));
+    let penalized = run(ParsimonyPressure::new(MSE::new(), 1.0).expect("Failed to create evaluator!"));
+    assert!(average_size(penalized.population()) < average_size(unpenalized.population()),
+        "Parsimony pressure should shrink trees! Found {} and {}",
+        average_size(penalized.population()), average_size(unpenalized.population())
+    );
+
+    let covariant = run(ParsimonyPressure::covariant(MSE::new()));
+    assert!(covariant.best().phenotype().is_finite());
+}
+
+#[rstest]
+fn test_ea_with_double_tournament(sample_dataset: Dataset) {
+    let operators = sample_operators();
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![Box::new(MaxGenerations::new(5))];
+    let ea = EABuilder::<DoubleTournamentComponents, TreeGenotype>::new()
+        .set_initializer(Grow::new(2, 4))
+        .set_mutator(SubtreeMutation::new(0.2, (1, 3)).expect("Failed to create mutation scheme!"))
+        .set_crossoverer(SubtreeCrossover::new(0.9).expect("Failed to create crossover scheme!"))
+        .set_evaluator(Tarpeian::new(MSE::new(), 0.3, 42).expect("Failed to create evaluator!"))
+        .set_selector(DoubleTournament::new(3, 1.4).expect("Failed to create selection!"))
+        .set_sampler(operators.sampler())
+        .set_map(operators.create_map())
+        .build().expect("Failed to build EA!");
+    let result = ea.run(&mut StdRng::seed_from_u64(42), 30, &sample_dataset, &criteria);
+
+    assert_eq!(result.generations(), 5);
+    assert!(result.best().phenotype().is_finite());
+}
+
+#[rstest]
+fn test_population_dependent() {
+    let constant = ParsimonyPressure::new(MSE::new(), 0.1).expect("Failed to create evaluator!");
+    let tarpeian = Tarpeian::new(MSE::new(), 0.5, 42).expect("Failed to create evaluator!");
+
+    assert!(!MSE::new().population_dependent());
+    assert!(!constant.population_dependent());
+    assert!(ParsimonyPressure::covariant(MSE::new()).population_dependent());
+    assert!(tarpeian.population_dependent());
+    assert!(ParsimonyPressure::new(tarpeian, 0.1).expect("Failed to create evaluator!").population_dependent());
+    assert!(ParallelEvaluator::from(ParsimonyPressure::covariant(MSE::new())).population_dependent());
+}
+
+fn steady_state_builder(evaluator: ParsimonyPressure<MSE>) -> EABuilder<Components, TreeGenotype> {
+    let operators = sample_operators();
+    return EABuilder::<Components, TreeGenotype>::new()
+        .set_initializer(Grow::new(2, 4))
+        .set_mutator(SubtreeMutation::new(0.2, (1, 3)).expect("Failed to create mutation scheme!"))
+        .set_crossoverer(SubtreeCrossover::new(0.9).expect("Failed to create crossover scheme!"))
+        .set_evaluator(evaluator)
+        .set_selector(TournamentSelection::new(3))
+        .set_sampler(operators.sampler())
+        .set_map(operators.create_map());
+}
+
+#[rstest]
+fn test_steady_state_with_parsimony(sample_dataset: Dataset) {
+    // Children are evaluated one at a time, so only constant coefficient has an effect
+    let covariant = steady_state_builder(ParsimonyPressure::covariant(MSE::new()))
+        .build_steady_state(SteadyStateReplacement::Worst, 2);
+    assert!(matches!(covariant, Err(BuilderError::PopulationDependentEvaluator)));
+
+    let criteria: Vec<Box<dyn TerminationCriterion>> = vec![Box::new(MaxGenerations::new(5))];
+    let constant = steady_state_builder(ParsimonyPressure::new(MSE::new(), 0.1).expect("Failed to create evaluator!"))
+        .build_steady_state(SteadyStateReplacement::Worst, 2).expect("Failed to build steady-state EA!");
+    let result = constant.run(&mut StdRng::seed_from_u64(42), 20, &sample_dataset, &criteria);
+
+    let map = sample_operators().create_map();
+    for individual in result.population() {
+        let raw = MSE::new().evaluate(individual.genotype(), &sample_dataset, &map);
+        let expected = raw + 0.1 * individual.genotype().arena().len() as f64;
+        assert!((individual.phenotype() - expected).abs() < 1e-12 || (raw.is_nan() && individual.phenotype().is_nan()));
+    }
+}
+
+#[rstest]
+#[should_panic]
+fn test_steady_state_rejects_tarpeian() {
+    let operators = sample_operators();
+    let _ = SteadyStateEA::<DoubleTournamentComponents, TreeGenotype>::new(
+        Grow::new(2, 4),
+        SubtreeMutation::new(0.2, (1, 3)).expect("Failed to create mutation scheme!"),
+        SubtreeCrossover::new(0.9).expect("Failed to create crossover scheme!"),
+        Tarpeian::new(MSE::new(), 0.3, 42).expect("Failed to create evaluator!"),
+        DoubleTournament::new(3, 1.4).expect("Failed to create selection!"),
+        operators.sampler(), operators.create_map(), SteadyStateReplacement::Worst, 1
+    );
+}
@@ -0,0 +1,110 @@
+use rstest::*;
+
+use mycoforge::common::traits::Evaluator;
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::functions::koza;
+use mycoforge::operators::functions::symbolic::add;
+use mycoforge::operators::registry::{lookup, register, registered, RegistryError};
+use mycoforge::operators::set::{NodeType, Operators};
+use mycoforge::tree::core::tree::TreeGenotype;
+use mycoforge::tree::fitness::evaluate::MSE;
+
+fn square(args: &[&[f64]]) -> Vec<f64> { return args[0].iter().map(|a| a * a).collect(); }
+fn cube(args: &[&[f64]]) -> Vec<f64> { return args[0].iter().map(|a| a * a * a).collect(); }
+
+#[fixture]
+fn sample_operators() -> Operators {
+    register("tests::square", square, 1).expect("Failed to register function!");
+    return OperatorsBuilder::default()
+        .add_registered("+", "add", 0.25).expect("Failed to add function!")
+        .add_registered("*", "mul", 0.25).expect("Failed to add function!")
+        .add_registered("/", "protected_div", 0.25).expect("Failed to add function!")
+        .add_registered("sq", "tests::square", 0.25).expect("Failed to add function!")
+        .add_variable("x", 0.4).expect("Failed to add variable!")
+        .add_variable("y", 0.4).expect("Failed to add variable!")
+        .add_constant(-0.1, 0.2).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+}
+
+#[rstest]
+fn test_builtins_registered() {
+    let keys = registered();
+
+    for key in ["add", "sub", "mul", "protected_div", "sin", "cos", "protected_ln"] {
+        assert!(keys.contains(&key.to_string()), "Missing builtin {}", key);
+    }
+    assert_eq!(lookup("protected_div").map(|(_, arity)| arity), Some(2));
+    assert_eq!(lookup("sin").map(|(_, arity)| arity), Some(1));
+    assert!(lookup("missing").is_none());
+}
+
+#[rstest]
+fn test_register() {
+    assert_eq!(register("tests::cube", cube, 1), Ok(()));
+    assert_eq!(register("tests::cube", cube, 1), Ok(()));
+
+    assert_eq!(register("tests::cube", square, 1), Err(RegistryError::KeyExists("tests::cube".to_string())));
+    assert_eq!(register("tests::cube", cube, 2), Err(RegistryError::KeyExists("tests::cube".to_string())));
+    assert_eq!(register("add", cube, 1), Err(RegistryError::KeyExists("add".to_string())));
+}
+
+#[rstest]
+fn test_json_round_trip(sample_operators: Operators) {
+    let json = serde_json::to_string(&sample_operators).expect("Failed to serialize operators!");
+    let loaded: Operators = serde_json::from_str(&json).expect("Failed to deserialize operators!");
+
+    assert!(json.contains("\"function\":\"protected_div\""));
+    assert_eq!(serde_json::to_string(&loaded).expect("Failed to serialize operators!"), json);
+    for (name, functor) in sample_operators.functions() {
+        let found = loaded.functions().get(name).expect("Function is missing after load!");
+        assert_eq!((found.arity(), found.weight(), found.key()), (functor.arity(), functor.weight(), functor.key()));
+    }
+    assert_eq!(format!("{:?}", loaded.terminals()), format!("{:?}", sample_operators.terminals()));
+    assert_eq!(loaded.terminal_sampler().weights(), sample_operators.terminal_sampler().weights());
+
+    let dataset = Dataset::new(vec!["x".to_string(), "y".to_string()], "z".to_string(),
+        vec![vec![0.0, 1.0, 2.0], vec![3.0, 0.0, -1.0]], vec![1.0, 2.0, 3.0]);
+    let tree = TreeGenotype::from_infix("sq(x) / y + -0.1 * x", &sample_operators).expect("Failed to parse tree!");
+    let evaluator = MSE::new();
+    assert_eq!(evaluator.evaluate(&tree, &dataset, &loaded.create_map()), evaluator.evaluate(&tree, &dataset, &sample_operators.create_map()));
+}
+
+#[rstest]
+fn test_koza_serializable() {
+    let operators = koza(7).expect("Failed to create koza set!")
+        .add_registered("ln", "protected_ln", 1.0 / 7.0).expect("Failed to add function!")
+        .add_variable("x", 1.0).expect("Failed to add variable!")
+        .build().expect("Failed to build operators!");
+
+    let json = serde_json::to_string(&operators).expect("Failed to serialize operators!");
+    let loaded: Operators = serde_json::from_str(&json).expect("Failed to deserialize operators!");
+
+    assert_eq!(loaded.functions().len(), 7);
+}
+
+#[rstest]
+fn test_serialization_errors() {
+    let raw = OperatorsBuilder::default()
+        .add_function("+", add, 2, 1.0).expect("Failed to add function!")
+        .add_variable("x", 1.0).expect("Failed to add variable!")
+        .build().expect("Failed to build operators!");
+    let ephemeral = OperatorsBuilder::default()
+        .add_registered("+", "add", 1.0).expect("Failed to add function!")
+        .add_ephemeral(Box::new(|| 0.5), 1.0).expect("Failed to add ephemeral!")
+        .build().expect("Failed to build operators!");
+
+    assert!(serde_json::to_string(&raw).is_err());
+    assert!(serde_json::to_string(&ephemeral).is_err());
+    assert!(matches!(ephemeral.terminals()[0], NodeType::EphemeralGenerator(_)));
+    assert!(OperatorsBuilder::default().add_registered("+", "missing", 1.0).is_err());
+}
+
+#[rstest]
+#[case(r#"{"functions":[{"name":"f","function":"missing","arity":2,"weight":1.0}],"terminals":[{"type":"variable","name":"x","weight":1.0}]}"#)]
+#[case(r#"{"functions":[{"name":"f","function":"add","arity":1,"weight":1.0}],"terminals":[{"type":"variable","name":"x","weight":1.0}]}"#)]
+#[case(r#"{"functions":[{"name":"f","function":"add","arity":2,"weight":0.5}],"terminals":[{"type":"variable","name":"x","weight":1.0}]}"#)]
+#[case(r#"{"functions":[{"name":"f","function":"add","arity":2,"weight":1.0}],"terminals":[]}"#)]
+fn test_deserialization_errors(#[case] json: &str) {
+    assert!(serde_json::from_str::<Operators>(json).is_err());
+}
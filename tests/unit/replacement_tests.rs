@@ -0,0 +1,55 @@
+use rstest::{fixture, rstest};
+
+use mycoforge::common::objectives::Direction;
+use mycoforge::common::traits::Individual;
+use mycoforge::optimizers::replacement::Replacement;
+use mycoforge::tree::core::individual::TreeIndividual;
+use mycoforge::tree::core::tree::TreeGenotype;
+
+fn individuals(fitness: &[f64]) -> Vec<TreeIndividual<TreeGenotype>> {
+    return TreeIndividual::from_vecs(&vec![TreeGenotype::default(); fitness.len()], fitness);
+}
+
+fn fitness(individuals: &[TreeIndividual<TreeGenotype>]) -> Vec<f64> {
+    return individuals.iter().map(|i| i.phenotype()).collect();
+}
+
+#[fixture]
+fn sample_parents() -> Vec<TreeIndividual<TreeGenotype>> { return individuals(&[3.0, 1.0, 4.0, 2.0]); }
+
+#[fixture]
+fn sample_offspring() -> Vec<TreeIndividual<TreeGenotype>> { return individuals(&[5.0, 0.5, 6.0, 7.0]); }
+
+#[rstest]
+#[case(Replacement::Generational, vec![5.0, 0.5, 6.0, 7.0])]
+#[case(Replacement::Elitism(0), vec![5.0, 0.5, 6.0, 7.0])]
+#[case(Replacement::Elitism(2), vec![1.0, 2.0, 5.0, 0.5])]
+#[case(Replacement::Elitism(10), vec![1.0, 2.0, 3.0, 4.0])]
+#[case(Replacement::MuPlusLambda, vec![0.5, 1.0, 2.0, 3.0])]
+#[case(Replacement::MuCommaLambda, vec![0.5, 5.0, 6.0, 7.0])]
+#[case(Replacement::ReplaceWorst, vec![5.0, 0.5, 6.0, 7.0])]
+fn test_replacement(
+    #[case] replacement: Replacement, #[case] expected: Vec<f64>,
+    sample_parents: Vec<TreeIndividual<TreeGenotype>>, sample_offspring: Vec<TreeIndividual<TreeGenotype>>
+) {
+    let next = replacement.replace(&sample_parents, &sample_offspring, Direction::Minimize);
+
+    assert_eq!(fitness(&next), expected,
+        "{:?}: wrong next population! Expected {:?}, found {:?}", replacement, expected, fitness(&next)
+    );
+}
+
+#[rstest]
+fn test_replace_worst_partial(sample_parents: Vec<TreeIndividual<TreeGenotype>>) {
+    let offspring = individuals(&[10.0]);
+    let next = Replacement::ReplaceWorst.replace(&sample_parents, &offspring, Direction::Minimize);
+
+    assert_eq!(fitness(&next), vec![1.0, 2.0, 3.0, 10.0]);
+}
+
+#[rstest]
+#[should_panic]
+fn test_mu_comma_lambda_too_few_offspring(sample_parents: Vec<TreeIndividual<TreeGenotype>>) {
+    let offspring = individuals(&[10.0]);
+    let _ = Replacement::MuCommaLambda.replace(&sample_parents, &offspring, Direction::Minimize);
+}
@@ -4,13 +4,18 @@ use rstest::{fixture, rstest};
 use rand::rngs::StdRng;
 use rand::{thread_rng, SeedableRng};
 
-use mycoforge::common::traits::{Initializer, Selector};
+use mycoforge::common::objectives::Direction;
+use mycoforge::common::traits::{Individual, Initializer, Selector};
 
+use mycoforge::operators::builder::OperatorsBuilder;
 use mycoforge::operators::sampler::OperatorSampler;
 use mycoforge::tree::core::individual::TreeIndividual;
 
 use mycoforge::tree::operators::init::Grow;
-use mycoforge::tree::operators::select::TournamentSelection;
+use mycoforge::tree::operators::select::{
+    ExponentialRankSelection, LinearRankSelection, RouletteSelection, StochasticUniversalSampling,
+    TournamentSelection, TruncationSelection
+};
 
 #[fixture]
 fn sample_sampler() -> OperatorSampler {
@@ -39,7 +44,7 @@ fn test_tournament_too_large(sample_population: Vec<TreeIndividual<TreeGenotype>
     let mut rng = thread_rng();
 
     let selection = TournamentSelection::new(11);
-    let _ = selection.select(&mut rng, &sample_population);
+    let _ = selection.select(&mut rng, &sample_population, Direction::Minimize);
 }
 
 #[rstest]
@@ -50,6 +55,163 @@ fn test_tournament_selection(#[case] size: usize, sample_population: Vec<TreeInd
     let mut rng = StdRng::seed_from_u64(42);
     
     let selection = TournamentSelection::new(size);
-    let chosen = selection.select(&mut rng, &sample_population);
+    let chosen = selection.select(&mut rng, &sample_population, Direction::Minimize);
     println!("{}", chosen);
 }
+
+/// Individuals with distinct genotypes (`x`, `x + 1`, `x + 1 + 1`, ...) and given fitness.
+fn ranked_population(fitness: &[f64]) -> Vec<TreeIndividual<TreeGenotype>> {
+    let operators = OperatorsBuilder::default()
+        .add_registered("+", "add", 1.0).expect("Failed to add function!")
+        .add_variable("x", 0.5).expect("Failed to add variable!")
+        .add_constant(1.0, 0.5).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+    let genotypes = (0..fitness.len()).map(|i| {
+        let infix = std::iter::once("x").chain(std::iter::repeat_n("1", i)).collect::<Vec<&str>>().join(" + ");
+        return TreeGenotype::from_infix(&infix, &operators).expect("Failed to parse tree!");
+    }).collect::<Vec<TreeGenotype>>();
+    return TreeIndividual::from_vecs(&genotypes, fitness);
+}
+
+/// Fraction of draws selecting each individual.
+fn selection_frequencies<S: Selector<TreeGenotype, I = TreeIndividual<TreeGenotype>>>(
+    selector: &S, population: &[TreeIndividual<TreeGenotype>], draws: usize
+) -> Vec<f64> {
+    return directed_frequencies(selector, population, draws, Direction::Minimize);
+}
+
+fn directed_frequencies<S: Selector<TreeGenotype, I = TreeIndividual<TreeGenotype>>>(
+    selector: &S, population: &[TreeIndividual<TreeGenotype>], draws: usize, direction: Direction
+) -> Vec<f64> {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut counts = vec![0.0; population.len()];
+    for chosen in selector.select_many(&mut rng, population, draws, direction) {
+        let index = population.iter().position(|i| *i.genotype() == chosen).expect("Selected unknown genotype!");
+        counts[index] += 1.0;
+    }
+    return counts.iter().map(|count| count / draws as f64).collect();
+}
+
+fn assert_frequencies(found: &[f64], expected: &[f64]) {
+    for (f, e) in found.iter().zip(expected) {
+        assert!((f - e).abs() < 0.03, "Expected frequencies {:?}, found {:?}", expected, found);
+    }
+}
+
+#[rstest]
+fn test_roulette_favours_low_error() {
+    // Weights 1 / (1 + e - 1): 1, 1/2, 1/4 and 0 for NaN
+    let population = ranked_population(&[2.0, 1.0, 4.0, f64::NAN]);
+    let frequencies = selection_frequencies(&RouletteSelection::new(), &population, 20000);
+
+    assert_frequencies(&frequencies, &[2.0 / 7.0, 4.0 / 7.0, 1.0 / 7.0, 0.0]);
+}
+
+#[rstest]
+fn test_roulette_shift_invariant() {
+    let shifted = ranked_population(&[-8.0, -9.0, -6.0]);
+    let frequencies = selection_frequencies(&RouletteSelection::new(), &shifted, 20000);
+
+    assert_frequencies(&frequencies, &[2.0 / 7.0, 4.0 / 7.0, 1.0 / 7.0]);
+}
+
+#[rstest]
+fn test_roulette_invalid_population_is_uniform() {
+    let population = ranked_population(&[f64::NAN, f64::INFINITY]);
+    let frequencies = selection_frequencies(&RouletteSelection::new(), &population, 20000);
+
+    assert_frequencies(&frequencies, &[0.5, 0.5]);
+}
+
+#[rstest]
+fn test_roulette_best_infinity() {
+    let population = ranked_population(&[1.0, f64::NEG_INFINITY, f64::INFINITY, f64::NAN]);
+
+    let frequencies = directed_frequencies(&RouletteSelection::new(), &population, 1000, Direction::Minimize);
+    assert_eq!(frequencies, vec![0.0, 1.0, 0.0, 0.0]);
+    let frequencies = directed_frequencies(&RouletteSelection::new(), &population, 1000, Direction::Maximize);
+    assert_eq!(frequencies, vec![0.0, 0.0, 1.0, 0.0]);
+}
+
+#[rstest]
+#[case(4, vec![1, 2, 1])]
+#[case(8, vec![2, 4, 2])]
+fn test_sus_exact_counts(#[case] count: usize, #[case] expected: Vec<usize>) {
+    // Weights 1/2, 1 and 1/2: every sample contains exactly the expected number of copies
+    let population = ranked_population(&[1.0, 0.0, 1.0]);
+    let mut rng = StdRng::seed_from_u64(7);
+
+    for _ in 0..20 {
+        let selected = StochasticUniversalSampling::new().select_many(&mut rng, &population, count, Direction::Minimize);
+        let counts = population.iter()
+            .map(|individual| selected.iter().filter(|&g| g == individual.genotype()).count())
+            .collect::<Vec<usize>>();
+        assert_eq!(counts, expected);
+    }
+}
+
+#[rstest]
+fn test_linear_rank_selection() {
+    // Ranks by error: individual 1, 2, 0; probabilities (2 - 2 * i / 2) / 3
+    let population = ranked_population(&[5.0, -1.0, 3.0]);
+    let selection = LinearRankSelection::new(2.0).expect("Failed to create selection!");
+    assert_frequencies(&selection_frequencies(&selection, &population, 20000), &[0.0, 2.0 / 3.0, 1.0 / 3.0]);
+
+    let uniform = LinearRankSelection::new(1.0).expect("Failed to create selection!");
+    assert_frequencies(&selection_frequencies(&uniform, &population, 20000), &[1.0 / 3.0; 3]);
+}
+
+#[rstest]
+fn test_exponential_rank_selection() {
+    // Ranks by error: individual 2, 0, 1 with weights 1, 1/2, 1/4
+    let population = ranked_population(&[1.0, f64::NAN, 0.5]);
+    let selection = ExponentialRankSelection::new(0.5).expect("Failed to create selection!");
+
+    assert_frequencies(&selection_frequencies(&selection, &population, 20000), &[2.0 / 7.0, 1.0 / 7.0, 4.0 / 7.0]);
+}
+
+#[rstest]
+#[case(0.5, vec![0.0, 0.5, 0.0, 0.5])]
+#[case(0.25, vec![0.0, 0.0, 0.0, 1.0])]
+#[case(1.0, vec![0.25; 4])]
+fn test_truncation_selection(#[case] proportion: f64, #[case] expected: Vec<f64>) {
+    let population = ranked_population(&[3.0, 1.0, 2.0, 0.0]);
+    let selection = TruncationSelection::new(proportion).expect("Failed to create selection!");
+
+    assert_frequencies(&selection_frequencies(&selection, &population, 20000), &expected);
+}
+
+#[rstest]
+fn test_selection_parameters() {
+    assert!(LinearRankSelection::new(0.9).is_err());
+    assert!(LinearRankSelection::new(f64::NAN).is_err());
+    assert!(ExponentialRankSelection::new(0.0).is_err());
+    assert!(TruncationSelection::new(1.5).is_err());
+    assert_eq!(LinearRankSelection::default().pressure(), 1.5);
+    assert_eq!(ExponentialRankSelection::default().base(), 0.9);
+    assert_eq!(TruncationSelection::default().proportion(), 0.5);
+}
+
+#[rstest]
+fn test_tournament_direction() {
+    let population = ranked_population(&[2.0, f64::NAN, 5.0, -1.0]);
+    let full = TournamentSelection::new(4);
+
+    assert_eq!(directed_frequencies(&full, &population, 100, Direction::Minimize), vec![0.0, 0.0, 0.0, 1.0]);
+    assert_eq!(directed_frequencies(&full, &population, 100, Direction::Maximize), vec![0.0, 0.0, 1.0, 0.0]);
+}
+
+#[rstest]
+fn test_selection_maximization() {
+    // Scores 4, 2, 1: weights 1 / (1 + distance from best score) are 1, 1/3 and 1/4
+    let population = ranked_population(&[4.0, 2.0, 1.0]);
+    let frequencies = directed_frequencies(&RouletteSelection::new(), &population, 20000, Direction::Maximize);
+    assert_frequencies(&frequencies, &[12.0 / 19.0, 4.0 / 19.0, 3.0 / 19.0]);
+
+    let linear = LinearRankSelection::new(2.0).expect("Failed to create selection!");
+    let frequencies = directed_frequencies(&linear, &population, 20000, Direction::Maximize);
+    assert_frequencies(&frequencies, &[2.0 / 3.0, 1.0 / 3.0, 0.0]);
+
+    let truncation = TruncationSelection::new(0.3).expect("Failed to create selection!");
+    assert_eq!(directed_frequencies(&truncation, &population, 100, Direction::Maximize), vec![1.0, 0.0, 0.0]);
+}
@@ -0,0 +1,117 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use rstest::*;
+
+use mycoforge::common::traits::{Evaluator, Initializer, Mutator};
+use mycoforge::dataset::core::Dataset;
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::functions::symbolic::{add, cos, div, ln, mul, sin, sub};
+use mycoforge::operators::set::Operators;
+use mycoforge::tree::core::tree::TreeGenotype;
+use mycoforge::tree::fitness::evaluate::MSE;
+use mycoforge::tree::operators::init::Grow;
+use mycoforge::tree::operators::mutation::SubtreeMutation;
+use mycoforge::tree::operators::simplify::{Simplifier, SimplifyingMutation};
+
+#[fixture]
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_function("+", add, 2, 1.0 / 7.0).expect("Failed to add function!")
+        .add_function("-", sub, 2, 1.0 / 7.0).expect("Failed to add function!")
+        .add_function("*", mul, 2, 1.0 / 7.0).expect("Failed to add function!")
+        .add_function("/", div, 2, 1.0 / 7.0).expect("Failed to add function!")
+        .add_function("sin", sin, 1, 1.0 / 7.0).expect("Failed to add function!")
+        .add_function("cos", cos, 1, 1.0 / 7.0).expect("Failed to add function!")
+        .add_function("ln", ln, 1, 1.0 / 7.0).expect("Failed to add function!")
+        .add_variable("x", 0.4).expect("Failed to add variable!")
+        .add_variable("y", 0.4).expect("Failed to add variable!")
+        .add_constant(1.0, 0.2).expect("Failed to add constant!")
+        .build().expect("Failed to build operators!");
+}
+
+#[fixture]
+fn sample_dataset() -> Dataset {
+    let xs = vec![-2.0, -0.5, 0.0, 0.25, 1.0, 3.0];
+    let ys = vec![1.0, 0.0, -1.5, 2.0, 0.5, -3.0];
+    let targets = xs.iter().zip(ys.iter()).map(|(x, y)| x * y + 1.0).collect();
+    return Dataset::new(vec!["x".to_string(), "y".to_string()], "z".to_string(), vec![xs, ys], targets);
+}
+
+#[rstest]
+#[case("2 * 3 + 1", "7")]
+#[case("sin(0)", "0")]
+#[case("ln(0)", &format!("{}", f64::MIN))]
+#[case("1 / 0", "1")]
+#[case("x / 0", "1")]
+#[case("x / 0.0000001", "1")]
+#[case("0 / x", "0 / x0")]
+#[case("x / x", "1")]
+#[case("x / 1", "x0")]
+#[case("x * 1", "x0")]
+#[case("1 * x", "x0")]
+#[case("x + 0", "x0")]
+#[case("x - 0", "x0")]
+#[case("0 - x", "0 - x0")]
+#[case("sin(x) - sin(x)", "0")]
+#[case("y * (x - x)", "0")]
+#[case("(2 + y) + (x + 3)", "x0 + x1 + 5")]
+#[case("2 * (y * 4) * x", "x0 * x1 * 8")]
+#[case("y + x", "x0 + x1")]
+#[case("x * sin(y)", "sin(x1) * x0")]
+#[case("(x + 1) - 1", "x0 + 1 - 1")]
+fn test_simplify(#[case] expression: &str, #[case] expected: &str, sample_operators: Operators) {
+    let simplifier = Simplifier::new(sample_operators.create_map());
+    let tree = TreeGenotype::from_infix(expression, &sample_operators).expect("Failed to parse tree!");
+
+    let simplified = simplifier.simplify(&tree);
+
+    assert_eq!(simplified.to_infix(), expected);
+    assert_eq!(simplified.validate(&sample_operators), Ok(()));
+}
+
+#[rstest]
+fn test_simplify_preserves_semantics(sample_operators: Operators, sample_dataset: Dataset) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let map = sample_operators.create_map();
+    let simplifier = Simplifier::new(map.clone());
+    let sampler = sample_operators.sampler();
+    let evaluator = MSE::new();
+
+    for _ in 0..200 {
+        let tree: TreeGenotype = Grow::new(1, 5).initialize(&mut rng, &sampler);
+        let simplified = simplifier.simplify(&tree);
+
+        let (expected, found) = (evaluator.evaluate(&tree, &sample_dataset, &map), evaluator.evaluate(&simplified, &sample_dataset, &map));
+        // Algebraic rules assume finite values
+        if !expected.is_finite() { continue; }
+
+        assert!(simplified.arena().len() <= tree.arena().len(),
+            "Simplified tree is larger! Original {}, found {}", tree.to_infix(), simplified.to_infix()
+        );
+        assert!((expected - found).abs() <= 1e-9 * expected.abs().max(1.0),
+            "Fitness changed for {} -> {}! Expected {}, found {}", tree.to_infix(), simplified.to_infix(), expected, found
+        );
+        assert_eq!(simplifier.simplify(&simplified), simplified,
+            "Simplification is not idempotent for {}", simplified.to_infix()
+        );
+    }
+}
+
+#[rstest]
+fn test_simplifying_mutation(sample_operators: Operators) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let sampler = sample_operators.sampler();
+    let mutator = SimplifyingMutation::new(
+        SubtreeMutation::new(1.0, (1, 3)).expect("Failed to create mutation scheme!"),
+        Simplifier::new(sample_operators.create_map())
+    );
+
+    for _ in 0..50 {
+        let tree: TreeGenotype = Grow::new(2, 4).initialize(&mut rng, &sampler);
+        let mutant = mutator.variate(&mut rng, &tree, &sampler);
+
+        assert_eq!(mutator.simplifier().simplify(&mutant), mutant);
+        assert_eq!(mutant.validate(&sample_operators), Ok(()));
+    }
+}
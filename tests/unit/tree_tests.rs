@@ -2,12 +2,22 @@ use std::collections::HashMap;
 
 use rstest::*;
 
+use mycoforge::operators::builder::OperatorsBuilder;
+use mycoforge::operators::functions::symbolic::{add, sin};
 use mycoforge::operators::sampler::*;
+use mycoforge::operators::set::Operators;
+use mycoforge::tree::core::errors::TreeError;
+use mycoforge::tree::core::node::*;
 use mycoforge::tree::core::tree::*;
 
+fn to_nodes(arena: &[&str]) -> Vec<Node> {
+    let sampler = sample_sampler();
+    return arena.iter().map(|token| Node::from_token(token, &sampler).expect("Unknown token!")).collect();
+}
+
 #[fixture]
 fn sample_tree_simple() -> TreeGenotype {
-    let arena: Vec<String> = ["+", "*", "2", "x", "-1"].iter().map(|w| w.to_string()).collect();
+    let arena = to_nodes(&["+", "*", "2", "x", "-1"]);
     let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
     children.insert(0, vec![1, 4]);
     children.insert(1, vec![2, 3]);
@@ -17,8 +27,7 @@ fn sample_tree_simple() -> TreeGenotype {
 
 #[fixture]
 fn sample_tree_complex() -> TreeGenotype {
-    let arena: Vec<String> = ["-", "-", "-", "y", "sin", "y", "z", "-", "sin", "+", "x", "y", "y"].iter()
-        .map(|w| w.to_string()).collect();
+    let arena = to_nodes(&["-", "-", "-", "y", "sin", "y", "z", "-", "sin", "+", "x", "y", "y"]);
     let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
     children.insert(0, vec![1, 7]);
     children.insert(1, vec![2, 6]);
@@ -39,9 +48,9 @@ fn sample_trees() -> impl Iterator<Item = TreeGenotype> {
 
 #[fixture]
 fn sample_sampler() -> OperatorSampler {
-    let operators: Vec<String> = ["+", "-", "sin", "x", "y", "z"].iter().map(|&w| w.to_string()).collect();
-    let arity = vec![2, 2, 1, 0, 0, 0];
-    let weights = vec![1.0 / 6.0; 6];
+    let operators: Vec<String> = ["+", "-", "*", "sin", "x", "y", "z"].iter().map(|&w| w.to_string()).collect();
+    let arity = vec![2, 2, 2, 1, 0, 0, 0];
+    let weights = vec![1.0 / 7.0; 7];
 
     let sampler = OperatorSampler::new(operators, arity, weights);
 
@@ -52,13 +61,13 @@ fn sample_sampler() -> OperatorSampler {
 fn test_access() {
     let mut tree = TreeGenotype::default();
 
-    let arena: Vec<String> = ["+", "*", "2", "x", "-1"].iter().map(|w| w.to_string()).collect();
+    let arena = to_nodes(&["+", "*", "2", "x", "-1"]);
     let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
     children.insert(0, vec![1, 4]);
     children.insert(1, vec![2, 3]);
 
     for node in &arena {
-        tree.arena_mut().push(node.to_string());
+        tree.arena_mut().push(*node);
     }
 
     for (&key, value) in children.iter() {
@@ -80,17 +89,47 @@ fn test_subtree(#[case] root: usize, #[case] expected: usize, sample_tree_simple
 }
 
 #[rstest]
-fn test_construct_children(sample_sampler: OperatorSampler) {
+fn test_construct_children() {
     for sample_tree in sample_trees() {
-        let mut tree = TreeGenotype::with_arena(sample_tree.arena().clone());
-        *tree.children_mut() = tree.construct_children(&sample_sampler);
+        let tree = TreeGenotype::with_arena(sample_tree.arena().clone());
 
         assert_eq!(sample_tree.arena(), tree.arena());
+        assert_eq!(sample_tree.children(), &tree.construct_children());
 
         assert!(!tree.children().is_empty());
     }
 }
 
+#[rstest]
+fn test_tokens(sample_sampler: OperatorSampler) {
+    let tokens = ["-", "-", "-", "y", "sin", "y", "z", "-", "sin", "+", "x", "y", "y"];
+    let tree = TreeGenotype::from_tokens(&tokens, &sample_sampler).expect("Failed to create tree!");
+
+    assert_eq!(tree, sample_tree_complex());
+    assert_eq!(tree.to_tokens(&sample_sampler), tokens);
+}
+
+#[rstest]
+#[case(&["+", "x"], NodeError::IncompleteTree(1))]
+#[case(&["x", "y"], NodeError::TrailingTokens(1))]
+#[case(&["+", "x", "w"], NodeError::UnknownSymbol("w".to_string()))]
+fn test_tokens_invalid(#[case] tokens: &[&str], #[case] expected: NodeError, sample_sampler: OperatorSampler) {
+    assert_eq!(TreeGenotype::from_tokens(tokens, &sample_sampler).err(), Some(expected));
+}
+
+#[rstest]
+fn test_variable_named_as_constant() {
+    let operators: Vec<String> = ["+", "1", "1.0"].iter().map(|&w| w.to_string()).collect();
+    let nodes = vec![Node::function("+", 2), Node::Variable(0), Node::Constant(1.0)];
+    let sampler = OperatorSampler::with_nodes(operators, nodes, vec![1.0 / 3.0; 3]);
+
+    let tree = TreeGenotype::from_tokens(&["+", "1", "1.0"], &sampler).expect("Failed to create tree!");
+
+    assert_eq!(tree.arena()[1], Node::Variable(0));
+    assert_eq!(tree.arena()[2], Node::Constant(1.0));
+    assert_eq!(tree.to_tokens(&sampler), vec!["+", "1", "1.0"]);
+}
+
 #[rstest]
 fn test_tree_display(sample_tree_simple: TreeGenotype) {
     let expected_output = "\
@@ -102,3 +141,96 @@ fn test_tree_display(sample_tree_simple: TreeGenotype) {
 ";
     assert_eq!(format!("{}", sample_tree_simple), expected_output);
 }
+
+#[rstest]
+fn test_structure_queries(sample_tree_simple: TreeGenotype) {
+    assert_eq!(sample_tree_simple.depth(), 2);
+    assert_eq!(sample_tree_simple.parent(0), None);
+    assert_eq!(sample_tree_simple.parent(3), Some(1));
+    assert_eq!(sample_tree_simple.node_depth(3), 2);
+    assert_eq!(sample_tree_simple.node_depth(4), 1);
+    assert_eq!(sample_tree_simple.subtree_size(0), 5);
+    assert_eq!(sample_tree_simple.subtree_size(1), 3);
+    assert_eq!(sample_tree_simple.function_count(), 2);
+    assert_eq!(sample_tree_simple.terminal_count(), 3);
+}
+
+#[rstest]
+fn test_traversals(sample_tree_complex: TreeGenotype) {
+    let preorder = sample_tree_complex.preorder().collect::<Vec<usize>>();
+    let postorder = sample_tree_complex.postorder().collect::<Vec<usize>>();
+    let leaves = sample_tree_complex.leaves().collect::<Vec<usize>>();
+
+    assert_eq!(preorder, (0..13).collect::<Vec<usize>>());
+    assert_eq!(postorder, vec![3, 5, 4, 2, 6, 1, 10, 11, 9, 8, 12, 7, 0]);
+    assert_eq!(leaves, vec![3, 5, 6, 10, 11, 12]);
+    assert_eq!(sample_tree_complex.depth(), 4);
+    assert_eq!(sample_tree_complex.node_depth(10), 4);
+}
+
+#[rstest]
+fn test_parents_index(sample_tree_complex: TreeGenotype) {
+    let parents = sample_tree_complex.parents();
+
+    assert_eq!(parents.len(), 13);
+    assert_eq!(parents[0], None);
+    for (index, parent) in parents.iter().enumerate() {
+        assert_eq!(*parent, sample_tree_complex.parent(index), "Wrong parent of node {}", index);
+    }
+}
+
+#[rstest]
+fn test_structure_queries_empty() {
+    let tree = TreeGenotype::default();
+
+    assert_eq!(tree.depth(), 0);
+    assert_eq!(tree.preorder().count(), 0);
+    assert_eq!(tree.postorder().count(), 0);
+    assert_eq!(tree.leaves().count(), 0);
+}
+
+#[fixture]
+fn sample_operators() -> Operators {
+    return OperatorsBuilder::default()
+        .add_function("+", add, 2, 0.5).expect("Failed to add function!")
+        .add_function("sin", sin, 1, 0.5).expect("Failed to add function!")
+        .add_variable("x", 1.0).expect("Failed to add variable!")
+        .build().expect("Failed to build operators!");
+}
+
+#[rstest]
+fn test_validate(sample_operators: Operators) {
+    let arena = vec![Node::function("+", 2), Node::function("sin", 1), Node::Variable(0), Node::Constant(2.0)];
+    let tree = TreeGenotype::with_arena(arena);
+
+    assert_eq!(tree.validate(&sample_operators), Ok(()));
+    assert_eq!(tree.validate_with_sampler(&sample_operators.sampler()), Ok(()));
+}
+
+#[rstest]
+#[case(vec![], TreeError::EmptyTree)]
+#[case(vec![Node::function("*", 2), Node::Variable(0), Node::Variable(0)],
+    TreeError::UnknownSymbol { index: 0, symbol: "*".to_string() })]
+#[case(vec![Node::function("sin", 1), Node::Variable(1)],
+    TreeError::UnknownSymbol { index: 1, symbol: "x1".to_string() })]
+#[case(vec![Node::function("+", 1), Node::Variable(0)],
+    TreeError::ArityMismatch { index: 0, symbol: "+".to_string(), expected: 2, found: 1 })]
+#[case(vec![Node::function("+", 2), Node::Variable(0)], TreeError::MissingOperands(1))]
+#[case(vec![Node::Variable(0), Node::Constant(1.0), Node::Constant(2.0)], TreeError::ExtraNodes(2))]
+fn test_validate_invalid(#[case] arena: Vec<Node>, #[case] expected: TreeError, sample_operators: Operators) {
+    let tree = TreeGenotype::new(arena, HashMap::new());
+
+    assert_eq!(tree.validate(&sample_operators), Err(expected));
+}
+
+#[rstest]
+fn test_validate_inconsistent_children(sample_operators: Operators) {
+    let arena = vec![Node::function("+", 2), Node::Variable(0), Node::Constant(1.0)];
+    let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+    children.insert(0, vec![2, 1]);
+    let tree = TreeGenotype::new(arena, children);
+
+    assert_eq!(tree.validate(&sample_operators),
+        Err(TreeError::InconsistentChildren { index: 0, expected: vec![1, 2], found: vec![2, 1] })
+    );
+}